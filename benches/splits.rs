@@ -0,0 +1,75 @@
+//! Time and allocation counts of ordering synthetic pages of word boxes
+//!
+//! ```text
+//! cargo bench --bench splits
+//! ```
+
+use std::time::Instant;
+
+use xycut_plus_plus::profile::CountingAllocator;
+use xycut_plus_plus::{Element, SemanticLabel, XYCutConfig, XYCutPlusPlus};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Words of `columns` columns of `lines` lines each, `words` words per line,
+/// with a full-width title on top; words and lines are far enough apart to be cut
+fn word_page(columns: usize, lines: usize, words: usize) -> (Vec<Element>, f32, f32) {
+    let (column_width, gutter, line_height) = (600.0, 40.0, 32.0);
+    let word_width = column_width / words as f32;
+    let mut elements = vec![Element::new(
+        0,
+        20.0,
+        20.0,
+        20.0 + columns as f32 * (column_width + gutter) - gutter,
+        60.0,
+        SemanticLabel::HorizontalTitle,
+    )];
+    for column in 0..columns {
+        let x = 20.0 + column as f32 * (column_width + gutter);
+        for line in 0..lines {
+            let y = 80.0 + line as f32 * line_height;
+            for word in 0..words {
+                let x1 = x + word as f32 * word_width;
+                elements.push(Element::new(
+                    elements.len(),
+                    x1,
+                    y,
+                    x1 + word_width - 20.0,
+                    y + line_height - 18.0,
+                    SemanticLabel::Regular,
+                ));
+            }
+        }
+    }
+    let width = 40.0 + columns as f32 * (column_width + gutter);
+    let height = 100.0 + lines as f32 * line_height;
+    (elements, width, height)
+}
+
+fn main() {
+    let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+    println!(
+        "{:<22}{:>9}{:>13}{:>14}",
+        "page", "elements", "time", "allocations"
+    );
+    for (columns, lines, words) in [(1, 40, 1), (2, 60, 8), (3, 80, 10), (2, 200, 12)] {
+        let (elements, width, height) = word_page(columns, lines, words);
+        let runs = (20_000 / elements.len()).clamp(3, 200);
+
+        let start = Instant::now();
+        for _ in 0..runs {
+            xycut.compute_order(&elements, 0.0, 0.0, width, height);
+        }
+        let elapsed = start.elapsed() / runs as u32;
+        let (_, profile) = xycut.compute_order_profiled(&elements, 0.0, 0.0, width, height);
+
+        println!(
+            "{:<22}{:>9}{:>13.2?}{:>14}",
+            format!("{columns} col × {lines} × {words}"),
+            elements.len(),
+            elapsed,
+            profile.allocations.unwrap_or_default()
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use xycut_plus_plus::{
+    group_paragraphs, BoundingBox, ParagraphConfig, SemanticLabel, XYCutConfig, XYCutPlusPlus,
+};
+
+#[derive(Clone)]
+struct Element {
+    id: usize,
+    x1: f32,
+    y1: f32,
+    x2: f32,
+    y2: f32,
+    label: SemanticLabel,
+}
+
+impl Element {
+    fn new(id: usize, x1: f32, y1: f32, x2: f32, y2: f32, label: SemanticLabel) -> Self {
+        Self {
+            id,
+            x1,
+            y1,
+            x2,
+            y2,
+            label,
+        }
+    }
+}
+
+impl BoundingBox for Element {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn center(&self) -> (f32, f32) {
+        ((self.x1 + self.x2) / 2.0, (self.y1 + self.y2) / 2.0)
+    }
+
+    fn bounds(&self) -> (f32, f32, f32, f32) {
+        (self.x1, self.y1, self.x2, self.y2)
+    }
+
+    fn iou(&self, other: &Self) -> f32 {
+        let x_overlap = (self.x2.min(other.x2) - self.x1.max(other.x1)).max(0.0);
+        let y_overlap = (self.y2.min(other.y2) - self.y1.max(other.y1)).max(0.0);
+        let intersection = x_overlap * y_overlap;
+        let union = (self.x2 - self.x1) * (self.y2 - self.y1)
+            + (other.x2 - other.x1) * (other.y2 - other.y1)
+            - intersection;
+        if union > 0.0 {
+            intersection / union
+        } else {
+            0.0
+        }
+    }
+
+    fn should_mask(&self) -> bool {
+        matches!(
+            self.label,
+            SemanticLabel::HorizontalTitle | SemanticLabel::VerticalTitle | SemanticLabel::Vision
+        )
+    }
+
+    fn semantic_label(&self) -> SemanticLabel {
+        self.label
+    }
+}
+
+fn main() {
+    use SemanticLabel::*;
+
+    // A two-column page: a title spanning both columns, then text lines in each column
+    let elements = vec![
+        Element::new(0, 50.0, 40.0, 550.0, 70.0, HorizontalTitle),
+        Element::new(1, 60.0, 100.0, 280.0, 115.0, Regular),
+        Element::new(2, 50.0, 118.0, 280.0, 133.0, Regular),
+        Element::new(3, 50.0, 136.0, 200.0, 151.0, Regular),
+        Element::new(4, 60.0, 160.0, 280.0, 175.0, Regular),
+        Element::new(5, 50.0, 178.0, 280.0, 193.0, Regular),
+        Element::new(6, 320.0, 100.0, 550.0, 115.0, Regular),
+        Element::new(7, 320.0, 118.0, 550.0, 133.0, Regular),
+        Element::new(8, 320.0, 136.0, 480.0, 151.0, Regular),
+    ];
+
+    let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+    let order = xycut.compute_order(&elements, 0.0, 0.0, 600.0, 800.0);
+    println!("Reading order: {:?}", order);
+
+    let paragraphs = group_paragraphs(&elements, &order, &ParagraphConfig::default());
+    for (i, paragraph) in paragraphs.iter().enumerate() {
+        println!("Paragraph {}: {:?}", i, paragraph);
+    }
+}
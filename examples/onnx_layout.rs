@@ -0,0 +1,123 @@
+//! Image in, ordered regions out: run a layout detector and order its detections
+//!
+//! ```text
+//! ORT_DYLIB_PATH=/path/to/libonnxruntime.so \
+//!     cargo run --release --example onnx_layout --features onnx -- doclayout_yolo.onnx page.png
+//! ```
+//!
+//! The model takes a `(1, 3, size, size)` RGB tensor scaled to 0–1 and returns
+//! post-NMS detections as rows of `[x1, y1, x2, y2, score, class]` in input pixels,
+//! shaped `(1, n, 6)` or `(n, 6)`; DocLayout-YOLO and other YOLOv10-style exports do.
+//! Optional arguments pick the class preset (`doclayout_yolo`, `pp_doclayout`,
+//! `doclaynet`, `layoutlmv3`), the input size (default 1024) and the score threshold
+//! (default 0.25). Regions are printed in reading order with their class and box in
+//! image pixels.
+
+use std::error::Error;
+
+use image::imageops::FilterType;
+use ndarray::{Array2, Array4, Axis};
+use ort::session::Session;
+use ort::value::TensorRef;
+use xycut_plus_plus::{LabelMap, XYCutConfig, XYCutPlusPlus};
+
+/// One detection in image pixels
+struct Detection {
+    bounds: [f32; 4],
+    score: f32,
+    class_id: usize,
+}
+
+fn label_map(preset: &str) -> Result<LabelMap, Box<dyn Error>> {
+    Ok(match preset {
+        "doclayout_yolo" => LabelMap::doclayout_yolo(),
+        "pp_doclayout" => LabelMap::pp_doclayout(),
+        "doclaynet" => LabelMap::doclaynet(),
+        "layoutlmv3" => LabelMap::layoutlmv3(),
+        other => return Err(format!("unknown class preset {other}").into()),
+    })
+}
+
+fn detect(
+    session: &mut Session,
+    page: &image::RgbImage,
+    size: u32,
+    threshold: f32,
+) -> Result<Vec<Detection>, Box<dyn Error>> {
+    let resized = image::imageops::resize(page, size, size, FilterType::Triangle);
+    let input = Array4::from_shape_fn((1, 3, size as usize, size as usize), |(_, c, y, x)| {
+        f32::from(resized.get_pixel(x as u32, y as u32)[c]) / 255.0
+    });
+    let outputs = session.run(ort::inputs![TensorRef::from_array_view(&input)?])?;
+    let output = outputs[0].try_extract_array::<f32>()?;
+    let rows = match output.ndim() {
+        3 => output.index_axis(Axis(0), 0),
+        2 => output.view(),
+        _ => return Err(format!("unexpected output shape {:?}", output.shape()).into()),
+    };
+    if rows.shape()[1] < 6 {
+        return Err(format!("unexpected output shape {:?}", output.shape()).into());
+    }
+
+    let scale_x = page.width() as f32 / size as f32;
+    let scale_y = page.height() as f32 / size as f32;
+    Ok(rows
+        .outer_iter()
+        .filter(|row| row[4] >= threshold)
+        .map(|row| Detection {
+            bounds: [
+                row[0] * scale_x,
+                row[1] * scale_y,
+                row[2] * scale_x,
+                row[3] * scale_y,
+            ],
+            score: row[4],
+            class_id: row[5].max(0.0) as usize,
+        })
+        .collect())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [model, page, ..] = args.as_slice() else {
+        return Err(
+            "usage: onnx_layout <model.onnx> <page image> [preset] [size] [threshold]".into(),
+        );
+    };
+    let labels = label_map(args.get(2).map_or("doclayout_yolo", String::as_str))?;
+    let size: u32 = args.get(3).map_or(Ok(1024), |size| size.parse())?;
+    let threshold: f32 = args
+        .get(4)
+        .map_or(Ok(0.25), |threshold| threshold.parse())?;
+
+    let page = image::open(page)?.to_rgb8();
+    let mut session = Session::builder()?.commit_from_file(model)?;
+    let detections = detect(&mut session, &page, size, threshold)?;
+
+    let boxes = Array2::from_shape_fn((detections.len(), 4), |(i, j)| detections[i].bounds[j]);
+    let classes: Vec<_> = detections
+        .iter()
+        .map(|detection| labels.map_class_id(detection.class_id))
+        .collect();
+    let order = XYCutPlusPlus::new(XYCutConfig::default()).compute_order_array(
+        boxes.view(),
+        &classes,
+        0.0,
+        0.0,
+        page.width() as f32,
+        page.height() as f32,
+    )?;
+
+    for (rank, &row) in order.iter().enumerate() {
+        let Detection {
+            bounds: [x1, y1, x2, y2],
+            score,
+            class_id,
+        } = detections[row];
+        println!(
+            "{rank:>3}  class {class_id:<2} {:<16} {score:.2}  [{x1:.0}, {y1:.0}, {x2:.0}, {y2:.0}]",
+            format!("{:?}", classes[row])
+        );
+    }
+    Ok(())
+}
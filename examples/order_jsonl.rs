@@ -0,0 +1,102 @@
+//! Order a JSON Lines stream of pages, one result line per page
+//!
+//! ```text
+//! cargo run --release --example order_jsonl --features io-json < pages.jsonl > orders.jsonl
+//! ```
+//!
+//! Each input line is one page in the layout format of `tests/fixtures` and
+//! `export::to_annotation_json`: `{"page": [x1, y1, x2, y2], "elements": [{"id", "bbox":
+//! [x1, y1, x2, y2], "label"}]}`. Each output line is `{"order": [ids...]}`, carrying the
+//! page's `name` over when it has one, or `{"error": "..."}` for a page that cannot be
+//! read. Pages are read, ordered and written one at a time, so memory stays bounded by
+//! the largest page however long the stream is.
+
+use std::error::Error;
+use std::io::{self, BufRead, BufWriter, Write};
+
+use serde_json::{json, Value};
+use xycut_plus_plus::{Element, SemanticLabel, XYCutConfig, XYCutPlusPlus};
+
+/// Page bounds `(x1, y1, x2, y2)`
+type Page = (f32, f32, f32, f32);
+
+fn parse_page(layout: &Value) -> Result<(Page, Vec<Element>), Box<dyn Error>> {
+    let coords = |value: &Value| -> Result<Vec<f32>, Box<dyn Error>> {
+        let coords: Option<Vec<f32>> = value
+            .as_array()
+            .filter(|values| values.len() == 4)
+            .and_then(|values| {
+                values
+                    .iter()
+                    .map(|v| v.as_f64().map(|v| v as f32))
+                    .collect()
+            });
+        Ok(coords.ok_or("expected [x1, y1, x2, y2]")?)
+    };
+
+    let mut elements = Vec::new();
+    for element in layout["elements"].as_array().ok_or("missing elements")? {
+        let b = coords(&element["bbox"])?;
+        let label = match element["label"].as_str().unwrap_or("Regular") {
+            "CrossLayout" => SemanticLabel::CrossLayout,
+            "HorizontalTitle" => SemanticLabel::HorizontalTitle,
+            "VerticalTitle" => SemanticLabel::VerticalTitle,
+            "Vision" => SemanticLabel::Vision,
+            "Formula" => SemanticLabel::Formula,
+            "Regular" => SemanticLabel::Regular,
+            other => return Err(format!("unknown label {other}").into()),
+        };
+        let id = element["id"].as_u64().ok_or("missing id")? as usize;
+        elements.push(Element::new(id, b[0], b[1], b[2], b[3], label));
+    }
+
+    let page = match coords(&layout["page"]) {
+        Ok(p) => (p[0], p[1], p[2], p[3]),
+        Err(_) => elements.iter().fold(
+            (
+                f32::INFINITY,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                f32::NEG_INFINITY,
+            ),
+            |(x1, y1, x2, y2), e| (x1.min(e.x1), y1.min(e.y1), x2.max(e.x2), y2.max(e.y2)),
+        ),
+    };
+    Ok((page, elements))
+}
+
+fn order_line(xycut: &XYCutPlusPlus, line: &str) -> Result<Value, Box<dyn Error>> {
+    let layout: Value = serde_json::from_str(line)?;
+    let ((x1, y1, x2, y2), elements) = parse_page(&layout)?;
+    let order = xycut.compute_order(&elements, x1, y1, x2, y2);
+
+    let mut result = json!({ "order": order });
+    if let Some(name) = layout.get("name").filter(|name| name.is_string()) {
+        result["name"] = name.clone();
+    }
+    Ok(result)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut failed = 0;
+
+    for (index, line) in io::stdin().lock().lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let result = order_line(&xycut, &line).unwrap_or_else(|error| {
+            failed += 1;
+            json!({ "error": format!("line {}: {error}", index + 1) })
+        });
+        writeln!(out, "{result}")?;
+    }
+    out.flush()?;
+
+    if failed > 0 {
+        return Err(format!("unreadable pages: {failed}").into());
+    }
+    Ok(())
+}
@@ -0,0 +1,483 @@
+//! Extract the text of a PDF in reading order
+//!
+//! ```text
+//! cargo run --example pdf_text --features pdf -- tests/fixtures/two_column.pdf
+//! ```
+//!
+//! Text spans are read from each page's content stream with `lopdf`: every text-showing
+//! operator becomes a span whose box follows the text and graphics matrices, with glyph
+//! advances from the font's `Widths` (`W` for CID fonts). Spans continuing the same
+//! baseline are joined into lines, the lines are ordered with `compute_order` and the
+//! page is printed as paragraphs with `export::to_plain_text`.
+//!
+//! The extraction is deliberately small: no Type3 fonts, vertical writing or
+//! `Differences` encodings. It is an example of feeding a real extractor into the
+//! crate, not a PDF library.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use lopdf::{Dictionary, Document, Encoding, Object, ObjectId};
+use xycut_plus_plus::export::to_plain_text;
+use xycut_plus_plus::{
+    BoundingBox, Element, ParagraphConfig, SemanticLabel, TextContent, XYCutConfig, XYCutPlusPlus,
+};
+
+/// A line of text with its box in top-down page coordinates
+#[derive(Debug, Clone)]
+struct Line {
+    element: Element,
+    text: String,
+    font_size: f32,
+}
+
+impl BoundingBox for Line {
+    fn id(&self) -> usize {
+        self.element.id()
+    }
+
+    fn center(&self) -> (f32, f32) {
+        self.element.center()
+    }
+
+    fn bounds(&self) -> (f32, f32, f32, f32) {
+        self.element.bounds()
+    }
+
+    fn iou(&self, other: &Self) -> f32 {
+        self.element.iou(&other.element)
+    }
+
+    fn should_mask(&self) -> bool {
+        self.element.should_mask()
+    }
+
+    fn semantic_label(&self) -> SemanticLabel {
+        self.element.semantic_label()
+    }
+}
+
+impl TextContent for Line {
+    fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Affine matrix `[a, b, c, d, e, f]` as in PDF operators
+type Matrix = [f32; 6];
+
+const IDENTITY: Matrix = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+/// `m` applied first, then `n`
+fn multiply(m: Matrix, n: Matrix) -> Matrix {
+    [
+        m[0] * n[0] + m[1] * n[2],
+        m[0] * n[1] + m[1] * n[3],
+        m[2] * n[0] + m[3] * n[2],
+        m[2] * n[1] + m[3] * n[3],
+        m[4] * n[0] + m[5] * n[2] + n[4],
+        m[4] * n[1] + m[5] * n[3] + n[5],
+    ]
+}
+
+fn apply(m: Matrix, (x, y): (f32, f32)) -> (f32, f32) {
+    (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5])
+}
+
+/// What the extractor needs from a font resource
+struct Font<'a> {
+    encoding: Option<Encoding<'a>>,
+    /// Glyph widths in thousandths of text space by character code
+    widths: BTreeMap<u32, f32>,
+    default_width: f32,
+    /// CID fonts use two-byte codes
+    two_byte: bool,
+}
+
+impl<'a> Font<'a> {
+    fn load(dict: &'a Dictionary, doc: &'a Document) -> Self {
+        let number = |object: &Object| doc.dereference(object).ok()?.1.as_float().ok();
+        let mut widths = BTreeMap::new();
+        let two_byte = dict.get(b"Subtype").and_then(Object::as_name).ok() == Some(b"Type0");
+        let mut default_width = if two_byte { 1000.0 } else { 500.0 };
+
+        if two_byte {
+            let descendant = dict
+                .get_deref(b"DescendantFonts", doc)
+                .and_then(Object::as_array)
+                .ok()
+                .and_then(|fonts| fonts.first())
+                .and_then(|font| doc.dereference(font).ok())
+                .and_then(|(_, font)| font.as_dict().ok());
+            if let Some(descendant) = descendant {
+                if let Some(dw) = descendant.get(b"DW").ok().and_then(number) {
+                    default_width = dw;
+                }
+                let w = descendant.get_deref(b"W", doc).and_then(Object::as_array);
+                let mut items = w.map(|w| w.iter()).into_iter().flatten().peekable();
+                // `c [w1 w2 ...]` or `c_first c_last w`
+                while let Some(first) = items.next().and_then(number) {
+                    match items.next().map(|o| doc.dereference(o).map(|(_, o)| o)) {
+                        Some(Ok(Object::Array(list))) => {
+                            for (i, w) in list.iter().filter_map(number).enumerate() {
+                                widths.insert(first as u32 + i as u32, w);
+                            }
+                        }
+                        Some(Ok(last)) => {
+                            let (Some(last), Some(w)) =
+                                (last.as_float().ok(), items.next().and_then(number))
+                            else {
+                                break;
+                            };
+                            for code in first as u32..=last as u32 {
+                                widths.insert(code, w);
+                            }
+                        }
+                        _ => break,
+                    }
+                }
+            }
+        } else if let Ok(list) = dict.get_deref(b"Widths", doc).and_then(Object::as_array) {
+            let first = dict.get(b"FirstChar").ok().and_then(number).unwrap_or(0.0) as u32;
+            for (i, w) in list.iter().filter_map(number).enumerate() {
+                widths.insert(first + i as u32, w);
+            }
+        }
+
+        Self {
+            encoding: dict.get_font_encoding(doc).ok(),
+            widths,
+            default_width,
+            two_byte,
+        }
+    }
+
+    fn codes(&self, bytes: &[u8]) -> Vec<u32> {
+        if self.two_byte {
+            bytes
+                .chunks(2)
+                .map(|pair| pair.iter().fold(0, |code, &b| code << 8 | u32::from(b)))
+                .collect()
+        } else {
+            bytes.iter().map(|&b| u32::from(b)).collect()
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> String {
+        self.encoding
+            .as_ref()
+            .and_then(|encoding| Document::decode_text(encoding, bytes).ok())
+            .unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+/// Text state of the content stream interpreter
+struct TextState {
+    matrix: Matrix,
+    line_matrix: Matrix,
+    font: Option<Vec<u8>>,
+    size: f32,
+    char_spacing: f32,
+    word_spacing: f32,
+    scale: f32,
+    leading: f32,
+    rise: f32,
+}
+
+/// A shown string: its box corners in PDF user space, text and font size
+struct Span {
+    bounds: (f32, f32, f32, f32),
+    text: String,
+    size: f32,
+}
+
+fn extract_spans(doc: &Document, page_id: ObjectId) -> Result<Vec<Span>, Box<dyn Error>> {
+    let font_dicts = doc.get_page_fonts(page_id)?;
+    let fonts: BTreeMap<&Vec<u8>, Font> = font_dicts
+        .iter()
+        .map(|(name, dict)| (name, Font::load(dict, doc)))
+        .collect();
+    let content = doc.get_and_decode_page_content(page_id)?;
+
+    let mut ctm = IDENTITY;
+    let mut saved = Vec::new();
+    let mut state = TextState {
+        matrix: IDENTITY,
+        line_matrix: IDENTITY,
+        font: None,
+        size: 0.0,
+        char_spacing: 0.0,
+        word_spacing: 0.0,
+        scale: 1.0,
+        leading: 0.0,
+        rise: 0.0,
+    };
+    let mut spans = Vec::new();
+
+    for operation in &content.operations {
+        let numbers: Vec<f32> = operation
+            .operands
+            .iter()
+            .filter_map(|o| o.as_float().ok())
+            .collect();
+        let number = |i: usize| numbers.get(i).copied().unwrap_or_default();
+        let move_line = |tx: f32, ty: f32, state: &mut TextState| {
+            state.line_matrix = multiply([1.0, 0.0, 0.0, 1.0, tx, ty], state.line_matrix);
+            state.matrix = state.line_matrix;
+        };
+
+        let shown: Vec<&Object> = match operation.operator.as_str() {
+            "q" => {
+                saved.push(ctm);
+                continue;
+            }
+            "Q" => {
+                ctm = saved.pop().unwrap_or(IDENTITY);
+                continue;
+            }
+            "cm" if numbers.len() == 6 => {
+                let m = [
+                    number(0),
+                    number(1),
+                    number(2),
+                    number(3),
+                    number(4),
+                    number(5),
+                ];
+                ctm = multiply(m, ctm);
+                continue;
+            }
+            "BT" => {
+                state.matrix = IDENTITY;
+                state.line_matrix = IDENTITY;
+                continue;
+            }
+            "Tf" => {
+                state.font = operation
+                    .operands
+                    .first()
+                    .and_then(|o| o.as_name().ok())
+                    .map(<[u8]>::to_vec);
+                state.size = number(0);
+                continue;
+            }
+            "Tc" => {
+                state.char_spacing = number(0);
+                continue;
+            }
+            "Tw" => {
+                state.word_spacing = number(0);
+                continue;
+            }
+            "Tz" => {
+                state.scale = number(0) / 100.0;
+                continue;
+            }
+            "TL" => {
+                state.leading = number(0);
+                continue;
+            }
+            "Ts" => {
+                state.rise = number(0);
+                continue;
+            }
+            "Td" => {
+                move_line(number(0), number(1), &mut state);
+                continue;
+            }
+            "TD" => {
+                state.leading = -number(1);
+                move_line(number(0), number(1), &mut state);
+                continue;
+            }
+            "Tm" if numbers.len() == 6 => {
+                state.line_matrix = [
+                    number(0),
+                    number(1),
+                    number(2),
+                    number(3),
+                    number(4),
+                    number(5),
+                ];
+                state.matrix = state.line_matrix;
+                continue;
+            }
+            "T*" => {
+                move_line(0.0, -state.leading, &mut state);
+                continue;
+            }
+            "Tj" => operation.operands.iter().collect(),
+            "'" => {
+                move_line(0.0, -state.leading, &mut state);
+                operation.operands.iter().collect()
+            }
+            "\"" => {
+                state.word_spacing = number(0);
+                state.char_spacing = number(1);
+                move_line(0.0, -state.leading, &mut state);
+                operation.operands.iter().skip(2).collect()
+            }
+            "TJ" => operation
+                .operands
+                .first()
+                .and_then(|o| o.as_array().ok())
+                .map(|items| items.iter().collect())
+                .unwrap_or_default(),
+            _ => continue,
+        };
+        let Some(font) = state.font.as_ref().and_then(|name| fonts.get(name)) else {
+            continue;
+        };
+
+        let start = state.matrix;
+        let mut advance = 0.0;
+        let mut text = String::new();
+        for object in shown {
+            match object {
+                Object::String(bytes, _) => {
+                    for code in font.codes(bytes) {
+                        let width = font
+                            .widths
+                            .get(&code)
+                            .copied()
+                            .unwrap_or(font.default_width);
+                        let spacing = if code == 32 && !font.two_byte {
+                            state.word_spacing
+                        } else {
+                            0.0
+                        };
+                        advance += (width / 1000.0 * state.size + state.char_spacing + spacing)
+                            * state.scale;
+                    }
+                    text.push_str(&font.decode(bytes));
+                }
+                other => {
+                    // TJ adjustments move left by thousandths of the font size
+                    let adjustment = other.as_float().unwrap_or_default();
+                    advance -= adjustment / 1000.0 * state.size * state.scale;
+                    if adjustment < -250.0 {
+                        text.push(' ');
+                    }
+                }
+            }
+        }
+        state.matrix = multiply([1.0, 0.0, 0.0, 1.0, advance, 0.0], state.matrix);
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let to_page = multiply(start, ctm);
+        let (bottom, top) = (state.rise - 0.2 * state.size, state.rise + 0.8 * state.size);
+        let corners = [(0.0, bottom), (advance, bottom), (0.0, top), (advance, top)]
+            .map(|corner| apply(to_page, corner));
+        let bounds = corners.iter().fold(
+            (
+                f32::INFINITY,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                f32::NEG_INFINITY,
+            ),
+            |(x1, y1, x2, y2), &(x, y)| (x1.min(x), y1.min(y), x2.max(x), y2.max(y)),
+        );
+        let size = state.size * (to_page[2].hypot(to_page[3]));
+        spans.push(Span { bounds, text, size });
+    }
+    Ok(spans)
+}
+
+/// Join consecutive spans on the same baseline into lines, flipping y to grow downwards
+fn join_lines(spans: Vec<Span>, media_box: (f32, f32, f32, f32)) -> Vec<Line> {
+    let (x_min, _, _, y_max) = media_box;
+    let mut lines: Vec<Line> = Vec::new();
+    for span in spans {
+        let (x1, y1, x2, y2) = span.bounds;
+        let element = Element::new(
+            lines.len(),
+            x1 - x_min,
+            y_max - y2,
+            x2 - x_min,
+            y_max - y1,
+            SemanticLabel::Regular,
+        );
+        if let Some(line) = lines.last_mut() {
+            let same_baseline = (line.element.y2 - element.y2).abs() < 0.3 * span.size;
+            let gap = element.x1 - line.element.x2;
+            if same_baseline && gap > -0.5 * span.size && gap < 1.5 * span.size {
+                if gap > 0.15 * span.size && !line.text.ends_with(' ') {
+                    line.text.push(' ');
+                }
+                line.text.push_str(&span.text);
+                line.element.extend(&element);
+                line.font_size = line.font_size.max(span.size);
+                continue;
+            }
+        }
+        lines.push(Line {
+            element,
+            text: span.text,
+            font_size: span.size,
+        });
+    }
+    lines
+}
+
+/// The page's `MediaBox`, inherited from the page tree if needed
+fn media_box(doc: &Document, page_id: ObjectId) -> (f32, f32, f32, f32) {
+    let mut node = doc.get_dictionary(page_id).ok();
+    while let Some(dict) = node {
+        if let Ok(values) = dict.get_deref(b"MediaBox", doc).and_then(Object::as_array) {
+            let values: Vec<f32> = values
+                .iter()
+                .filter_map(|o| doc.dereference(o).ok()?.1.as_float().ok())
+                .collect();
+            if let [x1, y1, x2, y2] = values[..] {
+                return (x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2));
+            }
+        }
+        node = dict
+            .get(b"Parent")
+            .and_then(Object::as_reference)
+            .and_then(|parent| doc.get_dictionary(parent))
+            .ok();
+    }
+    (0.0, 0.0, 612.0, 792.0)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or("usage: pdf_text <document.pdf>")?;
+    let doc = Document::load(path)?;
+    let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+
+    for (number, page_id) in doc.get_pages() {
+        let media_box = media_box(&doc, page_id);
+        let mut lines = join_lines(extract_spans(&doc, page_id)?, media_box);
+
+        // Lines set noticeably larger than the page's body text are titles
+        let mut sizes: Vec<f32> = lines.iter().map(|line| line.font_size).collect();
+        sizes.sort_by(f32::total_cmp);
+        let body = sizes.get(sizes.len() / 2).copied().unwrap_or_default();
+        for line in &mut lines {
+            if line.font_size > 1.3 * body {
+                line.element = Element::new(
+                    line.element.id,
+                    line.element.x1,
+                    line.element.y1,
+                    line.element.x2,
+                    line.element.y2,
+                    SemanticLabel::HorizontalTitle,
+                );
+            }
+        }
+
+        let (x1, y1, x2, y2) = media_box;
+        let order = xycut.compute_order(&lines, 0.0, 0.0, x2 - x1, y2 - y1);
+        println!("--- page {number} ---");
+        println!(
+            "{}\n",
+            to_plain_text(&lines, &order, &ParagraphConfig::default())
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,318 @@
+//! Terminal viewer for tuning `XYCutConfig` on one page
+//!
+//! ```text
+//! cargo run --example viewer --features viewer -- tests/fixtures/newspaper.json
+//! ```
+//!
+//! The page is a layout JSON: `{"page": [x1, y1, x2, y2], "elements": [{"id", "bbox":
+//! [x1, y1, x2, y2], "label"}]}` with labels named after `SemanticLabel` variants (the
+//! format of `tests/fixtures` and `export::to_annotation_json`). ↑/↓ pick a setting,
+//! ←/→ change it, `r` resets and `q` quits; the order is recomputed on every change.
+
+use std::error::Error;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::symbols::Marker;
+use ratatui::text::Line as TextLine;
+use ratatui::widgets::canvas::{Canvas, Line, Rectangle};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use serde_json::Value;
+use xycut_plus_plus::{Algorithm, Element, SemanticLabel, XYCutConfig, XYCutPlusPlus};
+
+/// One adjustable setting of `XYCutConfig`
+enum Knob {
+    Number {
+        name: &'static str,
+        step: f32,
+        range: (f32, f32),
+        field: fn(&mut XYCutConfig) -> &mut f32,
+    },
+    Flag {
+        name: &'static str,
+        field: fn(&mut XYCutConfig) -> &mut bool,
+    },
+    Algorithm,
+}
+
+impl Knob {
+    fn name(&self) -> &'static str {
+        match self {
+            Knob::Number { name, .. } | Knob::Flag { name, .. } => name,
+            Knob::Algorithm => "algorithm",
+        }
+    }
+
+    fn value(&self, config: &mut XYCutConfig) -> String {
+        match self {
+            Knob::Number { field, .. } => format!("{:.2}", field(config)),
+            Knob::Flag { field, .. } => field(config).to_string(),
+            Knob::Algorithm => format!("{:?}", config.algorithm),
+        }
+    }
+
+    /// Step the setting up (`direction > 0`) or down
+    fn adjust(&self, config: &mut XYCutConfig, direction: f32) {
+        match self {
+            Knob::Number {
+                step, range, field, ..
+            } => {
+                let value = field(config);
+                *value = (*value + step * direction).clamp(range.0, range.1);
+            }
+            Knob::Flag { field, .. } => {
+                let value = field(config);
+                *value = !*value;
+            }
+            Knob::Algorithm => {
+                config.algorithm = match config.algorithm {
+                    Algorithm::ClassicXYCut => Algorithm::XYCutPlusPlus,
+                    _ => Algorithm::ClassicXYCut,
+                }
+            }
+        }
+    }
+}
+
+const KNOBS: &[Knob] = &[
+    Knob::Algorithm,
+    Knob::Number {
+        name: "min_cut_threshold",
+        step: 1.0,
+        range: (0.0, 200.0),
+        field: |c| &mut c.min_cut_threshold,
+    },
+    Knob::Number {
+        name: "histogram_resolution_scale",
+        step: 0.05,
+        range: (0.05, 4.0),
+        field: |c| &mut c.histogram_resolution_scale,
+    },
+    Knob::Number {
+        name: "same_row_tolerance",
+        step: 1.0,
+        range: (0.0, 100.0),
+        field: |c| &mut c.same_row_tolerance,
+    },
+    Knob::Number {
+        name: "cut_overlap_tolerance",
+        step: 0.01,
+        range: (0.0, 0.5),
+        field: |c| &mut c.cut_overlap_tolerance,
+    },
+    Knob::Number {
+        name: "background_coverage",
+        step: 0.05,
+        range: (0.0, 1.0),
+        field: |c| &mut c.background_coverage,
+    },
+    Knob::Flag {
+        name: "region_subtraction",
+        field: |c| &mut c.region_subtraction,
+    },
+    Knob::Flag {
+        name: "promote_straddlers",
+        field: |c| &mut c.promote_straddlers,
+    },
+    Knob::Flag {
+        name: "repair_inversions",
+        field: |c| &mut c.repair_inversions,
+    },
+    Knob::Flag {
+        name: "deskew",
+        field: |c| &mut c.deskew,
+    },
+];
+
+struct App {
+    page: Page,
+    elements: Vec<Element>,
+    config: XYCutConfig,
+    order: Vec<usize>,
+    selected: ListState,
+}
+
+impl App {
+    fn recompute(&mut self) {
+        let (x1, y1, x2, y2) = self.page;
+        self.order =
+            XYCutPlusPlus::new(self.config.clone()).compute_order(&self.elements, x1, y1, x2, y2);
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [page_area, side] =
+            Layout::horizontal([Constraint::Min(40), Constraint::Length(44)]).areas(frame.area());
+        let [knobs_area, order_area] = Layout::vertical([
+            Constraint::Length(KNOBS.len() as u16 + 2),
+            Constraint::Min(3),
+        ])
+        .areas(side);
+
+        let (x_min, y_min, x_max, y_max) = self.page;
+        // The canvas y axis points up; flip page coordinates so the top is at the top
+        let flip = |y: f32| f64::from(y_max - y + y_min);
+        let centers: Vec<(f64, f64)> = self
+            .order
+            .iter()
+            .filter_map(|id| self.elements.iter().find(|e| e.id == *id))
+            .map(|e| (f64::from(e.x1 + e.x2) / 2.0, flip((e.y1 + e.y2) / 2.0)))
+            .collect();
+        let canvas = Canvas::default()
+            .block(Block::bordered().title("page"))
+            .marker(Marker::Braille)
+            .x_bounds([f64::from(x_min), f64::from(x_max)])
+            .y_bounds([f64::from(y_min), f64::from(y_max)])
+            .paint(|ctx| {
+                for e in &self.elements {
+                    ctx.draw(&Rectangle {
+                        x: f64::from(e.x1),
+                        y: flip(e.y2),
+                        width: f64::from(e.x2 - e.x1),
+                        height: f64::from(e.y2 - e.y1),
+                        color: label_color(e.label),
+                    });
+                }
+                for pair in centers.windows(2) {
+                    ctx.draw(&Line {
+                        x1: pair[0].0,
+                        y1: pair[0].1,
+                        x2: pair[1].0,
+                        y2: pair[1].1,
+                        color: Color::Red,
+                    });
+                }
+                ctx.layer();
+                for (rank, &(x, y)) in centers.iter().enumerate() {
+                    ctx.print(x, y, rank.to_string().bold());
+                }
+            });
+        frame.render_widget(canvas, page_area);
+
+        let items: Vec<ListItem> = KNOBS
+            .iter()
+            .map(|knob| {
+                ListItem::new(format!(
+                    "{:<27}{}",
+                    knob.name(),
+                    knob.value(&mut self.config)
+                ))
+            })
+            .collect();
+        let knobs = List::new(items)
+            .block(Block::bordered().title("config  ↑↓ ←→  r reset  q quit"))
+            .highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(knobs, knobs_area, &mut self.selected);
+
+        let order: Vec<String> = self.order.iter().map(|id| id.to_string()).collect();
+        let order = Paragraph::new(TextLine::from(order.join(" → ")))
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title("order (ids)"));
+        frame.render_widget(order, order_area);
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let knob = &KNOBS[self.selected.selected().unwrap_or(0).min(KNOBS.len() - 1)];
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up => self.selected.select_previous(),
+                KeyCode::Down => self.selected.select_next(),
+                KeyCode::Left => knob.adjust(&mut self.config, -1.0),
+                KeyCode::Right => knob.adjust(&mut self.config, 1.0),
+                KeyCode::Char('r') => self.config = XYCutConfig::default(),
+                _ => continue,
+            }
+            self.recompute();
+        }
+    }
+}
+
+fn label_color(label: SemanticLabel) -> Color {
+    match label {
+        SemanticLabel::CrossLayout => Color::Magenta,
+        SemanticLabel::HorizontalTitle | SemanticLabel::VerticalTitle => Color::Yellow,
+        SemanticLabel::Vision => Color::Green,
+        SemanticLabel::Formula => Color::Blue,
+        SemanticLabel::Regular => Color::Cyan,
+        _ => Color::Gray,
+    }
+}
+
+/// Page bounds `(x1, y1, x2, y2)`
+type Page = (f32, f32, f32, f32);
+
+fn load_page(path: &str) -> Result<(Page, Vec<Element>), Box<dyn Error>> {
+    let layout: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let coords = |value: &Value| -> Result<Vec<f32>, Box<dyn Error>> {
+        let coords: Option<Vec<f32>> = value
+            .as_array()
+            .filter(|values| values.len() == 4)
+            .and_then(|values| {
+                values
+                    .iter()
+                    .map(|v| v.as_f64().map(|v| v as f32))
+                    .collect()
+            });
+        Ok(coords.ok_or("expected [x1, y1, x2, y2]")?)
+    };
+
+    let mut elements = Vec::new();
+    for element in layout["elements"].as_array().ok_or("missing elements")? {
+        let b = coords(&element["bbox"])?;
+        let label = match element["label"].as_str().unwrap_or("Regular") {
+            "CrossLayout" => SemanticLabel::CrossLayout,
+            "HorizontalTitle" => SemanticLabel::HorizontalTitle,
+            "VerticalTitle" => SemanticLabel::VerticalTitle,
+            "Vision" => SemanticLabel::Vision,
+            "Formula" => SemanticLabel::Formula,
+            "Regular" => SemanticLabel::Regular,
+            other => return Err(format!("unknown label {other}").into()),
+        };
+        let id = element["id"].as_u64().ok_or("missing id")? as usize;
+        elements.push(Element::new(id, b[0], b[1], b[2], b[3], label));
+    }
+
+    let page = match coords(&layout["page"]) {
+        Ok(p) => (p[0], p[1], p[2], p[3]),
+        Err(_) => elements.iter().fold(
+            (
+                f32::INFINITY,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                f32::NEG_INFINITY,
+            ),
+            |(x1, y1, x2, y2), e| (x1.min(e.x1), y1.min(e.y1), x2.max(e.x2), y2.max(e.y2)),
+        ),
+    };
+    Ok((page, elements))
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or("usage: viewer <layout.json>")?;
+    let (page, elements) = load_page(&path)?;
+
+    let mut app = App {
+        page,
+        elements,
+        config: XYCutConfig::default(),
+        order: Vec::new(),
+        selected: ListState::default().with_selected(Some(0)),
+    };
+    app.recompute();
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    Ok(result?)
+}
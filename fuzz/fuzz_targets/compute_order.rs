@@ -0,0 +1,138 @@
+//! Random pages through `compute_order_with_report`: every input id must be either
+//! ordered exactly once or reported as dropped, and the call must never panic
+//!
+//! Run with `cargo fuzz run compute_order` from the repository root.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use xycut_plus_plus::{
+    Algorithm, Element, PageOrientation, SemanticLabel, SplitAssignment, XYCutConfig, XYCutPlusPlus,
+};
+
+/// Coordinate drawn from ordinary page values and the pathological ones
+#[derive(Debug, Arbitrary)]
+enum Coord {
+    Page(u16),
+    Raw(f32),
+    Huge(bool),
+    NaN,
+}
+
+impl Coord {
+    fn value(&self) -> f32 {
+        match self {
+            Coord::Page(v) => *v as f32 / 16.0,
+            Coord::Raw(v) => *v,
+            Coord::Huge(negative) => {
+                if *negative {
+                    -1e30
+                } else {
+                    1e30
+                }
+            }
+            Coord::NaN => f32::NAN,
+        }
+    }
+}
+
+#[derive(Debug, Arbitrary)]
+struct BoxInput {
+    x1: Coord,
+    y1: Coord,
+    x2: Coord,
+    y2: Coord,
+    label: u8,
+    /// Emit this box twice, as an overlapping duplicate with its own id
+    duplicate: bool,
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    width: Coord,
+    height: Coord,
+    boxes: Vec<BoxInput>,
+    classic: bool,
+    split: u8,
+    orientation: u8,
+    promote_straddlers: bool,
+    region_subtraction: bool,
+    deskew: bool,
+    repair_inversions: bool,
+    cut_overlap_tolerance: u8,
+    fixed_point: Option<u8>,
+    pre_binning: Option<u8>,
+}
+
+fuzz_target!(|input: Input| {
+    let labels = [
+        SemanticLabel::CrossLayout,
+        SemanticLabel::HorizontalTitle,
+        SemanticLabel::VerticalTitle,
+        SemanticLabel::Vision,
+        SemanticLabel::Formula,
+        SemanticLabel::Regular,
+    ];
+    let mut elements = Vec::new();
+    for b in &input.boxes {
+        let copies = if b.duplicate { 2 } else { 1 };
+        for _ in 0..copies {
+            elements.push(Element::new(
+                elements.len(),
+                b.x1.value(),
+                b.y1.value(),
+                b.x2.value(),
+                b.y2.value(),
+                labels[b.label as usize % labels.len()],
+            ));
+        }
+    }
+
+    let config = XYCutConfig {
+        algorithm: if input.classic {
+            Algorithm::ClassicXYCut
+        } else {
+            Algorithm::XYCutPlusPlus
+        },
+        split_assignment: match input.split % 3 {
+            0 => SplitAssignment::Center,
+            1 => SplitAssignment::MaxOverlap,
+            _ => SplitAssignment::NoSplitIfStraddling,
+        },
+        orientation: match input.orientation % 5 {
+            0 => PageOrientation::Upright,
+            1 => PageOrientation::Rotated90,
+            2 => PageOrientation::Rotated180,
+            3 => PageOrientation::Rotated270,
+            _ => PageOrientation::Auto,
+        },
+        promote_straddlers: input.promote_straddlers,
+        region_subtraction: input.region_subtraction,
+        deskew: input.deskew,
+        repair_inversions: input.repair_inversions,
+        cut_overlap_tolerance: input.cut_overlap_tolerance as f32 / 255.0,
+        fixed_point: input.fixed_point.map(u32::from),
+        pre_binning: input.pre_binning.map(usize::from),
+        ..XYCutConfig::default()
+    };
+
+    let (width, height) = (input.width.value(), input.height.value());
+    let report =
+        XYCutPlusPlus::new(config).compute_order_with_report(&elements, 0.0, 0.0, width, height);
+
+    // Pages without a valid size order nothing
+    if !(width.is_finite() && height.is_finite() && width > 0.0 && height > 0.0) {
+        assert!(report.order.is_empty(), "invalid page ordered {:?}", report.order);
+        return;
+    }
+    let mut seen = vec![false; elements.len()];
+    for &id in report.order.iter().chain(&report.dropped) {
+        assert!(id < elements.len(), "unknown id {id}");
+        assert!(!seen[id], "id {id} ordered or dropped twice");
+        seen[id] = true;
+    }
+    if let Some(id) = seen.iter().position(|seen| !seen) {
+        panic!("id {id} neither ordered nor reported as dropped");
+    }
+});
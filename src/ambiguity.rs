@@ -0,0 +1,124 @@
+//! Flag ordering decisions that were close calls, for targeted human review
+
+use crate::core::XYCutPlusPlus;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+/// A cut whose whitespace gap is below this multiple of `min_cut_threshold` is marginal
+pub(crate) const MARGINAL_GAP_RATIO: f32 = 1.5;
+
+/// Why a decision was flagged
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AmbiguityKind {
+    /// A cut separated the region through a gap only slightly wider than the
+    /// threshold (or through overlapping boxes, for overlap-tolerant cuts)
+    MarginalCut {
+        /// Whitespace between the two sides of the cut, negative when they overlap
+        gap: f32,
+    },
+    /// A masked element had two insertion points whose distances differ by less
+    /// than `same_row_tolerance` pixels of proximity
+    NearTiedInsertion {
+        /// Distance to the chosen insertion point
+        best: f32,
+        /// Distance to the next best insertion point
+        runner_up: f32,
+    },
+    /// No cut was found and the region was ordered by position alone
+    FallbackSort,
+}
+
+/// A region of the page whose ordering deserves a second look
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ambiguity {
+    /// Why the region was flagged
+    pub kind: AmbiguityKind,
+
+    /// Ids of the elements involved; for near-tied insertions the masked element
+    /// followed by the best and runner-up neighbors
+    pub ids: Vec<usize>,
+
+    /// Union of the involved elements' bounds, in the input coordinate frame
+    pub bounds: (f32, f32, f32, f32),
+}
+
+impl XYCutPlusPlus {
+    /// Order the page and return the decisions that were close calls
+    ///
+    /// An annotation tool can highlight just these regions instead of asking a
+    /// reviewer to check the whole page. Flags are listed in the order they were taken.
+    pub fn find_ambiguities<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Vec<Ambiguity> {
+        let mut trace = Trace {
+            track_ambiguity: true,
+            ..Trace::default()
+        };
+        self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace);
+
+        let fallbacks = trace
+            .fallback_groups
+            .into_iter()
+            .map(|ids| (AmbiguityKind::FallbackSort, ids));
+        trace
+            .ambiguities
+            .into_iter()
+            .chain(fallbacks)
+            .filter_map(|(kind, ids)| {
+                let bounds = ids
+                    .iter()
+                    .filter_map(|&id| elements.iter().find(|e| e.id() == id))
+                    .map(|e| e.bounds())
+                    .reduce(|(a1, b1, a2, b2), (x1, y1, x2, y2)| {
+                        (a1.min(x1), b1.min(y1), a2.max(x2), b2.max(y2))
+                    })?;
+                Some(Ambiguity { kind, ids, bounds })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    /// Two stacked paragraphs `gap` pixels apart
+    fn ambiguities(gap: f32) -> Vec<Ambiguity> {
+        let page = [
+            Element::new(0, 100.0, 100.0, 500.0, 300.0, SemanticLabel::Regular),
+            Element::new(
+                1,
+                100.0,
+                300.0 + gap,
+                500.0,
+                500.0 + gap,
+                SemanticLabel::Regular,
+            ),
+        ];
+        XYCutPlusPlus::new(XYCutConfig::default()).find_ambiguities(&page, 0.0, 0.0, 600.0, 1000.0)
+    }
+
+    #[test]
+    fn narrow_gap_cut_is_flagged() {
+        let flagged = ambiguities(20.0);
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].kind, AmbiguityKind::MarginalCut { gap: 20.0 });
+        assert_eq!(flagged[0].ids, vec![0, 1]);
+        assert_eq!(flagged[0].bounds, (100.0, 100.0, 500.0, 520.0));
+    }
+
+    #[test]
+    fn wide_gap_cut_is_not_flagged() {
+        assert!(ambiguities(200.0).is_empty());
+    }
+}
@@ -0,0 +1,239 @@
+//! Audit a tagged PDF's structure-tree order against the geometric reading order
+//!
+//! Accessibility checkers (PDF/UA, WCAG 1.3.2) need the tag order to match the visual
+//! reading order. Given the structure tree's order as ids per page, the audit reports
+//! where it departs from what the page geometry suggests.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::batch::PageInput;
+use crate::core::XYCutPlusPlus;
+use crate::eval::kendall_tau;
+use crate::traits::BoundingBox;
+
+/// An element the structure tree reads at a different point than the geometry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Divergence {
+    pub id: usize,
+
+    /// Position in the structure-tree order
+    pub tagged_rank: usize,
+
+    /// Position in the computed order
+    pub computed_rank: usize,
+}
+
+/// How one page's structure-tree order departs from the computed order
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PageAudit {
+    /// Kendall's τ between the two orders over the ids both contain
+    pub kendall_tau: f32,
+
+    /// Fewest tagged elements that must move for the tag order to agree with the
+    /// computed one, in tagged order; everything else is already in sequence
+    pub out_of_order: Vec<Divergence>,
+
+    /// Ids the computed order reads that the structure tree leaves out
+    pub untagged: Vec<usize>,
+
+    /// Tagged ids the computed order does not read (dropped as noise, background
+    /// or excluded marginalia, or not on the page)
+    pub unplaced: Vec<usize>,
+}
+
+impl PageAudit {
+    /// Whether the tag order reads every ordered element in the computed sequence
+    pub fn passes(&self) -> bool {
+        self.out_of_order.is_empty() && self.untagged.is_empty()
+    }
+}
+
+/// Compare a structure-tree order with a computed order
+pub fn audit_order(tagged: &[usize], computed: &[usize]) -> PageAudit {
+    let computed_rank: HashMap<usize, usize> = computed
+        .iter()
+        .enumerate()
+        .map(|(rank, &id)| (id, rank))
+        .collect();
+    let tagged_ids: HashSet<usize> = tagged.iter().copied().collect();
+
+    // Tagged elements in tag order with their computed positions; the longest
+    // increasing run of positions is the part already in sequence
+    let shared: Vec<Divergence> = tagged
+        .iter()
+        .enumerate()
+        .filter_map(|(tagged_rank, &id)| {
+            computed_rank.get(&id).map(|&computed_rank| Divergence {
+                id,
+                tagged_rank,
+                computed_rank,
+            })
+        })
+        .collect();
+    let in_sequence = longest_increasing(&shared);
+
+    PageAudit {
+        kendall_tau: kendall_tau(tagged, computed),
+        out_of_order: shared
+            .iter()
+            .zip(in_sequence)
+            .filter(|(_, kept)| !kept)
+            .map(|(divergence, _)| *divergence)
+            .collect(),
+        untagged: computed
+            .iter()
+            .copied()
+            .filter(|id| !tagged_ids.contains(id))
+            .collect(),
+        unplaced: tagged
+            .iter()
+            .copied()
+            .filter(|id| !computed_rank.contains_key(id))
+            .collect(),
+    }
+}
+
+/// Mark the members of one longest subsequence with increasing computed ranks
+fn longest_increasing(shared: &[Divergence]) -> Vec<bool> {
+    // tails[k]: index into `shared` ending the best increasing run of length k + 1
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous: Vec<Option<usize>> = vec![None; shared.len()];
+    for (i, divergence) in shared.iter().enumerate() {
+        let length = tails.partition_point(|&t| shared[t].computed_rank < divergence.computed_rank);
+        previous[i] = length.checked_sub(1).map(|k| tails[k]);
+        if length == tails.len() {
+            tails.push(i);
+        } else {
+            tails[length] = i;
+        }
+    }
+
+    let mut kept = vec![false; shared.len()];
+    let mut next = tails.last().copied();
+    while let Some(i) = next {
+        kept[i] = true;
+        next = previous[i];
+    }
+    kept
+}
+
+/// Audit of every page of a document
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditReport {
+    /// One audit per page, in page order
+    pub pages: Vec<PageAudit>,
+}
+
+impl AuditReport {
+    /// Indices and audits of the pages that do not pass
+    pub fn failing_pages(&self) -> impl Iterator<Item = (usize, &PageAudit)> {
+        self.pages
+            .iter()
+            .enumerate()
+            .filter(|(_, audit)| !audit.passes())
+    }
+}
+
+impl fmt::Display for AuditReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (page, audit) in self.pages.iter().enumerate() {
+            let status = if audit.passes() { "ok" } else { "FAIL" };
+            writeln!(
+                f,
+                "page {page}: {status} (tau {:.3}, {} out of order, {} untagged, {} unplaced)",
+                audit.kendall_tau,
+                audit.out_of_order.len(),
+                audit.untagged.len(),
+                audit.unplaced.len()
+            )?;
+            for divergence in &audit.out_of_order {
+                writeln!(
+                    f,
+                    "  id {} tagged at {} but read at {}",
+                    divergence.id, divergence.tagged_rank, divergence.computed_rank
+                )?;
+            }
+            if !audit.untagged.is_empty() {
+                writeln!(f, "  untagged: {:?}", audit.untagged)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl XYCutPlusPlus {
+    /// Order each page and compare it with the structure tree's order for that page
+    ///
+    /// `tagged[i]` lists the ids of page `i` in structure-tree order; pages without an
+    /// entry are audited against an empty tag order.
+    pub fn audit_structure_order<T: BoundingBox + Sync>(
+        &self,
+        pages: &[PageInput<T>],
+        tagged: &[Vec<usize>],
+    ) -> AuditReport {
+        AuditReport {
+            pages: self
+                .compute_orders(pages)
+                .iter()
+                .enumerate()
+                .map(|(page, computed)| {
+                    audit_order(tagged.get(page).map_or(&[][..], Vec::as_slice), computed)
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    #[test]
+    fn matching_tag_order_passes() {
+        let audit = audit_order(&[0, 1, 2], &[0, 1, 2]);
+        assert!(audit.passes());
+        assert_eq!(audit.kendall_tau, 1.0);
+    }
+
+    #[test]
+    fn fewest_moved_tags_are_reported() {
+        // Id 3 is tagged first but read last; 9 is tagged but not on the page
+        let audit = audit_order(&[3, 0, 1, 9, 2], &[0, 1, 2, 3, 4]);
+        assert!(!audit.passes());
+        assert_eq!(
+            audit.out_of_order,
+            vec![Divergence {
+                id: 3,
+                tagged_rank: 0,
+                computed_rank: 3
+            }]
+        );
+        assert_eq!(audit.untagged, vec![4]);
+        assert_eq!(audit.unplaced, vec![9]);
+    }
+
+    #[test]
+    fn structure_order_is_audited_per_page() {
+        let page = vec![
+            Element::new(0, 50.0, 50.0, 550.0, 100.0, SemanticLabel::Regular),
+            Element::new(1, 50.0, 200.0, 550.0, 250.0, SemanticLabel::Regular),
+        ];
+        let bounds = (0.0, 0.0, 600.0, 300.0);
+        let pages: Vec<PageInput<Element>> = vec![(&page, bounds), (&page, bounds)];
+        let report = XYCutPlusPlus::new(XYCutConfig::default())
+            .audit_structure_order(&pages, &[vec![0, 1], vec![1, 0]]);
+
+        let failing: Vec<usize> = report.failing_pages().map(|(page, _)| page).collect();
+        assert_eq!(failing, vec![1]);
+        let text = report.to_string();
+        assert!(text.starts_with("page 0: ok"));
+        assert!(text.contains("page 1: FAIL"));
+    }
+}
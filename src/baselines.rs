@@ -0,0 +1,134 @@
+//! Simple reference orderings to measure XY-Cut++ against
+
+use crate::traits::{BoundingBox, ReadingOrder};
+use crate::utils::{compare_position, compute_median_height, sort_by_rows};
+
+/// Side length (as a power of two) of the grid centers are snapped to for Hilbert order
+const HILBERT_ORDER: u32 = 16;
+
+/// Layout-agnostic reading orders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Baseline {
+    /// Top-left sweep: rows top to bottom, each row left to right
+    Raster,
+    /// Columns of horizontally overlapping elements left to right, each top to bottom
+    ColumnFirst,
+    /// Position of each center along a Hilbert curve over the page
+    Hilbert,
+}
+
+impl ReadingOrder for Baseline {
+    fn order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Vec<usize> {
+        let mut sorted = elements.to_vec();
+        match self {
+            Baseline::Raster => {
+                // Centers within half a line height share a row
+                sort_by_rows(&mut sorted, compute_median_height(elements) / 2.0);
+            }
+            Baseline::ColumnFirst => {
+                sorted.sort_by(|a, b| a.bounds().0.total_cmp(&b.bounds().0));
+                let mut columns: Vec<(f32, Vec<T>)> = Vec::new();
+                for element in sorted {
+                    let (x1, _, x2, _) = element.bounds();
+                    match columns.last_mut() {
+                        Some((right, column)) if x1 < *right => {
+                            *right = right.max(x2);
+                            column.push(element);
+                        }
+                        _ => columns.push((x2, vec![element])),
+                    }
+                }
+                sorted = columns
+                    .into_iter()
+                    .flat_map(|(_, mut column)| {
+                        column.sort_by(compare_position);
+                        column
+                    })
+                    .collect();
+            }
+            Baseline::Hilbert => {
+                let side = (1u32 << HILBERT_ORDER) as f32;
+                let cell = |value: f32, min: f32, max: f32| {
+                    (((value - min) / (max - min)).clamp(0.0, 1.0) * (side - 1.0)) as u32
+                };
+                sorted.sort_by_cached_key(|e| {
+                    let (cx, cy) = e.center();
+                    let x = cell(cx, x_min, x_max);
+                    let y = cell(cy, y_min, y_max);
+                    (hilbert_index(x, y), e.id())
+                });
+            }
+        }
+        sorted.iter().map(|e| e.id()).collect()
+    }
+}
+
+/// Distance of grid cell `(x, y)` along the Hilbert curve of order [`HILBERT_ORDER`],
+/// starting at the top-left corner
+fn hilbert_index(mut x: u32, mut y: u32) -> u64 {
+    let n = 1u32 << HILBERT_ORDER;
+    let mut index = 0u64;
+    let mut s = n / 2;
+    while s > 0 {
+        let rx = u32::from(x & s > 0);
+        let ry = u32::from(y & s > 0);
+        index += u64::from(s) * u64::from(s) * u64::from((3 * rx) ^ ry);
+
+        // Rotate the quadrant so the sub-curve is traversed in standard orientation
+        if ry == 0 {
+            if rx == 1 {
+                x = n - 1 - x;
+                y = n - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    /// Two columns of two paragraphs, ids row by row
+    fn page() -> Vec<Element> {
+        [(50.0, 50.0), (550.0, 50.0), (50.0, 550.0), (550.0, 550.0)]
+            .into_iter()
+            .enumerate()
+            .map(|(id, (x, y))| {
+                Element::new(id, x, y, x + 400.0, y + 400.0, SemanticLabel::Regular)
+            })
+            .collect()
+    }
+
+    fn order(baseline: Baseline) -> Vec<usize> {
+        baseline.order(&page(), 0.0, 0.0, 1000.0, 1000.0)
+    }
+
+    #[test]
+    fn raster_reads_rows() {
+        assert_eq!(order(Baseline::Raster), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn column_first_reads_columns() {
+        assert_eq!(order(Baseline::ColumnFirst), vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn hilbert_visits_the_quadrants_along_the_curve() {
+        // Top-left, bottom-left, bottom-right, top-right
+        assert_eq!(order(Baseline::Hilbert), vec![0, 2, 3, 1]);
+    }
+}
@@ -0,0 +1,29 @@
+//! Ordering many pages in one call
+
+use crate::core::XYCutPlusPlus;
+use crate::traits::BoundingBox;
+
+/// A page of a batch: its elements and bounds `(x_min, y_min, x_max, y_max)`
+pub type PageInput<'a, T> = (&'a [T], (f32, f32, f32, f32));
+
+impl XYCutPlusPlus {
+    /// Order a batch of pages; orders are returned in page order
+    ///
+    /// With the `parallel` feature pages are spread over the rayon thread pool,
+    /// otherwise they are ordered one after another. Results are identical either way.
+    pub fn compute_orders<T: BoundingBox + Sync>(&self, pages: &[PageInput<T>]) -> Vec<Vec<usize>> {
+        let order_page = |&(elements, (x_min, y_min, x_max, y_max)): &PageInput<T>| {
+            self.compute_order(elements, x_min, y_min, x_max, y_max)
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            pages.par_iter().map(order_page).collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            pages.iter().map(order_page).collect()
+        }
+    }
+}
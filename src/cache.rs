@@ -0,0 +1,176 @@
+//! Memoized orders for pages ordered again and again (render previews, editors)
+
+use std::collections::HashMap;
+use std::mem::{size_of, size_of_val};
+
+use crate::core::XYCutPlusPlus;
+use crate::report::{fnv1a, FNV_OFFSET_BASIS};
+use crate::traits::{BoundingBox, SemanticLabel};
+
+/// Bytes counted per cached page on top of its order: key, recency and map slot
+const ENTRY_OVERHEAD: usize = 4 * size_of::<u64>();
+
+/// Least-recently-used cache of orders, keyed by [`XYCutPlusPlus::layout_hash`]
+///
+/// Memory is bounded by `max_bytes`, counting each page's order plus a small fixed
+/// overhead; the least recently used pages are evicted first. Hash collisions
+/// between different layouts are not detected (64-bit keys make them unlikely).
+#[derive(Debug, Clone)]
+pub struct OrderCache {
+    entries: HashMap<u64, (Vec<usize>, u64)>,
+    max_bytes: usize,
+    bytes: usize,
+    /// Incremented on every lookup, the recency of an entry
+    clock: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl OrderCache {
+    /// An empty cache holding at most `max_bytes` of orders
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            max_bytes,
+            bytes: 0,
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// [`XYCutPlusPlus::compute_order`], returning the memoized order when the same
+    /// layout was ordered with the same config before
+    pub fn compute_order<T: BoundingBox>(
+        &mut self,
+        xycut: &XYCutPlusPlus,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Vec<usize> {
+        let key = xycut.layout_hash(elements, x_min, y_min, x_max, y_max);
+        self.clock += 1;
+        if let Some((order, used)) = self.entries.get_mut(&key) {
+            *used = self.clock;
+            self.hits += 1;
+            return order.clone();
+        }
+        self.misses += 1;
+        let order = xycut.compute_order(elements, x_min, y_min, x_max, y_max);
+        self.insert(key, order.clone());
+        order
+    }
+
+    fn insert(&mut self, key: u64, order: Vec<usize>) {
+        let size = entry_size(&order);
+        if size > self.max_bytes {
+            return;
+        }
+        self.bytes += size;
+        self.entries.insert(key, (order, self.clock));
+        self.evict();
+    }
+
+    /// Drop the least recently used entries until the cache fits `max_bytes`
+    fn evict(&mut self) {
+        while self.bytes > self.max_bytes {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| *key)
+            else {
+                break;
+            };
+            if let Some((order, _)) = self.entries.remove(&oldest) {
+                self.bytes -= entry_size(&order);
+            }
+        }
+    }
+
+    /// Change the memory bound, evicting entries if the cache no longer fits
+    pub fn set_max_bytes(&mut self, max_bytes: usize) {
+        self.max_bytes = max_bytes;
+        self.evict();
+    }
+
+    /// Estimated memory held by the cached orders, in bytes
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Number of cached pages
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Lookups answered from the cache
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Lookups that computed the order
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Drop every cached order, keeping the hit and miss counts
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+    }
+}
+
+fn entry_size(order: &[usize]) -> usize {
+    size_of_val(order) + ENTRY_OVERHEAD
+}
+
+impl XYCutPlusPlus {
+    /// Hash of the elements, the page bounds and [`fingerprint`](Self::fingerprint)
+    ///
+    /// Equal for the same boxes (ids, coordinates, labels, masks and confidences, in
+    /// the same order) ordered with the same config, so it keys cached orders. Text
+    /// is not hashed; it does not affect the order.
+    pub fn layout_hash<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> u64 {
+        let mut hash = fnv1a(FNV_OFFSET_BASIS, &self.fingerprint().to_le_bytes());
+        for value in [x_min, y_min, x_max, y_max] {
+            hash = fnv1a(hash, &value.to_bits().to_le_bytes());
+        }
+        for e in elements {
+            let (x1, y1, x2, y2) = e.bounds();
+            hash = fnv1a(hash, &(e.id() as u64).to_le_bytes());
+            for value in [x1, y1, x2, y2, e.confidence().unwrap_or(f32::NAN)] {
+                hash = fnv1a(hash, &value.to_bits().to_le_bytes());
+            }
+            hash = fnv1a(
+                hash,
+                &[label_byte(e.semantic_label()), u8::from(e.should_mask())],
+            );
+        }
+        hash
+    }
+}
+
+fn label_byte(label: SemanticLabel) -> u8 {
+    match label {
+        SemanticLabel::CrossLayout => 0,
+        SemanticLabel::HorizontalTitle => 1,
+        SemanticLabel::VerticalTitle => 2,
+        SemanticLabel::Vision => 3,
+        SemanticLabel::Formula => 4,
+        SemanticLabel::Regular => 5,
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+
+use crate::paragraph::{group_paragraphs_with_text, ParagraphConfig};
+use crate::text::{append_line, ends_with_hyphenation, join_lines};
+use crate::traits::{BoundingBox, TextContent};
+
+/// Configuration for reading-order chunking
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ChunkConfig {
+    /// Maximum chunk size in budget units (characters for [`chunk_order`])
+    pub max_size: usize,
+
+    /// Line-to-paragraph settings; paragraphs are only split when larger than `max_size`
+    pub paragraph: ParagraphConfig,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 1000,
+            paragraph: ParagraphConfig::default(),
+        }
+    }
+}
+
+/// A contiguous run of the reading order sized for LLM ingestion
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chunk {
+    /// Element ids in reading order
+    pub ids: Vec<usize>,
+
+    /// Chunk text, paragraphs separated by a blank line
+    pub text: String,
+
+    /// Size of `text` in budget units, summed over its paragraphs and lines
+    pub size: usize,
+}
+
+/// Group the reading order into chunks of at most `config.max_size` characters
+///
+/// See [`chunk_order_with`] for the boundary rules.
+pub fn chunk_order<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+    config: &ChunkConfig,
+) -> Vec<Chunk> {
+    chunk_order_with(elements, order, config, |text| text.chars().count())
+}
+
+/// Group the reading order into chunks, measuring text with `measure` (e.g. a tokenizer)
+///
+/// Chunks never cross a section boundary (a title always opens a new chunk)
+/// or a column boundary (the next paragraph moves to another column).
+/// Paragraphs are kept whole unless a single paragraph exceeds the budget,
+/// in which case it is split between lines; a single line is never split.
+///
+/// Each paragraph and line is measured once and sizes are summed, so `measure`
+/// should be additive over concatenated text, as character and token counts
+/// (nearly) are.
+pub fn chunk_order_with<T, F>(
+    elements: &[T],
+    order: &[usize],
+    config: &ChunkConfig,
+    measure: F,
+) -> Vec<Chunk>
+where
+    T: BoundingBox + TextContent,
+    F: Fn(&str) -> usize,
+{
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+
+    let mut chunks = Vec::new();
+    let mut builder = ChunkBuilder::default();
+    let mut previous: Option<&T> = None;
+
+    for paragraph in group_paragraphs_with_text(elements, order, &config.paragraph) {
+        let first = by_id[&paragraph[0]];
+        let last = by_id[&paragraph[paragraph.len() - 1]];
+
+        let forced_break = match previous {
+            Some(prev) => first.semantic_label().is_title() || changes_column(prev, first),
+            None => false,
+        };
+        if forced_break {
+            builder.finish(&mut chunks);
+        }
+
+        let text = join_lines(paragraph.iter().map(|id| by_id[id].text()));
+        let size = measure(&text);
+        if size > config.max_size {
+            // Oversized paragraph: flush, then split it between lines
+            builder.finish(&mut chunks);
+            for id in &paragraph {
+                let line = by_id[id].text().trim();
+                if !builder.fits(measure(line), false, config.max_size, &measure) {
+                    builder.finish(&mut chunks);
+                }
+                builder.push_line(*id, line, &measure);
+            }
+            builder.finish(&mut chunks);
+        } else {
+            if !builder.fits(size, true, config.max_size, &measure) {
+                builder.finish(&mut chunks);
+            }
+            builder.push_paragraph(&paragraph, &text, size, &measure);
+        }
+
+        previous = Some(last);
+    }
+
+    builder.finish(&mut chunks);
+    chunks
+}
+
+/// Accumulates the chunk currently being built
+#[derive(Default)]
+struct ChunkBuilder {
+    ids: Vec<usize>,
+    text: String,
+    /// Running size of `text`, so adding to the chunk never re-measures it
+    size: usize,
+}
+
+impl ChunkBuilder {
+    fn separator(&self, paragraph: bool) -> &'static str {
+        match (self.text.is_empty(), paragraph) {
+            (true, _) => "",
+            (false, true) => "\n\n",
+            (false, false) => " ",
+        }
+    }
+
+    /// Whether text of `size` still fits after the separator
+    fn fits<F: Fn(&str) -> usize>(
+        &self,
+        size: usize,
+        paragraph: bool,
+        max_size: usize,
+        measure: &F,
+    ) -> bool {
+        self.text.is_empty() || self.size + measure(self.separator(paragraph)) + size <= max_size
+    }
+
+    fn push_paragraph<F: Fn(&str) -> usize>(
+        &mut self,
+        ids: &[usize],
+        text: &str,
+        size: usize,
+        measure: &F,
+    ) {
+        // Text-less elements (figures) still belong to the chunk
+        if !text.is_empty() {
+            self.size += measure(self.separator(true)) + size;
+            self.text.push_str(self.separator(true));
+            self.text.push_str(text);
+        }
+        self.ids.extend_from_slice(ids);
+    }
+
+    /// Append a line of the paragraph being split, undoing hyphenation like
+    /// [`join_lines`]
+    fn push_line<F: Fn(&str) -> usize>(&mut self, id: usize, line: &str, measure: &F) {
+        // Re-measure from the trailing hyphen, which the new line may remove
+        let mut tail = self.text.len();
+        if ends_with_hyphenation(&self.text) {
+            tail -= self.text.chars().next_back().map_or(0, char::len_utf8);
+        }
+        let before = measure(&self.text[tail..]);
+        append_line(&mut self.text, line);
+        self.size = self.size - before + measure(&self.text[tail..]);
+        self.ids.push(id);
+    }
+
+    fn finish(&mut self, chunks: &mut Vec<Chunk>) {
+        if self.ids.is_empty() {
+            return;
+        }
+
+        chunks.push(Chunk {
+            ids: std::mem::take(&mut self.ids),
+            text: std::mem::take(&mut self.text),
+            size: std::mem::take(&mut self.size),
+        });
+    }
+}
+
+/// Whether `next` starts in a different column than `prev` ended in
+fn changes_column<T: BoundingBox>(prev: &T, next: &T) -> bool {
+    let (px1, py1, px2, _) = prev.bounds();
+    let (nx1, ny1, nx2, _) = next.bounds();
+
+    let overlaps_horizontally = nx1 < px2 && nx2 > px1;
+    !overlaps_horizontally || ny1 < py1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{line, TextLine};
+    use crate::traits::SemanticLabel;
+
+    /// Line `id` on row `row` (14 pixels apart) of the column starting at `x`
+    fn at(id: usize, x: f32, row: usize, text: &str) -> TextLine {
+        let y = row as f32 * 14.0;
+        line(id, (x, y, x + 200.0, y + 10.0), text)
+    }
+
+    fn config(max_size: usize) -> ChunkConfig {
+        ChunkConfig {
+            max_size,
+            ..ChunkConfig::default()
+        }
+    }
+
+    fn ids(chunks: &[Chunk]) -> Vec<Vec<usize>> {
+        chunks.iter().map(|chunk| chunk.ids.clone()).collect()
+    }
+
+    #[test]
+    fn paragraphs_share_a_chunk_within_budget() {
+        // Two paragraphs separated by an empty row
+        let lines = [
+            at(0, 0.0, 0, "The first para-"),
+            at(1, 0.0, 1, "graph."),
+            at(2, 0.0, 3, "The second one."),
+        ];
+        let chunks = chunk_order(&lines, &[0, 1, 2], &ChunkConfig::default());
+        assert_eq!(
+            chunks,
+            vec![Chunk {
+                ids: vec![0, 1, 2],
+                text: "The first paragraph.\n\nThe second one.".to_string(),
+                size: 37,
+            }]
+        );
+    }
+
+    #[test]
+    fn paragraphs_are_kept_whole_across_chunks() {
+        let lines = [
+            at(0, 0.0, 0, "aaaa aaaa"),
+            at(1, 0.0, 1, "aaaa"),
+            at(2, 0.0, 3, "bbbb bbbb"),
+            at(3, 0.0, 5, "cccc"),
+        ];
+        let chunks = chunk_order(&lines, &[0, 1, 2, 3], &config(20));
+        assert_eq!(ids(&chunks), vec![vec![0, 1], vec![2, 3]]);
+        assert!(chunks.iter().all(|chunk| chunk.size <= 20));
+    }
+
+    #[test]
+    fn titles_and_columns_open_chunks() {
+        let mut lines = vec![
+            at(0, 0.0, 0, "Intro text."),
+            at(1, 0.0, 2, "Methods"),
+            at(2, 0.0, 4, "Method text."),
+            at(3, 250.0, 0, "Next column."),
+        ];
+        lines[1].element.label = SemanticLabel::HorizontalTitle;
+        lines[1].element.mask = true;
+        let chunks = chunk_order(&lines, &[0, 1, 2, 3], &ChunkConfig::default());
+        assert_eq!(ids(&chunks), vec![vec![0], vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn oversized_paragraph_splits_between_lines() {
+        let lines = [
+            at(0, 0.0, 0, "one two three"),
+            at(1, 0.0, 1, "four five six"),
+            at(2, 0.0, 2, "a line longer than the budget"),
+        ];
+        let chunks = chunk_order(&lines, &[0, 1, 2], &config(15));
+        assert_eq!(ids(&chunks), vec![vec![0], vec![1], vec![2]]);
+        assert_eq!(chunks[2].text, "a line longer than the budget");
+    }
+
+    #[test]
+    fn split_paragraph_undoes_hyphenation() {
+        let lines = [
+            at(0, 0.0, 0, "a hyphen-"),
+            at(1, 0.0, 1, "ated word"),
+            at(2, 0.0, 2, "and a Franco-"),
+            at(3, 0.0, 3, "German one"),
+        ];
+        let chunks = chunk_order(&lines, &[0, 1, 2, 3], &config(31));
+        assert_eq!(ids(&chunks), vec![vec![0, 1, 2], vec![3]]);
+        assert_eq!(chunks[0].text, "a hyphenated word and a Franco-");
+        assert_eq!(chunks[0].size, 31);
+        assert_eq!(chunks[1].text, "German one");
+    }
+
+    #[test]
+    fn custom_measure_sets_the_budget() {
+        let lines = [
+            at(0, 0.0, 0, "one two"),
+            at(1, 0.0, 2, "three four"),
+            at(2, 0.0, 4, "five"),
+        ];
+        let words = |text: &str| text.split_whitespace().count();
+        let chunks = chunk_order_with(&lines, &[0, 1, 2], &config(4), words);
+        assert_eq!(ids(&chunks), vec![vec![0, 1], vec![2]]);
+        assert_eq!(chunks[0].size, 4);
+    }
+}
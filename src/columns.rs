@@ -0,0 +1,55 @@
+//! Column gutters carried from page to page of one document
+
+use crate::batch::PageInput;
+use crate::core::XYCutPlusPlus;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+/// How gutters found on earlier pages guide the cuts of later ones
+///
+/// Carried gutters are only tried where a region has no vertical cut of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnHints {
+    /// Cut at a carried gutter when the region's elements leave it clear, even where
+    /// the gap is narrower than `min_cut_threshold`
+    #[default]
+    Prior,
+    /// Cut at a carried gutter even through boxes overlapping it; each goes to the
+    /// side holding most of it
+    Forced,
+}
+
+impl XYCutPlusPlus {
+    /// Order the pages of one document in sequence, carrying the column gutters
+    /// (vertical cut positions) of each page to the next
+    ///
+    /// Noisy pages whose boxes bridge a gutter, or whose gutter is too narrow to
+    /// cut, are then split where their neighbours were. A page with no vertical cut
+    /// (a title page, a full-width figure) keeps the gutters of the last page that
+    /// had one. Pages are ordered one after another, even with `parallel`.
+    pub fn compute_orders_with_column_hints<T: BoundingBox>(
+        &self,
+        pages: &[PageInput<T>],
+        hints: ColumnHints,
+    ) -> Vec<Vec<usize>> {
+        let mut gutters = Vec::new();
+        pages
+            .iter()
+            .map(|&(elements, (x_min, y_min, x_max, y_max))| {
+                let mut trace = Trace {
+                    column_hints: gutters.clone(),
+                    hint_mode: hints,
+                    record_columns: true,
+                    ..Trace::default()
+                };
+                let order =
+                    self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace);
+                if !trace.columns.is_empty() {
+                    gutters = trace.columns;
+                }
+                order
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,121 @@
+//! Per-transition confidence, for marking uncertain steps in generated text
+
+use std::collections::{HashMap, HashSet};
+
+use crate::ambiguity::MARGINAL_GAP_RATIO;
+use crate::core::XYCutPlusPlus;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+/// One step of the reading order and how sure the algorithm is of it
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transition {
+    pub from: usize,
+    pub to: usize,
+
+    /// From 0 (a coin flip) to 1 (no close call involved)
+    pub confidence: f32,
+}
+
+impl XYCutPlusPlus {
+    /// Order the page and rate each consecutive pair of the order
+    ///
+    /// A pair separated by a cut is rated by the cut's whitespace gap, reaching 1 at
+    /// 1.5 × `min_cut_threshold` (the point below which [`find_ambiguities`] flags
+    /// it). A pair ordered by the fallback sort is rated the same way by the gap
+    /// between the two boxes. A pair next to an inserted masked element is rated by
+    /// how much closer its insertion point was than the runner-up, reaching 1 at a
+    /// lead of `same_row_tolerance`, and 0 when it had no valid anchor.
+    /// A pair gets
+    /// the lowest of the ratings that apply.
+    ///
+    /// [`find_ambiguities`]: Self::find_ambiguities
+    pub fn compute_order_with_confidence<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> (Vec<usize>, Vec<Transition>) {
+        let mut trace = Trace {
+            track_confidence: true,
+            ..Trace::default()
+        };
+        let order = self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace);
+
+        let full_gap = MARGINAL_GAP_RATIO * self.config.min_cut_threshold;
+        let gap_confidence = |gap: f32| {
+            if full_gap > 0.0 {
+                (gap / full_gap).clamp(0.0, 1.0)
+            } else if gap >= 0.0 {
+                1.0
+            } else {
+                0.0
+            }
+        };
+        let rank: HashMap<usize, usize> =
+            order.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+        let mut confidence = vec![1.0f32; order.len().saturating_sub(1)];
+        // Lower the rating of the pair starting at rank `i`
+        let mut lower = |i: usize, value: f32| {
+            if let Some(c) = confidence.get_mut(i) {
+                *c = c.min(value);
+            }
+        };
+
+        for cut in &trace.cut_gaps {
+            let value = gap_confidence(cut.gap);
+            let after: HashSet<usize> = cut.after.iter().copied().collect();
+            // Pairs with one element on each side, in either direction
+            for &i in cut.before.iter().filter_map(|id| rank.get(id)) {
+                if order.get(i + 1).is_some_and(|next| after.contains(next)) {
+                    lower(i, value);
+                }
+                if i > 0 && after.contains(&order[i - 1]) {
+                    lower(i - 1, value);
+                }
+            }
+        }
+
+        let boxes: HashMap<usize, (f32, f32, f32, f32)> =
+            elements.iter().map(|e| (e.id(), e.bounds())).collect();
+        for group in &trace.fallback_groups {
+            for pair in group.windows(2) {
+                let (Some(&i), Some(&j), Some(a), Some(b)) = (
+                    rank.get(&pair[0]),
+                    rank.get(&pair[1]),
+                    boxes.get(&pair[0]),
+                    boxes.get(&pair[1]),
+                ) else {
+                    continue;
+                };
+                if j == i + 1 {
+                    let gap = (b.0 - a.2).max(a.0 - b.2).max(b.1 - a.3).max(a.1 - b.3);
+                    lower(i, gap_confidence(gap));
+                }
+            }
+        }
+
+        for &(id, clarity) in &trace.insertion_margins {
+            if let Some(&i) = rank.get(&id) {
+                if i > 0 {
+                    lower(i - 1, clarity);
+                }
+                lower(i, clarity);
+            }
+        }
+
+        let transitions = order
+            .windows(2)
+            .zip(confidence)
+            .map(|(pair, confidence)| Transition {
+                from: pair[0],
+                to: pair[1],
+                confidence,
+            })
+            .collect();
+        (order, transitions)
+    }
+}
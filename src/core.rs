@@ -1,13 +1,138 @@
 use core::f32;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::time::Instant;
 
-use crate::histogram::{build_horizontal_histogram, build_vertical_histogram, find_largest_gap};
-use crate::matching::partition_by_mask;
-use crate::traits::{BoundingBox, SemanticLabel};
-use crate::utils::compute_distance_with_early_exit;
+use crate::ambiguity::{AmbiguityKind, MARGINAL_GAP_RATIO};
+use crate::columns::ColumnHints;
+use crate::density::DensityEstimator;
+use crate::element::Element;
+use crate::fallback::FallbackSort;
+use crate::formula::{display_formula_position, FormulaHandling};
+use crate::histogram::{
+    bin_coordinate, fill_horizontal_histogram, fill_vertical_histogram, find_largest_gap,
+    find_tolerant_gap,
+};
+use crate::insertion::{sort_by_columns, InsertionOrder};
+use crate::marginalia::{detect_marginalia, place_marginalia, MarginaliaPolicy};
+use crate::matching::{partition_by_mask, MaskPartition, CROSS_LAYOUT_WIDTH_RATIO};
+use crate::observer::{Axis, Cut, Insertion, OrderingObserver};
+use crate::preference::{CutPreference, RegionStats};
+use crate::preprocess::background::detect_background;
+use crate::preprocess::content::estimate_content_area;
+use crate::preprocess::dropcap::merge_drop_caps;
+use crate::preprocess::figures::group_figures;
+use crate::preprocess::lists::group_lists;
+use crate::preprocess::noise::{detect_noise, NoiseFilter};
+use crate::preprocess::orientation::{detect_orientation, rotate_upright, PageOrientation};
+use crate::preprocess::skew::{deskew, estimate_skew};
+use crate::preprocess::snap::snap_edges;
+use crate::preprocess::tables::group_tables;
+use crate::preprocess::tiles::bin_tiles;
+use crate::repair::repair_inversions;
+use crate::trace::{CutGap, Trace};
+use crate::traits::{BoundingBox, ReadingOrder, SemanticLabel};
+use crate::unanchored::{Unanchored, UnanchoredPolicy, UnanchoredReason};
+use crate::utils::{
+    compare_position, compute_median_height, compute_median_width, compute_weighted_distance,
+    sort_by_rows, DistanceWeights,
+};
+use crate::validate::validate_order;
+
+/// Skew (degrees) below which pages are cut along the image axes
+pub(crate) const MIN_SKEW_DEGREES: f32 = 0.2;
+
+/// Minimum share of an element's extent on each side of a cut for it to straddle the cut
+const STRADDLE_SHARE: f32 = 0.25;
+
+/// Number of largest elements tried as obstacles by region-subtraction cuts
+const MAX_OBSTACLE_CANDIDATES: usize = 3;
+
+/// Nesting depth after which regions are ordered by position instead of cut further
+const MAX_CUT_DEPTH: usize = 256;
+
+/// Upper bound on projection histogram bins, so absurd page sizes cannot exhaust memory
+const MAX_HISTOGRAM_BINS: usize = 1 << 16;
+
+/// Ordering algorithm variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// Classic recursive XY-Cut: no pre-masking, no semantic density ratio,
+    /// horizontal cuts tried before vertical ones
+    ClassicXYCut,
+    /// XY-Cut++ (Liu et al., 2025) as described in the paper
+    #[default]
+    XYCutPlusPlus,
+}
+
+/// Side of a cut an element is assigned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SplitAssignment {
+    /// By the element's `center()`
+    #[default]
+    Center,
+    /// By the side holding the larger part of the element's bounds
+    MaxOverlap,
+    /// Reject cuts crossing any element's bounds; otherwise by center
+    NoSplitIfStraddling,
+}
+
+/// Rank of each label during masked-element insertion and label-aware fallback
+/// sorting; lower ranks go first, and a masked element is only placed before
+/// elements of its rank or above (Equation 7)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LabelPriorities {
+    pub cross_layout: u8,
+    pub title: u8,
+    /// Also the rank of formulas
+    pub vision: u8,
+    pub regular: u8,
+}
+
+impl Default for LabelPriorities {
+    /// The paper's order: cross-layout, titles, vision, regular
+    fn default() -> Self {
+        Self {
+            cross_layout: 0,
+            title: 1,
+            vision: 2,
+            regular: 3,
+        }
+    }
+}
+
+impl LabelPriorities {
+    /// Rank of `label`
+    pub fn rank(&self, label: SemanticLabel) -> u8 {
+        match label {
+            SemanticLabel::CrossLayout => self.cross_layout,
+            SemanticLabel::HorizontalTitle | SemanticLabel::VerticalTitle => self.title,
+            SemanticLabel::Vision | SemanticLabel::Formula => self.vision,
+            SemanticLabel::Regular => self.regular,
+        }
+    }
+}
 
 /// Configuration for XY-Cut algorithm
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct XYCutConfig {
+    /// Algorithm variant, for A/B comparisons and reproducing baselines
+    pub algorithm: Algorithm,
+
+    /// Estimator of the region density deciding whether to cut vertically first
+    pub density: DensityEstimator,
+
+    /// Density above which a region is cut vertically first (Equation 5)
+    pub vertical_first_threshold: f32,
+
     /// Minimum gap size (in pixels) to consider for cutting
     pub min_cut_threshold: f32,
 
@@ -16,20 +141,204 @@ pub struct XYCutConfig {
 
     /// Tolerance for considering elements in the same row (pixels)
     pub same_row_tolerance: f32,
+
+    /// Derive the row tolerance from each region instead, as this fraction of its
+    /// median element height, so the same config suits thumbnails and 600-DPI scans;
+    /// regions whose median height is 0 keep `same_row_tolerance`
+    pub adaptive_row_tolerance: Option<f32>,
+
+    /// Minimum detector confidence for trusting an element's `should_mask`
+    /// (only applies to elements reporting a confidence)
+    pub mask_confidence_threshold: f32,
+
+    /// How side notes in the page margins are ordered
+    pub marginalia: MarginaliaPolicy,
+
+    /// Merge drop caps and run-in headings into their paragraph before ordering
+    pub merge_drop_caps: bool,
+
+    /// Order each figure with its caption, footnote and inner labels as one unit,
+    /// so no body text is read between them
+    pub group_figures: bool,
+
+    /// Read lists (stacked text boxes with a consistent left edge) strictly top to
+    /// bottom, as one unit
+    pub group_lists: bool,
+
+    /// Insert dense grids of aligned text boxes (tables detected cell by cell) as
+    /// one masked unit each, cells in row-major order
+    pub group_tables: bool,
+
+    /// Which formulas are display equations, masked and kept between the paragraphs
+    /// around them, and which are ordered as text
+    pub formulas: FormulaHandling,
+
+    /// Masked elements at least this share of the page width wide (full-width tables
+    /// and figures) split the regular elements into bands above and below them,
+    /// each cut on its own, instead of only being inserted after cutting
+    pub masked_separators: Option<f32>,
+
+    /// Only elements within this multiple of a masked element's larger side (gap
+    /// between the boxes) are searched for its anchor, unless none of them qualifies
+    pub anchor_radius: Option<f32>,
+
+    /// When Equation 7 rules out every ordered element as a masked element's anchor,
+    /// search again ignoring label priorities instead of leaving it unanchored
+    pub relax_priorities: bool,
+
+    /// Order of the insertions within a priority group; `Dependency` also
+    /// re-evaluates each insertion once every masked element is placed
+    pub insertion_order: InsertionOrder,
+
+    /// Where masked elements with no valid anchor go; they are reported either way
+    pub unanchored: UnanchoredPolicy,
+
+    /// Cut between columns instead of across a region when the horizontal cut would
+    /// end one column but split the other (a short last column)
+    pub column_consistency: bool,
+
+    /// When a region has no clean cut, retry without its largest elements
+    /// (text wrapping around insets, L-shaped regions)
+    pub region_subtraction: bool,
+
+    /// Estimate the page skew and cut along the rotated axes (slightly rotated scans)
+    pub deskew: bool,
+
+    /// Rotation of the page content; `Auto` detects sideways and upside-down pages
+    pub orientation: PageOrientation,
+
+    /// Fraction of outlying boxes trimmed per side when estimating the content area
+    /// for the centrality check of Equation 3 (`None` uses the full page)
+    pub content_trim: Option<f32>,
+
+    /// Size thresholds below which boxes are dropped as speckle noise
+    /// (see [`detect_noise`] for the dropped ids)
+    pub noise: NoiseFilter,
+
+    /// Fraction of the page area above which a box is a page-sized background,
+    /// excluded from cutting
+    pub background_coverage: f32,
+
+    /// Keep background boxes in the order (first) instead of dropping them
+    pub keep_background: bool,
+
+    /// Fraction of a region's elements allowed to straddle a cut when the region
+    /// has no empty gap (`0.0` requires clean cuts)
+    pub cut_overlap_tolerance: f32,
+
+    /// How elements are assigned to the sides of a cut
+    pub split_assignment: SplitAssignment,
+
+    /// Set aside elements with a significant part on both sides of a cut and
+    /// reinsert them like cross-layout elements
+    pub promote_straddlers: bool,
+
+    /// Swap elements ordered after a box strictly below them in the same column
+    pub repair_inversions: bool,
+
+    /// Snap coordinates to a grid with this many steps per unit before ordering
+    /// (powers of two keep the snapped values exact), making orders bit-identical
+    /// across platforms; ordering itself stays in `f32` and relies on IEEE-754
+    /// rounding, not integer arithmetic. Disables `deskew`, which relies on platform
+    /// trigonometry
+    pub fixed_point: Option<u32>,
+
+    /// Snap box edges lying within this distance of each other to a common
+    /// coordinate before ordering (see [`snap_edges`]), for noisy detector output
+    pub snap_edges: Option<f32>,
+
+    /// Round coordinates to a grid this many units wide, anchored at the page's
+    /// top-left corner, before ordering, so re-detected pages whose boxes moved by a
+    /// fraction of it get the same order
+    pub quantization: Option<f32>,
+
+    /// On pages of at least this many elements, first group regular boxes that no
+    /// cut can separate into tiles, order the tiles, then the boxes within each tile
+    /// (see [`bin_tiles`]); keeps word-level pages of 100k boxes near O(n log n) at
+    /// the price of placing masked elements between tiles rather than words
+    pub pre_binning: Option<usize>,
+
+    /// ϕ3 variant and penalty used when inserting masked elements
+    pub distance: DistanceWeights,
+
+    /// How regions without a cut are ordered
+    pub fallback: FallbackSort,
+
+    /// Label ranks for insertion and the label-aware fallback sort
+    pub label_priorities: LabelPriorities,
 }
 
 impl Default for XYCutConfig {
     fn default() -> Self {
         Self {
+            algorithm: Algorithm::XYCutPlusPlus,
+            density: DensityEstimator::AspectRatio,
+            vertical_first_threshold: 0.9,
             min_cut_threshold: 15.0,
             histogram_resolution_scale: 0.5, // 1 bin per 2 pixels
             same_row_tolerance: 10.0,
+            adaptive_row_tolerance: None,
+            mask_confidence_threshold: 0.5,
+            marginalia: MarginaliaPolicy::Inline,
+            merge_drop_caps: false,
+            group_figures: false,
+            group_lists: false,
+            group_tables: false,
+            formulas: FormulaHandling::Auto,
+            masked_separators: None,
+            anchor_radius: None,
+            relax_priorities: false,
+            insertion_order: InsertionOrder::Rows,
+            unanchored: UnanchoredPolicy::Append,
+            column_consistency: false,
+            region_subtraction: false,
+            deskew: false,
+            orientation: PageOrientation::Upright,
+            content_trim: None,
+            noise: NoiseFilter::default(),
+            background_coverage: 0.9,
+            keep_background: true,
+            cut_overlap_tolerance: 0.0,
+            split_assignment: SplitAssignment::Center,
+            promote_straddlers: false,
+            repair_inversions: false,
+            fixed_point: None,
+            snap_edges: None,
+            quantization: None,
+            pre_binning: None,
+            distance: DistanceWeights::default(),
+            fallback: FallbackSort::default(),
+            label_priorities: LabelPriorities::default(),
         }
     }
 }
 
+/// Reading order detector
+///
+/// Holds nothing but its immutable config and every entry point takes `&self`, so it
+/// is `Send + Sync`: one instance can be shared by all threads of a server (in an
+/// `Arc` or a `static`) without locking.
 pub struct XYCutPlusPlus {
-    config: XYCutConfig,
+    pub(crate) config: XYCutConfig,
+}
+
+// Sharing one instance across threads is part of the API
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<XYCutPlusPlus>();
+    assert_send_sync::<XYCutConfig>();
+};
+
+/// Order `elements` on the page `(x_min, y_min, x_max, y_max)` with `config`
+///
+/// Stateless shorthand for [`XYCutPlusPlus::compute_order`], for callers that keep a
+/// shared config rather than a detector.
+pub fn order<T: BoundingBox>(
+    elements: &[T],
+    (x_min, y_min, x_max, y_max): (f32, f32, f32, f32),
+    config: &XYCutConfig,
+) -> Vec<usize> {
+    XYCutPlusPlus::new(config.clone()).compute_order(elements, x_min, y_min, x_max, y_max)
 }
 
 impl XYCutPlusPlus {
@@ -38,6 +347,10 @@ impl XYCutPlusPlus {
     }
 
     /// Main entry point: compute reading order for elements
+    ///
+    /// The result is deterministic: ties between positions are broken by the total
+    /// order of [`compare_position`](crate::utils::compare_position), so the same
+    /// boxes give the same order regardless of their order in `elements`.
     pub fn compute_order<T: BoundingBox>(
         &self,
         elements: &[T],
@@ -45,6 +358,36 @@ impl XYCutPlusPlus {
         y_min: f32,
         x_max: f32,
         y_max: f32,
+    ) -> Vec<usize> {
+        self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut Trace::default())
+    }
+
+    /// [`compute_order`](Self::compute_order), reporting each decision to `observer`
+    pub fn compute_order_observed<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        observer: &mut dyn OrderingObserver,
+    ) -> Vec<usize> {
+        let mut trace = Trace {
+            observer: Some(observer),
+            ..Trace::default()
+        };
+        self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace)
+    }
+
+    /// [`compute_order`](Self::compute_order), recording decisions into `trace`
+    pub(crate) fn compute_order_traced<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        trace: &mut Trace,
     ) -> Vec<usize> {
         // Validate empty input
         if elements.is_empty() {
@@ -54,35 +397,538 @@ impl XYCutPlusPlus {
         let page_width = x_max - x_min;
         let page_height = y_max - y_min;
 
-        // Validate page dimensions
+        // Validate page dimensions before snapping or rounding can change them
         if !page_width.is_finite()
             || !page_height.is_finite()
             || page_width <= 0.0
             || page_height <= 0.0
         {
-            eprintln!(
-                "Warning: Invalid page dimensions ({}, {})",
-                page_width, page_height
+            return Vec::new();
+        }
+
+        if let Some(scale) = self.config.fixed_point {
+            return self.compute_order_fixed(elements, (x_min, y_min, x_max, y_max), scale, trace);
+        }
+        if let Some(epsilon) = self.config.snap_edges {
+            let snapped = snap_edges(elements, epsilon);
+            let unsnapped = XYCutPlusPlus::new(XYCutConfig {
+                snap_edges: None,
+                ..self.config.clone()
+            });
+            return unsnapped.compute_order_traced(&snapped, x_min, y_min, x_max, y_max, trace);
+        }
+        if let Some(step) = self.config.quantization.filter(|step| *step > 0.0) {
+            return self.compute_order_quantized(
+                elements,
+                (x_min, y_min, x_max, y_max),
+                step,
+                trace,
             );
+        }
 
-            return Vec::new();
+        let PreparedPage {
+            elements,
+            width,
+            height,
+            noise,
+            background,
+        } = self.prepare_page(elements, (x_min, y_min, x_max, y_max));
+        trace.dropped.extend(&noise);
+        if !self.config.keep_background {
+            trace.dropped.extend(&background);
+        }
+        let order = self.order_upright(&elements, width, height, trace);
+        if cfg!(debug_assertions) {
+            let kept: Vec<Element> = elements
+                .iter()
+                .filter(|e| !trace.dropped.contains(&e.id()))
+                .cloned()
+                .collect();
+            if let Err(error) = validate_order(&kept, &order) {
+                panic!("invalid reading order: {error}");
+            }
+        }
+
+        // Background boxes contain the whole page, so they come first
+        if self.config.keep_background && !background.is_empty() {
+            return background.into_iter().chain(order).collect();
+        }
+        order
+    }
+
+    /// Order a prepared page whose text runs along the x axis
+    fn order_upright(
+        &self,
+        elements: &[Element],
+        width: f32,
+        height: f32,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        let order = self.order_preprocessed(elements, 0.0, 0.0, width, height, trace);
+        self.smooth_order(elements, order, trace)
+    }
+
+    /// The preprocessing shared by every entry point, up to the cut
+    ///
+    /// Drops noise, unplaceable and (unless kept) background boxes, grows the page to
+    /// hold the rest, turns it upright and corrects slight skew. The page must have a
+    /// valid size.
+    pub(crate) fn prepare_page<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        page: (f32, f32, f32, f32),
+    ) -> PreparedPage {
+        // Speckles neither block cuts nor appear in the order; page-sized
+        // background boxes would fill every histogram bin
+        let (noise, background) = detect_dropped(elements, &self.config, page);
+        let dropped: HashSet<usize> = noise.iter().chain(&background).copied().collect();
+        let kept: Vec<Element> = elements
+            .iter()
+            .filter(|e| !dropped.contains(&e.id()))
+            .map(Element::from_box)
+            .collect();
+
+        // Boxes reaching past the page (negative coordinates in PDF user space,
+        // margin notes outside the crop box) would be clamped into the edge bins
+        let (x_min, y_min, x_max, y_max) = grown_to_fit(&kept, page);
+
+        // Sideways and upside-down pages: order in the upright frame. Offset pages
+        // are moved to the origin too, since the insertion distance uses absolute
+        // coordinates and would otherwise round differently depending on the offset
+        let orientation = match self.config.orientation {
+            PageOrientation::Auto => detect_orientation(&kept),
+            orientation => orientation,
+        };
+        let (mut elements, width, height) =
+            if orientation != PageOrientation::Upright || (x_min, y_min) != (0.0, 0.0) {
+                rotate_upright(&kept, orientation, x_min, y_min, x_max, y_max)
+            } else {
+                (kept, x_max, y_max)
+            };
+
+        // Rotated scans: cut along the text axes instead of the image axes
+        if self.config.deskew {
+            let angle = estimate_skew(&elements);
+            if angle.abs() >= MIN_SKEW_DEGREES {
+                elements = deskew(&elements, angle, width / 2.0, height / 2.0);
+            }
+        }
+
+        PreparedPage {
+            elements,
+            width,
+            height,
+            noise,
+            background,
+        }
+    }
+
+    /// Optional post-order pass swapping obvious same-column inversions
+    fn smooth_order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        order: Vec<usize>,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        if !self.config.repair_inversions {
+            return order;
+        }
+        let (order, repairs) = repair_inversions(elements, &order);
+        trace.repairs += repairs;
+        order
+    }
+
+    /// Apply the optional element merges, then order the page
+    fn order_preprocessed<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        // Tables stacked between notes would be cut apart row by row
+        if self.config.group_tables {
+            if let Some((units, cells_of)) = group_tables(elements) {
+                return self
+                    .order_figures(&units, x_min, y_min, x_max, y_max, trace)
+                    .into_iter()
+                    .flat_map(|id| cells_of.get(&id).cloned().unwrap_or_else(|| vec![id]))
+                    .collect();
+            }
+        }
+
+        self.order_figures(elements, x_min, y_min, x_max, y_max, trace)
+    }
+
+    /// Group figures with their captions if configured, then order the page
+    fn order_figures<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        // Figures keep their captions and labels: order each group as one unit
+        if self.config.group_figures {
+            if let Some((units, members_of)) = group_figures(elements) {
+                return self
+                    .order_drop_caps(&units, x_min, y_min, x_max, y_max, trace)
+                    .into_iter()
+                    .flat_map(|id| members_of.get(&id).cloned().unwrap_or_else(|| vec![id]))
+                    .collect();
+            }
         }
 
-        let partition = partition_by_mask(elements, page_width, page_height);
-        let regular_order =
-            self.recursive_cut(&partition.regular_elements, x_min, y_min, x_max, y_max);
+        self.order_drop_caps(elements, x_min, y_min, x_max, y_max, trace)
+    }
+
+    /// Merge drop caps into their paragraph if configured, then order the page
+    fn order_drop_caps<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        // Drop caps overlap their paragraph's first lines: order them as one unit
+        if self.config.merge_drop_caps {
+            if let Some((units, drop_cap_of)) = merge_drop_caps(elements) {
+                return self
+                    .order_lists(&units, x_min, y_min, x_max, y_max, trace)
+                    .into_iter()
+                    .flat_map(|id| drop_cap_of.get(&id).copied().into_iter().chain([id]))
+                    .collect();
+            }
+        }
+
+        self.order_lists(elements, x_min, y_min, x_max, y_max, trace)
+    }
+
+    /// Group lists into units if configured, then order the page
+    fn order_lists<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        // Slightly misaligned items could be cut apart or read side by side
+        if self.config.group_lists {
+            if let Some((units, items_of)) = group_lists(elements) {
+                return self
+                    .order_body(&units, x_min, y_min, x_max, y_max, trace)
+                    .into_iter()
+                    .flat_map(|id| items_of.get(&id).cloned().unwrap_or_else(|| vec![id]))
+                    .collect();
+            }
+        }
+
+        self.order_body(elements, x_min, y_min, x_max, y_max, trace)
+    }
+
+    /// Order the page, handling marginalia according to the configured policy
+    fn order_body<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        if self
+            .config
+            .pre_binning
+            .is_some_and(|min_elements| elements.len() >= min_elements)
+        {
+            return self.order_binned(elements, x_min, y_min, x_max, y_max, trace);
+        }
+
+        // Take marginalia out of the main flow when a placement policy is set
+        if self.config.marginalia != MarginaliaPolicy::Inline {
+            let notes = detect_marginalia(elements);
+            if !notes.is_empty() {
+                let note_ids: HashSet<usize> = notes.iter().copied().collect();
+                let body: Vec<T> = elements
+                    .iter()
+                    .filter(|e| !note_ids.contains(&e.id()))
+                    .cloned()
+                    .collect();
+                let order = self.order_page(&body, x_min, y_min, x_max, y_max, trace);
+                if self.config.marginalia == MarginaliaPolicy::Exclude {
+                    trace.dropped.extend(&notes);
+                }
+                return place_marginalia(elements, order, &notes, self.config.marginalia);
+            }
+        }
 
-        self.merged_masked_elements(
-            &partition.regular_elements,
-            &regular_order,
-            &partition.masked_elements,
-        )
+        self.order_page(elements, x_min, y_min, x_max, y_max, trace)
+    }
+
+    /// Order the tiles of a very large page as single boxes, then the boxes of each tile
+    fn order_binned<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        // Wide boxes may be cross-layout elements, which the mask stage must see
+        let max_width = CROSS_LAYOUT_WIDTH_RATIO * compute_median_width(elements);
+        let min_gap_bins =
+            (self.config.min_cut_threshold * self.config.histogram_resolution_scale) as usize;
+        let gap = min_gap_bins as f32 / self.config.histogram_resolution_scale;
+        let tiles = bin_tiles(elements, gap, |e| {
+            let (x1, _, x2, _) = e.bounds();
+            e.semantic_label() == SemanticLabel::Regular && !e.should_mask() && x2 - x1 <= max_width
+        });
+
+        // Each tile stands in as one regular box with the id of its first element
+        let mut tile_of: HashMap<usize, usize> = HashMap::new();
+        let mut units: Vec<Element> = Vec::with_capacity(elements.len());
+        let mut tiled = vec![false; elements.len()];
+        for (index, tile) in tiles.iter().enumerate() {
+            let first = &elements[tile[0]];
+            let mut unit = Element::from_box(first);
+            for &i in tile {
+                let (x1, y1, x2, y2) = elements[i].bounds();
+                (unit.x1, unit.y1) = (unit.x1.min(x1), unit.y1.min(y1));
+                (unit.x2, unit.y2) = (unit.x2.max(x2), unit.y2.max(y2));
+                tiled[i] = true;
+            }
+            unit.confidence = None;
+            tile_of.insert(first.id(), index);
+            units.push(unit);
+        }
+        units.extend(
+            elements
+                .iter()
+                .zip(&tiled)
+                .filter(|(_, &tiled)| !tiled)
+                .map(|(e, _)| Element::from_box(e)),
+        );
+
+        let flat = XYCutPlusPlus::new(XYCutConfig {
+            pre_binning: None,
+            ..self.config.clone()
+        });
+        let unit_order = flat.order_body(&units, x_min, y_min, x_max, y_max, trace);
+
+        // Only overlap-tolerant cuts can split a tile, and no reinsertion stage is
+        // left to place the straddlers they would promote
+        let within = XYCutPlusPlus::new(XYCutConfig {
+            promote_straddlers: false,
+            ..flat.config
+        });
+        let mut order = Vec::with_capacity(elements.len());
+        for id in unit_order {
+            match tile_of.get(&id) {
+                Some(&index) => {
+                    let mut members: Vec<T> =
+                        tiles[index].iter().map(|&i| elements[i].clone()).collect();
+                    let unit = units[index].bounds();
+                    within.recursive_cut(&mut members, unit, trace, &mut order);
+                }
+                None => order.push(id),
+            }
+        }
+        order
+    }
+
+    /// Run the three XY-Cut++ stages on validated input:
+    /// pre-mask partition, recursive cutting and masked element reinsertion
+    fn order_page<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        let bounds = (x_min, y_min, x_max, y_max);
+
+        // Classic XY-Cut cuts every element, with no masking or reinsertion
+        if self.config.algorithm == Algorithm::ClassicXYCut {
+            let start = Instant::now();
+            // Cutting partitions a working copy in place
+            let mut elements = elements.to_vec();
+            let mut order = Vec::with_capacity(elements.len());
+            self.recursive_cut(&mut elements, bounds, trace, &mut order);
+            trace.profile.cutting += start.elapsed();
+            return order;
+        }
+
+        let partition = self.partition_page(elements, bounds, trace);
+        self.order_partition(&partition, bounds, trace)
+    }
+
+    /// Split `masked` into the separators of `masked_separators`, top to bottom, and
+    /// the elements left to the insertion stage
+    fn separators<T: BoundingBox>(
+        &self,
+        masked: &[T],
+        (x_min, _, x_max, _): (f32, f32, f32, f32),
+    ) -> (Vec<T>, Vec<T>) {
+        let Some(ratio) = self.config.masked_separators else {
+            return (Vec::new(), masked.to_vec());
+        };
+        let (mut separators, rest): (Vec<T>, Vec<T>) = masked.iter().cloned().partition(|e| {
+            let (x1, _, x2, _) = e.bounds();
+            x2 - x1 >= ratio * (x_max - x_min)
+        });
+        separators.sort_by(compare_position);
+        (separators, rest)
+    }
+
+    /// Cut the bands of `regular` between consecutive `separators` one after another,
+    /// each followed by its separator
+    fn cut_bands<T: BoundingBox>(
+        &self,
+        regular: &[T],
+        separators: &[T],
+        (x_min, y_min, x_max, y_max): (f32, f32, f32, f32),
+        trace: &mut Trace,
+        order: &mut Vec<usize>,
+    ) {
+        let mut bands: Vec<Vec<T>> = vec![Vec::new(); separators.len() + 1];
+        for e in regular {
+            let y = e.center().1;
+            let band = separators.iter().filter(|s| s.center().1 < y).count();
+            bands[band].push(e.clone());
+        }
+
+        for (index, mut band) in bands.into_iter().enumerate() {
+            if !band.is_empty() {
+                // The band between the separators, grown to hold its elements
+                let top = index
+                    .checked_sub(1)
+                    .map_or(y_min, |i| separators[i].bounds().3);
+                let bottom = separators.get(index).map_or(y_max, |s| s.bounds().1);
+                let (top, bottom) = band.iter().fold((top, bottom), |(top, bottom), e| {
+                    let (_, y1, _, y2) = e.bounds();
+                    (top.min(y1), bottom.max(y2))
+                });
+                self.recursive_cut(&mut band, (x_min, top, x_max, bottom), trace, order);
+            }
+            if let Some(separator) = separators.get(index) {
+                order.push(separator.id());
+            }
+        }
+    }
+
+    /// Pre-mask partition of the page into regular and masked elements
+    pub(crate) fn partition_page<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        bounds: (f32, f32, f32, f32),
+        trace: &mut Trace,
+    ) -> MaskPartition<T> {
+        // Centrality is judged against the content area when margin noise is trimmed
+        // or the area is known from the rest of the document
+        let page = trace
+            .content_area
+            .or_else(|| {
+                self.config
+                    .content_trim
+                    .and_then(|trim| estimate_content_area(elements, trim))
+            })
+            .unwrap_or(bounds);
+
+        let start = Instant::now();
+        let partition = partition_by_mask(elements, page, &self.config);
+        trace.profile.pre_mask += start.elapsed();
+        if trace.observing() {
+            trace.partition(
+                ids(&partition.regular_elements),
+                ids(&partition.masked_elements),
+            );
+        }
+        partition
+    }
+
+    /// Cut the regular elements of a partitioned page and reinsert the masked ones
+    pub(crate) fn order_partition<T: BoundingBox>(
+        &self,
+        partition: &MaskPartition<T>,
+        bounds: (f32, f32, f32, f32),
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        let start = Instant::now();
+        // Wide masked elements cut the page before they are set aside
+        let (separators, masked_elements) = self.separators(&partition.masked_elements, bounds);
+        let mut regular_order = Vec::with_capacity(partition.regular_elements.len());
+        if separators.is_empty() {
+            // Cutting partitions a working copy in place; the partition keeps page order
+            let mut working = partition.regular_elements.clone();
+            self.recursive_cut(&mut working, bounds, trace, &mut regular_order);
+        } else {
+            self.cut_bands(
+                &partition.regular_elements,
+                &separators,
+                bounds,
+                trace,
+                &mut regular_order,
+            );
+        }
+        trace.profile.cutting += start.elapsed();
+        // Separators are already ordered; the insertion stage may still anchor to them
+        let regular_elements: Vec<T> = partition
+            .regular_elements
+            .iter()
+            .chain(&separators)
+            .cloned()
+            .collect();
+
+        let promoted_ids = std::mem::take(&mut trace.promoted);
+        if promoted_ids.is_empty() {
+            return self.merged_masked_elements(
+                &regular_elements,
+                &regular_order,
+                &masked_elements,
+                trace,
+            );
+        }
+
+        // Elements straddling a cut are reinserted like cross-layout elements
+        let promoted_ids: HashSet<usize> = promoted_ids.into_iter().collect();
+        let regular: Vec<Element> = regular_elements
+            .iter()
+            .filter(|e| !promoted_ids.contains(&e.id()))
+            .map(Element::from_box)
+            .collect();
+        let masked: Vec<Element> = masked_elements
+            .iter()
+            .map(Element::from_box)
+            .chain(
+                partition
+                    .regular_elements
+                    .iter()
+                    .filter(|e| promoted_ids.contains(&e.id()))
+                    .map(|e| {
+                        let mut proxy = Element::from_box(e);
+                        proxy.label = SemanticLabel::CrossLayout;
+                        proxy.mask = true;
+                        proxy
+                    }),
+            )
+            .collect();
+        self.merged_masked_elements(&regular, &regular_order, &masked, trace)
     }
 
-    // TODO: Add this function before recursive_cut
     /// Calculate density ratio τd (tau_d) from Equation 4-5
     /// τd = Σ(w_k^(Cc) / h_k^(Cc)) / Σ(w_k^(Cs) / h_k^(Cs))
-    fn compute_density_ratio<T: BoundingBox>(elements: &[T]) -> f32 {
+    pub(crate) fn compute_density_ratio<T: BoundingBox>(elements: &[T]) -> f32 {
         let mut cross_layout_density = 0.0; // Cc - wide elements
         let mut single_layout_density = 0.0; // Cs - narrow elements
 
@@ -114,92 +960,444 @@ impl XYCutPlusPlus {
         cross_layout_density / single_layout_density
     }
 
+    /// Append the reading order of the region `bounds` to `order`
     fn recursive_cut<T: BoundingBox>(
         &self,
-        elements: &[T],
-        x_min: f32,
-        y_min: f32,
-        x_max: f32,
-        y_max: f32,
-    ) -> Vec<usize> {
-        if elements.is_empty() {
-            return Vec::new();
+        elements: &mut [T],
+        bounds: (f32, f32, f32, f32),
+        trace: &mut Trace,
+        order: &mut Vec<usize>,
+    ) {
+        let depth = trace.depth;
+        let mut regions = vec![Region {
+            range: 0..elements.len(),
+            bounds,
+            depth,
+        }];
+        while let Some(region) = regions.pop() {
+            self.cut_step(elements, region, &mut regions, trace, order);
         }
-        if elements.len() == 1 {
-            return vec![elements[0].id()];
+        trace.depth = depth;
+    }
+
+    /// Cut one region of `elements`, depth first
+    ///
+    /// A region that is finished (one element, sorted by position or cut around an
+    /// obstacle) has its ids appended to `order`; otherwise its two sides are pushed
+    /// onto `regions`, the side read first on top.
+    pub(crate) fn cut_step<T: BoundingBox>(
+        &self,
+        elements: &mut [T],
+        region: Region,
+        regions: &mut Vec<Region>,
+        trace: &mut Trace,
+        order: &mut Vec<usize>,
+    ) {
+        let Region {
+            range,
+            bounds,
+            depth,
+        } = region;
+        let start = range.start;
+        let elements = &mut elements[range];
+        if elements.len() <= 1 {
+            order.extend(elements.iter().map(|e| e.id()));
+            return;
+        }
+        if trace.record_state {
+            let (x_min, y_min, x_max, y_max) = bounds;
+            trace
+                .regions
+                .push(self.region_state(elements, x_min, y_min, x_max, y_max));
         }
 
-        // Equation 4: Calculate density ration τd
-        let tau_d = Self::compute_density_ratio(elements);
+        // Degenerate geometry (huge or non-finite coordinates) can make every cut
+        // peel off a sliver of empty margin; stop before exhausting the stack
+        trace.depth = depth;
+        if depth >= MAX_CUT_DEPTH {
+            self.fallback_sort(elements, trace, order);
+            return;
+        }
+        trace.depth = depth + 1;
+        trace.max_depth = trace.max_depth.max(trace.depth);
+        if let Some(sides) = self.cut_region(elements, bounds, trace, order) {
+            let middle = start + sides.before;
+            regions.push(Region {
+                range: middle..middle + sides.after,
+                bounds: sides.after_bounds,
+                depth: depth + 1,
+            });
+            regions.push(Region {
+                range: start..middle,
+                bounds: sides.before_bounds,
+                depth: depth + 1,
+            });
+        }
+    }
+
+    /// Cut a region of at least two elements, returning the sides still to be cut
+    fn cut_region<T: BoundingBox>(
+        &self,
+        elements: &mut [T],
+        bounds: (f32, f32, f32, f32),
+        trace: &mut Trace,
+        order: &mut Vec<usize>,
+    ) -> Option<Sides> {
+        let (x_min, y_min, x_max, y_max) = bounds;
 
         // Equation 5: Use XY-Cut (vertical first) if τd > 0.9
-        let try_vertical_first = tau_d > 0.9;
+        let try_vertical_first = self.config.algorithm == Algorithm::XYCutPlusPlus && {
+            // Equation 4: Calculate density ration τd
+            let tau_d = self.density(elements, bounds, &mut trace.workspace.histogram);
+            if trace.observing() {
+                trace.density(tau_d, &ids(elements));
+            }
+            // The caller's preference for this region goes before the rule
+            let preference = trace
+                .cut_preference
+                .map_or(CutPreference::Auto, |preference| {
+                    preference(&RegionStats {
+                        bounds,
+                        ids: &ids(elements),
+                        depth: trace.depth - 1,
+                        density: tau_d,
+                    })
+                });
+            match preference {
+                CutPreference::Auto => tau_d > self.config.vertical_first_threshold,
+                CutPreference::VerticalFirst => true,
+                CutPreference::HorizontalFirst => false,
+            }
+        };
 
         if try_vertical_first {
             // Try vertical cut first for multi-column layouts
-            if let Some(x_cut) = self.find_vertical_cut(elements, x_min, x_max) {
-                eprintln!(
-                    "  [XYCut] Vertical cut at x={:.0}, splitting {} elements (multi-column)",
-                    x_cut,
-                    elements.len()
-                );
-                let (left, right) = self.split_vertical(elements, x_cut);
-                eprintln!(
-                    "    → Left: {} elements, Right: {} elements",
-                    left.len(),
-                    right.len()
-                );
-                let mut result = Vec::new();
-                result.extend(self.recursive_cut(&left, x_min, y_min, x_cut, y_max));
-                result.extend(self.recursive_cut(&right, x_cut, y_min, x_max, y_max));
-                return result;
+            if let Some(x_cut) =
+                self.find_vertical_cut(elements, x_min, x_max, &mut trace.workspace.histogram)
+            {
+                return self.cut_at(elements, projection(Axis::X, x_cut, false), bounds, trace);
+            }
+            if let Some(sides) = self.hinted_cut(elements, bounds, trace) {
+                return Some(sides);
             }
         }
 
         // Try horizontal cut first (top-to-bottom reading)
-        if let Some(y_cut) = self.find_horizontal_cut(elements, y_min, y_max) {
-            eprintln!(
-                "  [XYCut] Horizontal cut at y={:.0}, splitting {} elements",
-                y_cut,
-                elements.len()
-            );
-            let (top, bottom) = self.split_horizontal(elements, y_cut);
-            eprintln!(
-                "    → Top: {} elements, Bottom: {} elements",
-                top.len(),
-                bottom.len()
-            );
-            let mut result = Vec::new();
-            result.extend(self.recursive_cut(&top, x_min, y_min, x_max, y_cut));
-            result.extend(self.recursive_cut(&bottom, x_min, y_cut, x_max, y_max));
-            return result;
+        if let Some(y_cut) =
+            self.find_horizontal_cut(elements, y_min, y_max, &mut trace.workspace.histogram)
+        {
+            // Columns of unequal length would be read interleaved
+            if let Some(x_cut) = self.column_cut(elements, y_cut, &mut trace.workspace.histogram) {
+                return self.cut_at(elements, projection(Axis::X, x_cut, false), bounds, trace);
+            }
+            return self.cut_at(elements, projection(Axis::Y, y_cut, false), bounds, trace);
         }
 
         // Try vertical cut (left-to-right for multi-column)
-        if let Some(x_cut) = self.find_vertical_cut(elements, x_min, x_max) {
-            eprintln!(
-                "  [XYCut] Vertical cut at x={:.0}, splitting {} elements",
-                x_cut,
-                elements.len()
-            );
-            let (left, right) = self.split_vertical(elements, x_cut);
-            eprintln!(
-                "    → Left: {} elements, Right: {} elements",
-                left.len(),
-                right.len()
-            );
-            let mut result = Vec::new();
-            result.extend(self.recursive_cut(&left, x_min, y_min, x_cut, y_max));
-            result.extend(self.recursive_cut(&right, x_cut, y_min, x_max, y_max));
-            return result;
+        if let Some(x_cut) =
+            self.find_vertical_cut(elements, x_min, x_max, &mut trace.workspace.histogram)
+        {
+            return self.cut_at(elements, projection(Axis::X, x_cut, false), bounds, trace);
+        }
+
+        // Gutters carried from earlier pages of the document
+        if let Some(sides) = self.hinted_cut(elements, bounds, trace) {
+            return Some(sides);
+        }
+
+        // Boxes slightly overlapping a gutter leave no empty bins
+        if let Some(sides) = self.tolerant_cut(elements, bounds, trace) {
+            return Some(sides);
+        }
+
+        // No cut through the whole region: try cutting around a blocking element
+        if self.config.region_subtraction
+            && self.cut_around_obstacle(elements, bounds, trace, order)
+        {
+            return None;
         }
 
         // No valid cuts found - sort by position
-        eprintln!(
-            "  [XYCut] No cuts found, sorting {} elements by position",
-            elements.len()
+        self.fallback_sort(elements, trace, order);
+        None
+    }
+
+    /// Split `elements` by a projection cut, returning the two sides
+    ///
+    /// The sides are partitioned in place to the front of `elements`, before the
+    /// promoted straddlers, so regions never copy their elements. Tolerant cuts
+    /// assign straddlers by `MaxOverlap` and are abandoned (`None`, with the elements
+    /// untouched) when one side would be empty.
+    fn cut_at<T: BoundingBox>(
+        &self,
+        elements: &mut [T],
+        cut: Cut,
+        (x_min, y_min, x_max, y_max): (f32, f32, f32, f32),
+        trace: &mut Trace,
+    ) -> Option<Sides> {
+        let Cut::Projection {
+            axis,
+            position,
+            tolerant,
+        } = cut
+        else {
+            return None;
+        };
+        let assignment = if tolerant {
+            SplitAssignment::MaxOverlap
+        } else {
+            self.config.split_assignment
+        };
+        let plan = &mut trace.workspace.split;
+        let split = match axis {
+            Axis::X => self.split_vertical(elements, position, assignment, plan),
+            Axis::Y => self.split_horizontal(elements, position, assignment, plan),
+        };
+        if tolerant && (split.before == 0 || split.after == 0) {
+            return None;
+        }
+
+        if trace.observing() {
+            trace.cut(cut, &ids(elements));
+        }
+        // Cuts peeling off an empty margin separate no columns
+        if trace.record_columns && axis == Axis::X && split.before > 0 && split.after > 0 {
+            trace.columns.push(position);
+        }
+        let (before, after, straddlers) = split.apply(elements, &mut trace.workspace.split);
+        trace.promoted.extend(straddlers.iter().map(|e| e.id()));
+        self.flag_marginal_cut(before, after, axis, trace);
+
+        let (before_bounds, after_bounds) = match axis {
+            Axis::X => (
+                (x_min, y_min, position, y_max),
+                (position, y_min, x_max, y_max),
+            ),
+            Axis::Y => (
+                (x_min, y_min, x_max, position),
+                (x_min, position, x_max, y_max),
+            ),
+        };
+        Some(Sides {
+            before: split.before,
+            after: split.after,
+            before_bounds,
+            after_bounds,
+        })
+    }
+
+    /// Fallback sorting when no valid cuts found
+    /// Sort by y-position first (top to bottom), then x-position (left to right)
+    fn fallback_sort<T: BoundingBox>(
+        &self,
+        elements: &mut [T],
+        trace: &mut Trace,
+        order: &mut Vec<usize>,
+    ) {
+        self.config.fallback.sort(
+            elements,
+            self.row_tolerance(elements),
+            &self.config.label_priorities,
         );
-        self.sort_by_position(elements)
+        let start = order.len();
+        order.extend(elements.iter().map(|e| e.id()));
+        trace.fallback(&order[start..]);
+    }
+
+    /// Tolerance for placing `elements` in the same row
+    ///
+    /// An adaptive tolerance that comes out at 0 (zero-height spans such as hOCR or
+    /// PDF baselines) falls back to `same_row_tolerance`.
+    fn row_tolerance<T: BoundingBox>(&self, elements: &[T]) -> f32 {
+        match self.config.adaptive_row_tolerance {
+            Some(ratio) if ratio * compute_median_height(elements) > 0.0 => {
+                ratio * compute_median_height(elements)
+            }
+            _ => self.config.same_row_tolerance,
+        }
+    }
+
+    /// Gutter to cut at instead of the horizontal cut at `y_cut`, when
+    /// `column_consistency` is set and that cut would leave one column entirely above
+    /// it while splitting the other
+    ///
+    /// The gutter must be clear over the region's full height; it is searched within
+    /// the elements' extent so empty margins don't hide it.
+    fn column_cut<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        y_cut: f32,
+        histogram: &mut Vec<usize>,
+    ) -> Option<f32> {
+        if !self.config.column_consistency {
+            return None;
+        }
+        let (x_min, x_max) =
+            elements
+                .iter()
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(x_min, x_max), e| {
+                    let (x1, _, x2, _) = e.bounds();
+                    (x_min.min(x1), x_max.max(x2))
+                });
+        let x_cut = self.find_vertical_cut(elements, x_min, x_max, histogram)?;
+
+        // Whether a column has elements above and below the horizontal cut
+        let extent = |left: bool| {
+            elements
+                .iter()
+                .filter(|e| (e.center().0 < x_cut) == left)
+                .fold((false, false), |(above, below), e| {
+                    let y = e.center().1;
+                    (above || y < y_cut, below || y >= y_cut)
+                })
+        };
+        let (left, right) = (extent(true), extent(false));
+        let ended = |(above, below): (bool, bool)| above && !below;
+        let split = |(above, below): (bool, bool)| above && below;
+        ((ended(left) && split(right)) || (split(left) && ended(right))).then_some(x_cut)
+    }
+
+    /// Vertical cut at a gutter carried from an earlier page (see
+    /// [`compute_orders_with_column_hints`](Self::compute_orders_with_column_hints))
+    fn hinted_cut<T: BoundingBox>(
+        &self,
+        elements: &mut [T],
+        bounds: (f32, f32, f32, f32),
+        trace: &mut Trace,
+    ) -> Option<Sides> {
+        let (x_min, _, x_max, _) = bounds;
+        let forced = trace.hint_mode == ColumnHints::Forced;
+        for i in 0..trace.column_hints.len() {
+            let x = trace.column_hints[i];
+            if x <= x_min || x >= x_max {
+                continue;
+            }
+            if !forced {
+                let clear = elements.iter().all(|e| {
+                    let (x1, _, x2, _) = e.bounds();
+                    x2 <= x || x1 >= x
+                });
+                let both_sides = elements.iter().any(|e| e.bounds().2 <= x)
+                    && elements.iter().any(|e| e.bounds().0 >= x);
+                if !clear || !both_sides {
+                    continue;
+                }
+            }
+            // Forced cuts split like tolerant ones, abandoned when a side is empty
+            if let Some(sides) =
+                self.cut_at(elements, projection(Axis::X, x, forced), bounds, trace)
+            {
+                return Some(sides);
+            }
+        }
+        None
+    }
+
+    /// Overlap-tolerant cut through bins covered by at most
+    /// `cut_overlap_tolerance` × element count boxes, vertical first
+    ///
+    /// Straddling elements go to the side holding the larger part of their box,
+    /// unless promoted (see `promote_straddlers`).
+    fn tolerant_cut<T: BoundingBox>(
+        &self,
+        elements: &mut [T],
+        bounds: (f32, f32, f32, f32),
+        trace: &mut Trace,
+    ) -> Option<Sides> {
+        let max_occupancy = (self.config.cut_overlap_tolerance * elements.len() as f32) as usize;
+        if max_occupancy == 0 {
+            return None;
+        }
+        let min_gap_bins =
+            (self.config.min_cut_threshold * self.config.histogram_resolution_scale) as usize;
+        let (x_min, y_min, x_max, y_max) = bounds;
+
+        let resolution = self.histogram_resolution(x_max - x_min);
+        let histogram = &mut trace.workspace.histogram;
+        fill_vertical_histogram(elements, x_min, x_max, resolution, histogram);
+        if let Some(bin) = find_tolerant_gap(histogram, min_gap_bins, max_occupancy) {
+            let x_cut = bin_coordinate(x_min, x_max, bin, resolution);
+            let sides = self.cut_at(elements, projection(Axis::X, x_cut, true), bounds, trace);
+            if sides.is_some() {
+                return sides;
+            }
+        }
+
+        let resolution = self.histogram_resolution(y_max - y_min);
+        let histogram = &mut trace.workspace.histogram;
+        fill_horizontal_histogram(elements, y_min, y_max, resolution, histogram);
+        let bin = find_tolerant_gap(histogram, min_gap_bins, max_occupancy)?;
+        let y_cut = bin_coordinate(y_min, y_max, bin, resolution);
+        self.cut_at(elements, projection(Axis::Y, y_cut, true), bounds, trace)
+    }
+
+    /// Region-subtraction cut for text wrapping around an inset
+    ///
+    /// Removes one of the largest elements of the region (the figure the text wraps
+    /// around, or a box bridging two columns); if the remaining elements can then be
+    /// cut, they are ordered recursively and the removed element is placed before the
+    /// first element starting below its top edge within its horizontal span.
+    fn cut_around_obstacle<T: BoundingBox>(
+        &self,
+        elements: &mut [T],
+        bounds: (f32, f32, f32, f32),
+        trace: &mut Trace,
+        order: &mut Vec<usize>,
+    ) -> bool {
+        if elements.len() < 3 {
+            return false;
+        }
+        let (x_min, y_min, x_max, y_max) = bounds;
+
+        let area = |e: &T| {
+            let (x1, y1, x2, y2) = e.bounds();
+            (x2 - x1) * (y2 - y1)
+        };
+        let mut candidates: Vec<usize> = (0..elements.len()).collect();
+        candidates.sort_by(|&a, &b| {
+            area(&elements[b])
+                .partial_cmp(&area(&elements[a]))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        for &index in candidates.iter().take(MAX_OBSTACLE_CANDIDATES) {
+            // Set the obstacle aside at the end, keeping the rest in order
+            elements[index..].rotate_left(1);
+            let (rest, obstacle) = elements.split_at_mut(elements.len() - 1);
+            let obstacle = &obstacle[0];
+
+            let histogram = &mut trace.workspace.histogram;
+            let can_cut = self
+                .find_vertical_cut(rest, x_min, x_max, histogram)
+                .is_some()
+                || self
+                    .find_horizontal_cut(rest, y_min, y_max, histogram)
+                    .is_some();
+            if !can_cut {
+                elements[index..].rotate_right(1);
+                continue;
+            }
+
+            if trace.observing() {
+                let region: Vec<usize> = ids(rest).into_iter().chain([obstacle.id()]).collect();
+                trace.cut(Cut::Obstacle(obstacle.id()), &region);
+            }
+            let start = order.len();
+            self.recursive_cut(rest, bounds, trace, order);
+
+            let (ox1, oy1, ox2, _) = obstacle.bounds();
+            let by_id: HashMap<usize, &T> = rest.iter().map(|e| (e.id(), e)).collect();
+            let position = order[start..].iter().position(|id| {
+                by_id.get(id).is_some_and(|e| {
+                    let (x1, y1, x2, _) = e.bounds();
+                    y1 >= oy1 && x1 < ox2 && x2 > ox1
+                })
+            });
+            order.insert(position.map_or(order.len(), |p| start + p), obstacle.id());
+            return true;
+        }
+
+        false
     }
 
     /// Find horizontal cut position using projection histogram
@@ -209,17 +1407,25 @@ impl XYCutPlusPlus {
         elements: &[T],
         y_min: f32,
         y_max: f32,
+        histogram: &mut Vec<usize>,
     ) -> Option<f32> {
-        let resolution = ((y_max - y_min) * self.config.histogram_resolution_scale) as usize;
-        let histogram = build_horizontal_histogram(elements, y_min, y_max, resolution);
+        let resolution = self.histogram_resolution(y_max - y_min);
+        fill_horizontal_histogram(elements, y_min, y_max, resolution, histogram);
 
         let min_gap_bins =
             (self.config.min_cut_threshold * self.config.histogram_resolution_scale) as usize;
 
-        let bin_index = find_largest_gap(&histogram, min_gap_bins);
+        let bin_index = find_largest_gap(histogram, min_gap_bins);
 
         if let Some(bin_index) = bin_index {
-            let y_coord = y_min + (bin_index as f32 / resolution as f32) * (y_max - y_min);
+            let y_coord = bin_coordinate(y_min, y_max, bin_index, resolution);
+            let straddled = elements.iter().any(|e| {
+                let (_, y1, _, y2) = e.bounds();
+                y1 < y_coord && y_coord < y2
+            });
+            if straddled && self.config.split_assignment == SplitAssignment::NoSplitIfStraddling {
+                return None;
+            }
             return Some(y_coord);
         }
 
@@ -233,29 +1439,23 @@ impl XYCutPlusPlus {
         elements: &[T],
         x_min: f32,
         x_max: f32,
+        histogram: &mut Vec<usize>,
     ) -> Option<f32> {
-        let resolution = ((x_max - x_min) * self.config.histogram_resolution_scale) as usize;
-        let histogram = build_vertical_histogram(elements, x_min, x_max, resolution);
+        let resolution = self.histogram_resolution(x_max - x_min);
+        fill_vertical_histogram(elements, x_min, x_max, resolution, histogram);
 
         let min_gap_bins =
             (self.config.min_cut_threshold * self.config.histogram_resolution_scale) as usize;
 
-        // Debug: show histogram for large element counts
-        if elements.len() > 15 {
-            eprintln!(
-                "    [Histogram] Vertical: {} bins, min_gap={}, x_range={:.0}-{:.0}",
-                resolution, min_gap_bins, x_min, x_max
-            );
-        }
-
-        let bin_index = find_largest_gap(&histogram, min_gap_bins);
+        let bin_index = find_largest_gap(histogram, min_gap_bins);
         if let Some(bin_index) = bin_index {
-            let x_coord = x_min + (bin_index as f32 / resolution as f32) * (x_max - x_min);
-            if elements.len() > 15 {
-                eprintln!(
-                    "    [Histogram] Found gap at bin {}, x={:.0}",
-                    bin_index, x_coord
-                );
+            let x_coord = bin_coordinate(x_min, x_max, bin_index, resolution);
+            let straddled = elements.iter().any(|e| {
+                let (x1, _, x2, _) = e.bounds();
+                x1 < x_coord && x_coord < x2
+            });
+            if straddled && self.config.split_assignment == SplitAssignment::NoSplitIfStraddling {
+                return None;
             }
             return Some(x_coord);
         }
@@ -263,65 +1463,226 @@ impl XYCutPlusPlus {
         None
     }
 
-    /// Split elements into top and bottom groups based on y-coordinate cut
-    fn split_horizontal<T: BoundingBox>(&self, elements: &[T], y_cut: f32) -> (Vec<T>, Vec<T>) {
-        let mut top: Vec<T> = Vec::new();
-        let mut bottom: Vec<T> = Vec::new();
+    /// Plan the split of elements into top and bottom groups at a y-coordinate cut
+    ///
+    /// Straddlers are only set aside when `promote_straddlers` is enabled.
+    fn split_horizontal<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        y_cut: f32,
+        assignment: SplitAssignment,
+        plan: &mut Vec<usize>,
+    ) -> Split {
+        self.split_at(elements, y_cut, assignment, plan, |e| {
+            let (_, y1, _, y2) = e.bounds();
+            (y1, y2, e.center().1)
+        })
+    }
+
+    /// Plan the split of elements into left and right groups at an x-coordinate cut
+    ///
+    /// Straddlers are only set aside when `promote_straddlers` is enabled.
+    fn split_vertical<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_cut: f32,
+        assignment: SplitAssignment,
+        plan: &mut Vec<usize>,
+    ) -> Split {
+        self.split_at(elements, x_cut, assignment, plan, |e| {
+            let (x1, _, x2, _) = e.bounds();
+            (x1, x2, e.center().0)
+        })
+    }
 
+    /// Plan a split along one axis; `extent` gives an element's `(start, end, center)`
+    /// on that axis
+    ///
+    /// `plan` receives each element's position in the stable partition
+    /// `[before | after | straddlers]`, for [`Split::apply`].
+    fn split_at<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        cut: f32,
+        assignment: SplitAssignment,
+        plan: &mut Vec<usize>,
+        extent: impl Fn(&T) -> (f32, f32, f32),
+    ) -> Split {
+        // Classic XY-Cut has no reinsertion stage to promote to
+        let promote =
+            self.config.promote_straddlers && self.config.algorithm != Algorithm::ClassicXYCut;
+
+        plan.clear();
+        let mut counts = [0; 3];
         for element in elements.iter() {
-            if element.center().1 < y_cut {
-                top.push(element.clone());
+            let (start, end, center) = extent(element);
+
+            // Significant extent on both sides of the cut
+            let length = end - start;
+            let straddles = length > 0.0 && (cut - start).min(end - cut) / length >= STRADDLE_SHARE;
+            let group = if straddles && promote {
+                2
             } else {
-                bottom.push(element.clone())
-            }
+                let goes_before = match assignment {
+                    SplitAssignment::MaxOverlap => cut - start >= end - cut,
+                    SplitAssignment::Center | SplitAssignment::NoSplitIfStraddling => center < cut,
+                };
+                usize::from(!goes_before)
+            };
+            counts[group] += 1;
+            plan.push(group);
         }
 
-        (top, bottom)
-    }
+        let mut next = [0, counts[0], counts[0] + counts[1]];
+        for slot in plan.iter_mut() {
+            let group = *slot;
+            *slot = next[group];
+            next[group] += 1;
+        }
 
-    /// Split elements into left and right groups based on x-coordinate cut
-    fn split_vertical<T: BoundingBox>(&self, elements: &[T], x_cut: f32) -> (Vec<T>, Vec<T>) {
-        let mut left: Vec<T> = Vec::new();
-        let mut right: Vec<T> = Vec::new();
+        Split {
+            before: counts[0],
+            after: counts[1],
+        }
+    }
 
-        for element in elements.iter() {
-            if element.center().0 < x_cut {
-                left.push(element.clone());
-            } else {
-                right.push(element.clone());
+    /// Flag a cut as ambiguous when the whitespace between its two sides is barely
+    /// wider than `min_cut_threshold`
+    fn flag_marginal_cut<T: BoundingBox>(
+        &self,
+        before: &[T],
+        after: &[T],
+        axis: Axis,
+        trace: &mut Trace,
+    ) {
+        let tracking = trace.track_ambiguity || trace.track_confidence;
+        if !tracking || before.is_empty() || after.is_empty() {
+            return;
+        }
+        let extent = |e: &T| {
+            let (x1, y1, x2, y2) = e.bounds();
+            match axis {
+                Axis::Y => (y1, y2),
+                Axis::X => (x1, x2),
             }
+        };
+        let before_end = before
+            .iter()
+            .map(|e| extent(e).1)
+            .fold(f32::NEG_INFINITY, f32::max);
+        let after_start = after
+            .iter()
+            .map(|e| extent(e).0)
+            .fold(f32::INFINITY, f32::min);
+        let gap = after_start - before_end;
+        if trace.track_confidence {
+            trace.cut_gaps.push(CutGap {
+                gap,
+                before: ids(before),
+                after: ids(after),
+            });
         }
+        if trace.track_ambiguity && gap < MARGINAL_GAP_RATIO * self.config.min_cut_threshold {
+            let ids = before.iter().chain(after).map(|e| e.id()).collect();
+            trace
+                .ambiguities
+                .push((AmbiguityKind::MarginalCut { gap }, ids));
+        }
+    }
 
-        (left, right)
+    /// Number of histogram bins covering `extent` pixels
+    pub(crate) fn histogram_resolution(&self, extent: f32) -> usize {
+        ((extent * self.config.histogram_resolution_scale) as usize).min(MAX_HISTOGRAM_BINS)
     }
 
-    /// Fallback sorting when no valid cuts found
-    /// Sort by y-position first (top to bottom), then x-position (left to right)
-    fn sort_by_position<T: BoundingBox>(&self, elements: &[T]) -> Vec<usize> {
-        let mut indexed: Vec<(usize, T)> = elements
-            .iter()
-            .enumerate()
-            .map(|(i, bbox)| (i, bbox.clone()))
-            .collect();
+    /// Nearest valid anchor in `result` for `masked` (Equations 7 and 8)
+    ///
+    /// With `anchor_radius`, the elements near `masked` are searched first, then the
+    /// whole order if none of them is a valid anchor. The runner-up distance is exact
+    /// only with `exact_runner_up`; otherwise it may be missing or a partial sum.
+    fn nearest_anchor<T: BoundingBox>(
+        &self,
+        masked: &T,
+        result: &[usize],
+        lookup: &HashMap<usize, &T>,
+        exact_runner_up: bool,
+    ) -> Anchor {
+        let priorities = &self.config.label_priorities;
+        // Get masked element's semantic priority for constraint checking
+        let masked_priority = priorities.rank(masked.semantic_label());
+        let (x1, y1, x2, y2) = masked.bounds();
+        let radius = self
+            .config
+            .anchor_radius
+            .map(|ratio| ratio * (x2 - x1).max(y2 - y1));
 
-        indexed.sort_by(|a, b| {
-            let y_diff = (a.1.center().1 - b.1.center().1).abs();
-            if y_diff < self.config.same_row_tolerance {
-                // Same row - sort by x
-                a.1.center()
-                    .0
-                    .partial_cmp(&b.1.center().0)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            } else {
-                // Different rows - sort by y
-                a.1.center()
-                    .1
-                    .partial_cmp(&b.1.center().1)
-                    .unwrap_or(std::cmp::Ordering::Equal)
+        let mut anchor = Anchor {
+            position: None,
+            distance: f32::INFINITY,
+            runner_up: None,
+            outranked: true,
+        };
+        let scan = |relaxed: bool, radius: Option<f32>, anchor: &mut Anchor| {
+            // Search through result to handle growing array correctly
+            for (idx, &elem_id) in result.iter().enumerate() {
+                // Find the element - could be regular OR previously inserted masked
+                let Some(&candidate) = lookup.get(&elem_id) else {
+                    continue;
+                };
+                if radius.is_some_and(|radius| box_gap(masked, candidate) > radius) {
+                    continue;
+                }
+
+                // Enforce L'o ⪰ l constraint (Equation 7)
+                let candidate_priority = priorities.rank(candidate.semantic_label());
+                if !relaxed && candidate_priority < masked_priority {
+                    continue;
+                }
+                anchor.outranked = false;
+
+                // Use 4-component distance metric
+                let distance = compute_weighted_distance(
+                    masked,
+                    candidate,
+                    anchor.distance,
+                    &self.config.distance,
+                );
+                if distance < anchor.distance {
+                    if let Some(position) = anchor.position {
+                        anchor.runner_up = Some((anchor.distance, result[position]));
+                    }
+                    anchor.distance = distance;
+                    anchor.position = Some(idx);
+                } else if exact_runner_up {
+                    // An early exit returns a partial distance; the runner-up
+                    // needs the full one. The choice itself must not change:
+                    // negative ϕ3 terms make the early exit differ from the argmin
+                    let distance = compute_weighted_distance(
+                        masked,
+                        candidate,
+                        f32::INFINITY,
+                        &self.config.distance,
+                    );
+                    if anchor.runner_up.is_none_or(|(d, _)| distance < d) {
+                        anchor.runner_up = Some((distance, elem_id));
+                    }
+                }
             }
-        });
+        };
 
-        indexed.iter().map(|(_, bbox)| bbox.id()).collect()
+        // A second pass without the constraint, when enabled and it ruled out
+        // every candidate
+        for relaxed in [false, true] {
+            if relaxed && !(self.config.relax_priorities && anchor.outranked && !result.is_empty())
+            {
+                break;
+            }
+            scan(relaxed, radius, &mut anchor);
+            if radius.is_some() && anchor.position.is_none() {
+                scan(relaxed, None, &mut anchor);
+            }
+        }
+        anchor
     }
 
     fn merged_masked_elements<T: BoundingBox>(
@@ -329,105 +1690,378 @@ impl XYCutPlusPlus {
         regular_elements: &[T],
         regular_order: &[usize],
         masked_elements: &[T],
+        trace: &mut Trace,
     ) -> Vec<usize> {
-        // Start with regular order as base
-        let mut result: Vec<usize> = regular_order.to_vec();
+        let start = Instant::now();
+
+        // Start with regular order as base, in the workspace's result buffer
+        let mut result = std::mem::take(&mut trace.workspace.order);
+        result.clear();
+        result.extend_from_slice(regular_order);
+
+        // Candidate anchors by id, the first element holding a shared id
+        let mut lookup: HashMap<usize, &T> = HashMap::new();
+        for e in regular_elements.iter().chain(masked_elements) {
+            lookup.entry(e.id()).or_insert(e);
+        }
 
-        let mut priority_groups: Vec<Vec<T>> = vec![Vec::new(); 4];
+        let priorities = &self.config.label_priorities;
+        let mut priority_groups: Vec<Vec<T>> = Vec::new();
         for element in masked_elements {
-            let priority = Self::label_priority(element.semantic_label()) as usize;
-            if priority < 4 {
-                priority_groups[priority].push(element.clone());
+            let priority = priorities.rank(element.semantic_label()) as usize;
+            if priority_groups.len() <= priority {
+                priority_groups.resize_with(priority + 1, Vec::new);
             }
+            priority_groups[priority].push(element.clone());
         }
 
-        // Process each priority group in order (CrossLayout → Title → Vision → Regular)
+        // Process each priority group in order (CrossLayout → Title → Vision → Regular
+        // by default)
+        // Elements placed by distance, re-evaluated in `Dependency` order
+        let mut reevaluated: Vec<T> = Vec::new();
         for mut group in priority_groups {
-            // Within each priority group, sort by reading order (y, then x)
-            group.sort_by(|a, b| {
-                let y_diff = (a.center().1 - b.center().1).abs();
-                if y_diff < self.config.same_row_tolerance {
-                    a.center()
-                        .0
-                        .partial_cmp(&b.center().0)
-                        .unwrap_or(std::cmp::Ordering::Equal)
-                } else {
-                    a.center()
-                        .1
-                        .partial_cmp(&b.center().1)
-                        .unwrap_or(std::cmp::Ordering::Equal)
+            // Within each priority group, sort by reading order (y, then x), or
+            // column by column
+            match self.config.insertion_order {
+                InsertionOrder::Rows => {
+                    let tolerance = self.row_tolerance(&group);
+                    sort_by_rows(&mut group, tolerance);
                 }
-            });
+                InsertionOrder::Dependency => sort_by_columns(&mut group),
+            }
 
             // Process each element in this priority group
             for masked in &group {
-                // Find the best insertion position using 4-component distance metric
-                let mut best_distance = f32::INFINITY;
-                let mut best_position: Option<usize> = None;
-
-                // Get masked element's semantic priority for constraint checking
-                let masked_priority = Self::label_priority(masked.semantic_label());
-
-                // Search through result to handle growing array correctly
-                for (idx, &elem_id) in result.iter().enumerate() {
-                    // Find the element - could be regular OR previously inserted masked
-                    let candidate = regular_elements
-                        .iter()
-                        .find(|e| e.id() == elem_id)
-                        .cloned()
-                        .or_else(|| {
-                            // Also check masked elements from ALL groups
-                            masked_elements.iter().find(|e| e.id() == elem_id).cloned()
+                // Display equations stay between the paragraphs around them
+                let anchored = (masked.semantic_label() == SemanticLabel::Formula)
+                    .then(|| {
+                        display_formula_position(
+                            masked,
+                            &result,
+                            &[regular_elements, masked_elements],
+                        )
+                    })
+                    .flatten();
+                if let Some(position) = anchored {
+                    if trace.observing() {
+                        trace.insertion(Insertion {
+                            id: masked.id(),
+                            position: Some(position),
+                            before: result.get(position).copied(),
+                            distance: 0.0,
                         });
+                    }
+                    trace.inserted += 1;
+                    result.insert(position, masked.id());
+                    continue;
+                }
+
+                let Anchor {
+                    position: best_position,
+                    distance: best_distance,
+                    runner_up,
+                    outranked,
+                } = self.nearest_anchor(
+                    masked,
+                    &result,
+                    &lookup,
+                    trace.track_ambiguity || trace.track_confidence,
+                );
 
-                    if let Some(candidate) = candidate {
-                        // Enforce L'o ⪰ l constraint (Equation 7)
-                        let candidate_priority = Self::label_priority(candidate.semantic_label());
-                        if candidate_priority < masked_priority {
-                            continue;
-                        }
-
-                        // Use 4-component distance metric
-                        let distance =
-                            compute_distance_with_early_exit(masked, &candidate, best_distance);
-                        if distance < best_distance {
-                            best_distance = distance;
-                            best_position = Some(idx);
-                        }
+                if let (Some(position), Some((second, second_id))) = (best_position, runner_up) {
+                    // Proximity is weighted by the masked element's larger side, so this
+                    // is a difference of less than `same_row_tolerance` in gap size
+                    let (x1, y1, x2, y2) = masked.bounds();
+                    let margin = (x2 - x1).max(y2 - y1) * self.config.same_row_tolerance;
+                    if trace.track_ambiguity && second - best_distance <= margin {
+                        let kind = AmbiguityKind::NearTiedInsertion {
+                            best: best_distance,
+                            runner_up: second,
+                        };
+                        let ids = vec![masked.id(), result[position], second_id];
+                        trace.ambiguities.push((kind, ids));
                     }
+                    if trace.track_confidence {
+                        // Rated in raw distance units: continuity and ordering terms
+                        // carry unit weight, so this is roughly a pixel offset
+                        let tolerance = self.config.same_row_tolerance;
+                        let lead = second - best_distance;
+                        let clarity = if tolerance > 0.0 {
+                            (lead / tolerance).clamp(0.0, 1.0)
+                        } else {
+                            f32::from(u8::from(lead > 0.0))
+                        };
+                        trace.insertion_margins.push((masked.id(), clarity));
+                    }
+                } else if best_position.is_none() && trace.track_confidence {
+                    trace.insertion_margins.push((masked.id(), 0.0));
                 }
 
-                if let Some(position) = best_position {
-                    eprintln!(
-                        "  [INSERT] Masked element {} ({:?}) -> position {} (before element {})",
-                        masked.id(),
-                        masked.semantic_label(),
+                let position = best_position.or_else(|| {
+                    let reason = if result.is_empty() {
+                        UnanchoredReason::NoCandidates
+                    } else if outranked {
+                        UnanchoredReason::Outranked
+                    } else {
+                        UnanchoredReason::NonFiniteDistance
+                    };
+                    trace.unanchored.push(Unanchored {
+                        id: masked.id(),
+                        reason,
+                    });
+                    (self.config.unanchored == UnanchoredPolicy::Geometric).then(|| {
+                        result
+                            .iter()
+                            .position(|id| {
+                                lookup.get(id).is_some_and(|&e| {
+                                    compare_position(e, masked) == Ordering::Greater
+                                })
+                            })
+                            .unwrap_or(result.len())
+                    })
+                });
+
+                if trace.observing() {
+                    trace.insertion(Insertion {
+                        id: masked.id(),
                         position,
-                        result[position]
-                    );
-                    result.insert(position, masked.id());
-                } else {
+                        before: position.and_then(|position| result.get(position).copied()),
+                        distance: best_distance,
+                    });
+                }
+
+                trace.inserted += 1;
+                match position {
+                    Some(position) => result.insert(position, masked.id()),
                     // No valid match found - append to end as a fallback
-                    eprintln!(
-                        "⚠️  No valid insertion for element {} ({:?}), appending",
-                        masked.id(),
-                        masked.semantic_label()
-                    );
-                    result.push(masked.id());
+                    None => result.push(masked.id()),
+                }
+                if best_position.is_some()
+                    && self.config.insertion_order == InsertionOrder::Dependency
+                {
+                    reevaluated.push(masked.clone());
                 }
             }
         }
+
+        // Each element may now anchor to masked elements inserted after it
+        for masked in &reevaluated {
+            let Some(current) = result.iter().position(|&id| id == masked.id()) else {
+                continue;
+            };
+            result.remove(current);
+            let anchor = self.nearest_anchor(masked, &result, &lookup, false);
+            let position = anchor.position.unwrap_or(current);
+            if position != current && trace.observing() {
+                trace.insertion(Insertion {
+                    id: masked.id(),
+                    position: Some(position),
+                    before: result.get(position).copied(),
+                    distance: anchor.distance,
+                });
+            }
+            result.insert(position, masked.id());
+        }
+        trace.profile.insertion += start.elapsed();
         result
     }
+}
 
-    /// Get priority value for semantic label (lower = higher priority)
-    fn label_priority(label: SemanticLabel) -> u8 {
-        match label {
-            SemanticLabel::CrossLayout => 0,
-            SemanticLabel::HorizontalTitle => 1,
-            SemanticLabel::VerticalTitle => 1,
-            SemanticLabel::Vision => 2,
-            SemanticLabel::Regular => 3,
+/// Insertion point chosen for a masked element
+struct Anchor {
+    /// Index in the order to insert at, `None` without a valid anchor
+    position: Option<usize>,
+    /// Distance to the element at `position` (Equation 8)
+    distance: f32,
+    /// Distance to and id of the second nearest valid anchor
+    runner_up: Option<(f32, usize)>,
+    /// Whether Equation 7 ruled out every candidate
+    outranked: bool,
+}
+
+impl ReadingOrder for XYCutPlusPlus {
+    fn order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Vec<usize> {
+        self.compute_order(elements, x_min, y_min, x_max, y_max)
+    }
+}
+
+/// Ids left out of ordering on `page`: noise, then background
+///
+/// Boxes with non-finite coordinates cannot be placed and count as noise whatever
+/// the [`NoiseFilter`]; noise is never background too.
+pub(crate) fn detect_dropped<T: BoundingBox>(
+    elements: &[T],
+    config: &XYCutConfig,
+    page: (f32, f32, f32, f32),
+) -> (Vec<usize>, Vec<usize>) {
+    let speckles: HashSet<usize> = detect_noise(elements, &config.noise).into_iter().collect();
+    let noise: Vec<usize> = elements
+        .iter()
+        .filter(|e| !has_finite_bounds(*e) || speckles.contains(&e.id()))
+        .map(|e| e.id())
+        .collect();
+    let noise_ids: HashSet<usize> = noise.iter().copied().collect();
+    let background = detect_background(elements, page, config.background_coverage)
+        .into_iter()
+        .filter(|id| !noise_ids.contains(id))
+        .collect();
+    (noise, background)
+}
+
+/// Whether all four coordinates of `element` are finite
+fn has_finite_bounds<T: BoundingBox>(element: &T) -> bool {
+    let (x1, y1, x2, y2) = element.bounds();
+    [x1, y1, x2, y2].iter().all(|v| v.is_finite())
+}
+
+/// `page` grown to hold every element with finite coordinates
+pub(crate) fn grown_to_fit<T: BoundingBox>(
+    elements: &[T],
+    page: (f32, f32, f32, f32),
+) -> (f32, f32, f32, f32) {
+    elements
+        .iter()
+        .filter(|e| has_finite_bounds(*e))
+        .map(BoundingBox::bounds)
+        .fold(page, |(x_min, y_min, x_max, y_max), (x1, y1, x2, y2)| {
+            (x_min.min(x1), y_min.min(y1), x_max.max(x2), y_max.max(y2))
+        })
+}
+
+/// Distance between the nearest edges of two boxes, 0 when they overlap
+fn box_gap<T: BoundingBox>(a: &T, b: &T) -> f32 {
+    let (ax1, ay1, ax2, ay2) = a.bounds();
+    let (bx1, by1, bx2, by2) = b.bounds();
+    let dx = (bx1 - ax2).max(ax1 - bx2).max(0.0);
+    let dy = (by1 - ay2).max(ay1 - by2).max(0.0);
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Ids of `elements`, in slice order
+fn ids<T: BoundingBox>(elements: &[T]) -> Vec<usize> {
+    elements.iter().map(|e| e.id()).collect()
+}
+
+/// Projection cut along `axis` at `position`
+fn projection(axis: Axis, position: f32, tolerant: bool) -> Cut {
+    Cut::Projection {
+        axis,
+        position,
+        tolerant,
+    }
+}
+
+/// A page ready to cut, from [`XYCutPlusPlus::prepare_page`]
+pub(crate) struct PreparedPage {
+    /// Kept elements in the upright frame `(0, 0, width, height)`
+    pub(crate) elements: Vec<Element>,
+    pub(crate) width: f32,
+    pub(crate) height: f32,
+
+    /// Speckles and boxes without finite coordinates, left out of the order
+    pub(crate) noise: Vec<usize>,
+
+    /// Page background boxes, left out of `elements`
+    pub(crate) background: Vec<usize>,
+}
+
+/// A region waiting to be cut: a range of the working elements with its bounds
+#[derive(Debug, Clone)]
+pub(crate) struct Region {
+    pub(crate) range: Range<usize>,
+    pub(crate) bounds: (f32, f32, f32, f32),
+    pub(crate) depth: usize,
+}
+
+/// The two sides of a cut region, partitioned to its front: `before` elements,
+/// then `after` elements
+struct Sides {
+    before: usize,
+    after: usize,
+    before_bounds: (f32, f32, f32, f32),
+    after_bounds: (f32, f32, f32, f32),
+}
+
+/// Group sizes of a split planned by `split_at`
+#[derive(Debug, Clone, Copy)]
+struct Split {
+    before: usize,
+    after: usize,
+}
+
+impl Split {
+    /// Move each element to its planned position, returning `(before, after, straddlers)`
+    fn apply<'e, T>(
+        self,
+        elements: &'e mut [T],
+        plan: &mut [usize],
+    ) -> (&'e mut [T], &'e mut [T], &'e mut [T]) {
+        // Follow the permutation's cycles, swapping each element into place
+        for i in 0..plan.len() {
+            while plan[i] != i {
+                let j = plan[i];
+                elements.swap(i, j);
+                plan.swap(i, j);
+            }
+        }
+        let (before, rest) = elements.split_at_mut(self.before);
+        let (after, straddlers) = rest.split_at_mut(self.after);
+        (before, after, straddlers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records every cut with the ids of the region it splits
+    #[derive(Default)]
+    struct Cuts(Vec<(Cut, Vec<usize>)>);
+
+    impl OrderingObserver for Cuts {
+        fn on_cut(&mut self, cut: &Cut, ids: &[usize]) {
+            self.0.push((*cut, ids.to_vec()));
         }
     }
+
+    #[test]
+    fn obstacle_bridging_two_columns_is_cut_around() {
+        let page = [
+            Element::new(0, 50.0, 50.0, 280.0, 400.0, SemanticLabel::Regular),
+            Element::new(1, 50.0, 420.0, 280.0, 950.0, SemanticLabel::Regular),
+            Element::new(2, 320.0, 50.0, 550.0, 600.0, SemanticLabel::Regular),
+            Element::new(3, 320.0, 620.0, 550.0, 950.0, SemanticLabel::Regular),
+            // Inset across the gutter, too narrow to be masked as cross-layout
+            Element::new(4, 200.0, 300.0, 400.0, 800.0, SemanticLabel::Regular),
+        ];
+        let config = XYCutConfig {
+            region_subtraction: true,
+            ..XYCutConfig::default()
+        };
+        let mut cuts = Cuts::default();
+        let order = XYCutPlusPlus::new(config)
+            .compute_order_observed(&page, 0.0, 0.0, 600.0, 1000.0, &mut cuts);
+
+        // Margin cuts come first, then the inset is set aside from the whole page
+        let obstacle = cuts
+            .0
+            .iter()
+            .position(|(cut, _)| matches!(cut, Cut::Obstacle(_)))
+            .expect("obstacle cut");
+        assert_eq!(cuts.0[obstacle].0, Cut::Obstacle(4));
+        assert_eq!(cuts.0[obstacle].1.len(), 5);
+        // The rest splits at the gutter, and the inset goes before the text below its top
+        assert!(matches!(
+            cuts.0[obstacle + 1],
+            (Cut::Projection { axis: Axis::X, .. }, ref ids) if ids.len() == 4
+        ));
+        assert_eq!(order, vec![0, 4, 1, 2, 3]);
+    }
 }
@@ -0,0 +1,221 @@
+//! Aggregate statistics over the [`OrderingReport`]s of a corpus, and drift between
+//! two runs (before and after a config or version change)
+
+use std::fmt;
+
+use crate::report::OrderingReport;
+
+/// Totals over the reports of one run
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CorpusStats {
+    pub pages: usize,
+
+    /// Elements ordered, over all pages
+    pub elements: usize,
+
+    /// Elements ordered by the fallback position sort
+    pub fallback_sorted: usize,
+
+    /// Masked elements placed by the insertion stage
+    pub inserted: usize,
+
+    /// Inserted elements without a valid anchor
+    pub unanchored: usize,
+
+    /// Inversions swapped by the smoothing pass
+    pub repairs: usize,
+
+    /// Sum of the per-page cut depths
+    pub total_depth: usize,
+
+    /// Deepest cut of any page
+    pub max_depth: usize,
+
+    /// Distinct fingerprints seen, in order of appearance; more than one means
+    /// the corpus mixes configs or versions
+    pub fingerprints: Vec<u64>,
+}
+
+impl CorpusStats {
+    /// Add one page's report
+    pub fn add(&mut self, report: &OrderingReport) {
+        self.pages += 1;
+        self.elements += report.order.len();
+        self.fallback_sorted += report.fallback_sorted;
+        self.inserted += report.inserted;
+        self.unanchored += report.unanchored.len();
+        self.repairs += report.repairs;
+        self.total_depth += report.depth;
+        self.max_depth = self.max_depth.max(report.depth);
+        if !self.fingerprints.contains(&report.fingerprint) {
+            self.fingerprints.push(report.fingerprint);
+        }
+    }
+
+    /// Share of elements ordered by the fallback sort instead of a cut
+    pub fn fallback_rate(&self) -> f32 {
+        ratio(self.fallback_sorted, self.elements)
+    }
+
+    /// Share of inserted elements without a valid anchor
+    pub fn unanchored_rate(&self) -> f32 {
+        ratio(self.unanchored, self.inserted)
+    }
+
+    /// Mean cut depth per page
+    pub fn average_depth(&self) -> f32 {
+        ratio(self.total_depth, self.pages)
+    }
+
+    /// Mean inversions repaired per page
+    pub fn repairs_per_page(&self) -> f32 {
+        ratio(self.repairs, self.pages)
+    }
+
+    /// Compare a candidate run against this baseline
+    pub fn compare(&self, candidate: &CorpusStats) -> DriftReport {
+        let metrics = [
+            ("fallback rate", Self::fallback_rate as fn(&Self) -> f32),
+            ("unanchored rate", Self::unanchored_rate),
+            ("average depth", Self::average_depth),
+            ("repairs per page", Self::repairs_per_page),
+        ];
+        DriftReport {
+            metrics: metrics
+                .iter()
+                .map(|&(name, metric)| Drift {
+                    name,
+                    baseline: metric(self),
+                    candidate: metric(candidate),
+                })
+                .collect(),
+            fingerprint_changed: self.fingerprints != candidate.fingerprints,
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a OrderingReport> for CorpusStats {
+    fn from_iter<I: IntoIterator<Item = &'a OrderingReport>>(reports: I) -> Self {
+        let mut stats = Self::default();
+        reports.into_iter().for_each(|report| stats.add(report));
+        stats
+    }
+}
+
+/// One statistic in a baseline and a candidate run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drift {
+    pub name: &'static str,
+    pub baseline: f32,
+    pub candidate: f32,
+}
+
+impl Drift {
+    pub fn delta(&self) -> f32 {
+        self.candidate - self.baseline
+    }
+}
+
+/// Statistics of two runs side by side
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriftReport {
+    pub metrics: Vec<Drift>,
+
+    /// Whether the runs were produced by different configs or algorithm versions
+    pub fingerprint_changed: bool,
+}
+
+impl DriftReport {
+    /// Statistics that grew by more than `tolerance` (absolute); each of them rises
+    /// when the algorithm has to fall back on its weaker heuristics
+    pub fn regressions(&self, tolerance: f32) -> Vec<&Drift> {
+        self.metrics
+            .iter()
+            .filter(|drift| drift.delta() > tolerance)
+            .collect()
+    }
+}
+
+impl fmt::Display for DriftReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<18}{:>10}{:>11}{:>10}",
+            "", "baseline", "candidate", "delta"
+        )?;
+        for drift in &self.metrics {
+            writeln!(
+                f,
+                "{:<18}{:>10.4}{:>11.4}{:>+10.4}",
+                drift.name,
+                drift.baseline,
+                drift.candidate,
+                drift.delta()
+            )?;
+        }
+        if self.fingerprint_changed {
+            writeln!(f, "(config or algorithm version differs)")?;
+        }
+        Ok(())
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> f32 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f32 / whole as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(
+        elements: usize,
+        fallback_sorted: usize,
+        depth: usize,
+        fingerprint: u64,
+    ) -> OrderingReport {
+        OrderingReport {
+            order: (0..elements).collect(),
+            fingerprint,
+            repairs: 0,
+            fallback_sorted,
+            inserted: 0,
+            unanchored: Vec::new(),
+            depth,
+            dropped: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn stats_total_the_reports() {
+        let stats: CorpusStats = [report(10, 2, 3, 1), report(30, 0, 5, 1)].iter().collect();
+        assert_eq!(stats.pages, 2);
+        assert_eq!(stats.elements, 40);
+        assert_eq!(stats.max_depth, 5);
+        assert_eq!(stats.fingerprints, vec![1]);
+        assert_eq!(stats.fallback_rate(), 0.05);
+        assert_eq!(stats.average_depth(), 4.0);
+        // No inserted elements: the rate is zero rather than NaN
+        assert_eq!(stats.unanchored_rate(), 0.0);
+    }
+
+    #[test]
+    fn drift_flags_grown_statistics_and_new_fingerprints() {
+        let baseline: CorpusStats = [report(10, 0, 2, 1)].iter().collect();
+        let candidate: CorpusStats = [report(10, 5, 2, 2)].iter().collect();
+        let drift = baseline.compare(&candidate);
+
+        assert!(drift.fingerprint_changed);
+        let regressions: Vec<&str> = drift.regressions(0.1).iter().map(|d| d.name).collect();
+        assert_eq!(regressions, vec!["fallback rate"]);
+        assert!(drift
+            .to_string()
+            .contains("(config or algorithm version differs)"));
+        assert!(!baseline.compare(&baseline).fingerprint_changed);
+    }
+}
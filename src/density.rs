@@ -0,0 +1,60 @@
+//! Layout density estimators deciding whether a region is cut vertically first
+
+use crate::core::XYCutPlusPlus;
+use crate::histogram::{count_gaps, fill_vertical_histogram};
+use crate::traits::{BoundingBox, SemanticLabel};
+
+/// How the density compared against `vertical_first_threshold` is estimated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DensityEstimator {
+    /// τd of Equation 4: summed aspect ratios of cross-layout elements over those
+    /// of single-layout elements
+    #[default]
+    AspectRatio,
+    /// Area of cross-layout elements over the area of single-layout elements; wide
+    /// one-line captions don't outweigh the text blocks
+    AreaCoverage,
+    /// Number of gutters (columns − 1) in the region's vertical projection, so any
+    /// region with columns running its full height is cut vertically first
+    ColumnCount,
+}
+
+impl XYCutPlusPlus {
+    /// Density of the region `bounds` under the configured estimator
+    pub(crate) fn density<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        (x_min, _, x_max, _): (f32, f32, f32, f32),
+        histogram: &mut Vec<usize>,
+    ) -> f32 {
+        match self.config.density {
+            DensityEstimator::AspectRatio => Self::compute_density_ratio(elements),
+            DensityEstimator::AreaCoverage => area_ratio(elements),
+            DensityEstimator::ColumnCount => {
+                let resolution = self.histogram_resolution(x_max - x_min);
+                fill_vertical_histogram(elements, x_min, x_max, resolution, histogram);
+                let min_gap_bins = (self.config.min_cut_threshold
+                    * self.config.histogram_resolution_scale)
+                    as usize;
+                count_gaps(histogram, min_gap_bins) as f32
+            }
+        }
+    }
+}
+
+/// Area of cross-layout elements over the area of the others, 1 without the latter
+fn area_ratio<T: BoundingBox>(elements: &[T]) -> f32 {
+    let (cross_layout, single_layout) = elements.iter().fold((0.0, 0.0), |(cross, single), e| {
+        let (x1, y1, x2, y2) = e.bounds();
+        let area = (x2 - x1) * (y2 - y1);
+        match e.semantic_label() {
+            SemanticLabel::CrossLayout => (cross + area, single),
+            _ => (cross, single + area),
+        }
+    });
+    if single_layout == 0.0 {
+        return 1.0;
+    }
+    cross_layout / single_layout
+}
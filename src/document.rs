@@ -0,0 +1,416 @@
+//! Two-pass ordering of a whole document
+//!
+//! The first pass learns the layout the pages share: column gutters, the content
+//! area, and running header and footer bands. The second orders each page with it,
+//! so books and reports are cut the same way from page to page.
+
+use std::collections::HashMap;
+
+use crate::batch::PageInput;
+use crate::columns::ColumnHints;
+use crate::core::XYCutPlusPlus;
+use crate::preprocess::content::estimate_content_area;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+/// Share of the pages a gutter or a header/footer box must recur on
+const RECURRING_PAGE_SHARE: f32 = 0.5;
+
+/// Fraction of the page height at the top and bottom searched for headers and footers
+const BAND_DEPTH: f32 = 0.12;
+
+/// Share of the pages a running element must recur on; below one half so that page
+/// numbers alternating between left and right pages still count
+const RUNNING_PAGE_SHARE: f32 = 0.4;
+
+/// Distance, as a fraction of the page height, within which boxes on different
+/// pages are at the same position
+const RUNNING_POSITION_TOLERANCE: f32 = 0.01;
+
+/// Layout shared by the pages of one document
+///
+/// Positions are offsets from each page's `(x_min, y_min)` corner, so pages with
+/// different origins share one model.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DocumentModel {
+    /// Column gutters cut on at least half of the pages
+    pub gutters: Vec<f32>,
+
+    /// Median body content area, judging centrality (Equation 3) on every page
+    pub content_area: Option<(f32, f32, f32, f32)>,
+
+    /// Bottom of the running header band; boxes ending above it are headers
+    pub header: Option<f32>,
+
+    /// Top of the running footer band; boxes starting below it are footers
+    pub footer: Option<f32>,
+}
+
+/// Document orders with the running elements taken out
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StrippedOrders {
+    /// Reading order of each page, without its running elements
+    pub orders: Vec<Vec<usize>>,
+
+    /// Ids removed from each page
+    pub removed: Vec<Vec<usize>>,
+}
+
+/// Part of the page an element belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PageZone {
+    /// Running header: titles, chapter names, page numbers above the body
+    Header,
+    Body,
+    /// Running footer: page numbers, footer lines below the body
+    Footer,
+}
+
+/// An element of an ordered page with its rank and zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ZonedElement {
+    pub id: usize,
+    pub rank: usize,
+    pub zone: PageZone,
+}
+
+impl DocumentModel {
+    /// Zone of a box `(x1, y1, x2, y2)` on a page with bounds `page`
+    ///
+    /// A box is a header or footer when it lies within the recurring band, or in the
+    /// top or bottom 12% of the page entirely outside the content area (a running
+    /// head that moves, a page number on its own).
+    pub fn zone(&self, bounds: (f32, f32, f32, f32), page: (f32, f32, f32, f32)) -> PageZone {
+        let (_, y_min, _, y_max) = page;
+        let (_, y1, _, y2) = bounds;
+        let (top, bottom) = (y1 - y_min, y2 - y_min);
+        let depth = BAND_DEPTH * (y_max - y_min);
+        let content = self.content_area.map(|(_, y1, _, y2)| (y1, y2));
+        if self.header.is_some_and(|band| bottom <= band)
+            || content.is_some_and(|(content_top, _)| bottom <= content_top.min(depth))
+        {
+            PageZone::Header
+        } else if self.footer.is_some_and(|band| top >= band)
+            || content
+                .is_some_and(|(_, content_bottom)| top >= content_bottom.max(y_max - y_min - depth))
+        {
+            PageZone::Footer
+        } else {
+            PageZone::Body
+        }
+    }
+
+    /// Split a page's elements into its header, body and footer
+    fn bands<T: BoundingBox>(&self, page: &PageInput<T>) -> (Vec<T>, Vec<T>, Vec<T>) {
+        let &(elements, bounds) = page;
+        let (mut header, mut body, mut footer) = (Vec::new(), Vec::new(), Vec::new());
+        for e in elements {
+            match self.zone(e.bounds(), bounds) {
+                PageZone::Header => header.push(e.clone()),
+                PageZone::Body => body.push(e.clone()),
+                PageZone::Footer => footer.push(e.clone()),
+            }
+        }
+        (header, body, footer)
+    }
+}
+
+impl XYCutPlusPlus {
+    /// First pass: learn the layout shared by `pages`
+    ///
+    /// Header and footer bands are boxes in the top or bottom 12% of the page that
+    /// recur at the same height on at least half of the pages. Gutters and the
+    /// content area come from ordering each page's body on its own.
+    pub fn learn_document_model<T: BoundingBox + Sync>(
+        &self,
+        pages: &[PageInput<T>],
+    ) -> DocumentModel {
+        let mut model = DocumentModel {
+            header: recurring_band(pages, true),
+            footer: recurring_band(pages, false),
+            ..DocumentModel::default()
+        };
+
+        let learn_page = |page: &PageInput<T>| {
+            let (x_min, y_min, x_max, y_max) = page.1;
+            let (_, body, _) = model.bands(page);
+            let mut trace = Trace {
+                record_columns: true,
+                ..Trace::default()
+            };
+            self.compute_order_traced(&body, x_min, y_min, x_max, y_max, &mut trace);
+            let content = estimate_content_area(&body, 0.0)
+                .map(|(x1, y1, x2, y2)| (x1 - x_min, y1 - y_min, x2 - x_min, y2 - y_min));
+            (trace.columns, content)
+        };
+        #[cfg(feature = "parallel")]
+        let learned: Vec<_> = {
+            use rayon::prelude::*;
+            pages.par_iter().map(learn_page).collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let learned: Vec<_> = pages.iter().map(learn_page).collect();
+
+        let cuts: Vec<(usize, f32)> = learned
+            .iter()
+            .enumerate()
+            .flat_map(|(page, (columns, _))| columns.iter().map(move |&x| (page, x)))
+            .collect();
+        model.gutters = recurring_gutters(cuts, pages.len(), self.config.min_cut_threshold);
+
+        let areas: Vec<_> = learned.iter().filter_map(|(_, area)| *area).collect();
+        if !areas.is_empty() {
+            model.content_area = Some((
+                median(areas.iter().map(|a| a.0).collect()),
+                median(areas.iter().map(|a| a.1).collect()),
+                median(areas.iter().map(|a| a.2).collect()),
+                median(areas.iter().map(|a| a.3).collect()),
+            ));
+        }
+        model
+    }
+
+    /// Second pass: order each page with a learned model
+    ///
+    /// Headers are read first and footers last (see [`DocumentModel::zone`]), each
+    /// band ordered on its own; the body is cut at the model's gutters where it has
+    /// none of its own (as [`ColumnHints::Prior`]) and judges centrality against the
+    /// model's content area.
+    pub fn compute_orders_with_model<T: BoundingBox + Sync>(
+        &self,
+        pages: &[PageInput<T>],
+        model: &DocumentModel,
+    ) -> Vec<Vec<usize>> {
+        let order_page = |page: &PageInput<T>| {
+            let (x_min, y_min, x_max, y_max) = page.1;
+            let (header, body, footer) = model.bands(page);
+            let mut trace = Trace {
+                column_hints: model.gutters.clone(),
+                hint_mode: ColumnHints::Prior,
+                content_area: model.content_area,
+                ..Trace::default()
+            };
+            let body = self.compute_order_traced(&body, x_min, y_min, x_max, y_max, &mut trace);
+            let header = self.compute_order(&header, x_min, y_min, x_max, y_max);
+            let footer = self.compute_order(&footer, x_min, y_min, x_max, y_max);
+            header.into_iter().chain(body).chain(footer).collect()
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            pages.par_iter().map(order_page).collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            pages.iter().map(order_page).collect()
+        }
+    }
+
+    /// Learn the document's layout, order each page with it, and tag every element
+    /// with its zone, so consumers can drop headers and footers
+    pub fn compute_document_zones<T: BoundingBox + Sync>(
+        &self,
+        pages: &[PageInput<T>],
+    ) -> Vec<Vec<ZonedElement>> {
+        let model = self.learn_document_model(pages);
+        pages
+            .iter()
+            .zip(self.compute_orders_with_model(pages, &model))
+            .map(|(&(elements, bounds), order)| {
+                let boxes: HashMap<usize, _> =
+                    elements.iter().map(|e| (e.id(), e.bounds())).collect();
+                order
+                    .into_iter()
+                    .enumerate()
+                    .map(|(rank, id)| ZonedElement {
+                        id,
+                        rank,
+                        zone: boxes
+                            .get(&id)
+                            .map_or(PageZone::Body, |&b| model.zone(b, bounds)),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Learn the document's layout from all pages, then order each page with it
+    pub fn compute_document_orders<T: BoundingBox + Sync>(
+        &self,
+        pages: &[PageInput<T>],
+    ) -> Vec<Vec<usize>> {
+        let model = self.learn_document_model(pages);
+        self.compute_orders_with_model(pages, &model)
+    }
+
+    /// [`compute_document_orders`](Self::compute_document_orders) with running
+    /// titles and page numbers (see [`detect_running_elements`]) stripped from every
+    /// page's order
+    pub fn compute_document_orders_stripped<T: BoundingBox + Sync>(
+        &self,
+        pages: &[PageInput<T>],
+    ) -> StrippedOrders {
+        let removed = detect_running_elements(pages);
+        let orders = self
+            .compute_document_orders(pages)
+            .into_iter()
+            .zip(&removed)
+            .map(|(order, removed)| {
+                order
+                    .into_iter()
+                    .filter(|id| !removed.contains(id))
+                    .collect()
+            })
+            .collect();
+        StrippedOrders { orders, removed }
+    }
+}
+
+/// Ids, per page, of the elements recurring at the same position on many pages:
+/// running titles, page numbers, margin tabs
+///
+/// Only boxes lying entirely in the outer 12% of the page (top, bottom, left or
+/// right) are considered, so the body of identically laid out pages (a batch of forms) is
+/// never stripped. Boxes match across pages when their centers and heights are
+/// within 1% of the page height; their text and width may differ, as page numbers
+/// do. A box recurring on at least 40% of the pages is a running element.
+pub fn detect_running_elements<T: BoundingBox>(pages: &[PageInput<T>]) -> Vec<Vec<usize>> {
+    // (page, id, center x, center y, height) of the margin boxes, as page offsets,
+    // bucketed by position so each box is only compared with its neighbours
+    let mut boxes: Vec<(usize, usize, f32, f32, f32)> = Vec::new();
+    let mut tolerance = f32::INFINITY;
+    for (page, &(elements, (x_min, y_min, x_max, y_max))) in pages.iter().enumerate() {
+        let (width, height) = (x_max - x_min, y_max - y_min);
+        tolerance = tolerance.min(RUNNING_POSITION_TOLERANCE * height);
+        for e in elements {
+            let (x1, y1, x2, y2) = e.bounds();
+            let (x1, y1, x2, y2) = (x1 - x_min, y1 - y_min, x2 - x_min, y2 - y_min);
+            let margin = y2 <= BAND_DEPTH * height
+                || y1 >= (1.0 - BAND_DEPTH) * height
+                || x2 <= BAND_DEPTH * width
+                || x1 >= (1.0 - BAND_DEPTH) * width;
+            if margin {
+                let (cx, cy) = e.center();
+                boxes.push((page, e.id(), cx - x_min, cy - y_min, y2 - y1));
+            }
+        }
+    }
+
+    let mut running = vec![Vec::new(); pages.len()];
+    if !(tolerance > 0.0 && tolerance.is_finite()) {
+        return running;
+    }
+    let cell = |x: f32| (x / tolerance).floor() as i64;
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (index, &(_, _, cx, cy, _)) in boxes.iter().enumerate() {
+        grid.entry((cell(cx), cell(cy))).or_default().push(index);
+    }
+    for &(page, id, cx, cy, height) in &boxes {
+        let matches = (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dy| (cell(cx) + dx, cell(cy) + dy)))
+            .filter_map(|key| grid.get(&key))
+            .flatten()
+            .map(|&other| boxes[other])
+            .filter(|&(_, _, ox, oy, oh)| {
+                (ox - cx).abs() <= tolerance
+                    && (oy - cy).abs() <= tolerance
+                    && (oh - height).abs() <= tolerance
+            })
+            .map(|(other_page, ..)| other_page);
+        if recurs(matches, pages.len(), RUNNING_PAGE_SHARE) {
+            running[page].push(id);
+        }
+    }
+    running
+}
+
+/// Bottom of the recurring header band (`top`) or top of the footer band, as an
+/// offset from the page top
+fn recurring_band<T: BoundingBox>(pages: &[PageInput<T>], top: bool) -> Option<f32> {
+    // (page, y1, y2) of the boxes lying within the band, as page offsets
+    let mut boxes: Vec<(usize, f32, f32)> = Vec::new();
+    for (page, &(elements, (_, y_min, _, y_max))) in pages.iter().enumerate() {
+        let depth = BAND_DEPTH * (y_max - y_min);
+        for e in elements {
+            let (_, y1, _, y2) = e.bounds();
+            let (y1, y2) = (y1 - y_min, y2 - y_min);
+            let inside = if top {
+                y2 <= depth
+            } else {
+                y1 >= y_max - y_min - depth
+            };
+            if inside {
+                boxes.push((page, y1, y2));
+            }
+        }
+    }
+    boxes.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    // Group boxes at the same height across pages; a group found on enough pages
+    // is a running header or footer
+    let mut band: Option<(f32, f32)> = None;
+    let mut start = 0;
+    while start < boxes.len() {
+        let (mut group_top, mut group_bottom) = (boxes[start].1, boxes[start].2);
+        let mut end = start + 1;
+        while end < boxes.len() {
+            let (_, y1, y2) = boxes[end];
+            let overlap = group_bottom.min(y2) - group_top.max(y1);
+            if overlap < 0.0 || overlap < 0.5 * (y2 - y1).min(group_bottom - group_top) {
+                break;
+            }
+            group_top = group_top.min(y1);
+            group_bottom = group_bottom.max(y2);
+            end += 1;
+        }
+        if recurs(
+            boxes[start..end].iter().map(|b| b.0),
+            pages.len(),
+            RECURRING_PAGE_SHARE,
+        ) {
+            band = Some(band.map_or((group_top, group_bottom), |(t, b)| {
+                (t.min(group_top), b.max(group_bottom))
+            }));
+        }
+        start = end;
+    }
+    band.map(|(band_top, band_bottom)| if top { band_bottom } else { band_top })
+}
+
+/// Gutters cut within `tolerance` of each other on enough pages, at their median
+fn recurring_gutters(mut cuts: Vec<(usize, f32)>, pages: usize, tolerance: f32) -> Vec<f32> {
+    cuts.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let mut gutters = Vec::new();
+    let mut start = 0;
+    while start < cuts.len() {
+        let mut end = start + 1;
+        while end < cuts.len() && cuts[end].1 - cuts[end - 1].1 <= tolerance {
+            end += 1;
+        }
+        let group = &cuts[start..end];
+        if recurs(group.iter().map(|c| c.0), pages, RECURRING_PAGE_SHARE) {
+            gutters.push(median(group.iter().map(|c| c.1).collect()));
+        }
+        start = end;
+    }
+    gutters
+}
+
+/// Whether the distinct pages in `found_on` make up at least `share` of `pages`
+fn recurs(found_on: impl Iterator<Item = usize>, pages: usize, share: f32) -> bool {
+    let mut found_on: Vec<usize> = found_on.collect();
+    found_on.sort_unstable();
+    found_on.dedup();
+    pages >= 2 && found_on.len() as f32 >= share * pages as f32
+}
+
+fn median(mut values: Vec<f32>) -> f32 {
+    values.sort_by(f32::total_cmp);
+    values[values.len() / 2]
+}
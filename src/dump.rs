@@ -0,0 +1,115 @@
+//! JSON dump of the intermediate pipeline state, for reproducible bug reports
+
+use serde_json::{json, Value};
+
+use crate::core::XYCutPlusPlus;
+use crate::element::Element;
+use crate::observer::{Axis, Cut};
+use crate::report::ALGORITHM_VERSION;
+use crate::trace::{Decision, RegionState, Trace};
+use crate::traits::BoundingBox;
+
+impl XYCutPlusPlus {
+    /// Order the page and serialize every intermediate decision as one JSON document
+    ///
+    /// ```text
+    /// {
+    ///   "version": <ALGORITHM_VERSION>, "fingerprint": <u64>, "config": <XYCutConfig>,
+    ///   "page": [x_min, y_min, x_max, y_max],
+    ///   "elements": [<Element>, ...],
+    ///   "partition": {"regular": [id, ...], "masked": [id, ...]},
+    ///   "regions": [{"bounds": [..], "ids": [..], "boxes": [[x1, y1, x2, y2], ..],
+    ///                "labels": [<SemanticLabel>, ..], "histograms": {"x": [..], "y": [..]},
+    ///                "density": f | null,
+    ///                "decision": {"kind": "cut", "axis": "x" | "y", "position": f, "tolerant": b}
+    ///                          | {"kind": "obstacle", "id": id} | {"kind": "fallback"} | null}],
+    ///   "insertions": [{"id": id, "position": i | null, "before": id | null, "distance": f}],
+    ///   "order": [id, ...]
+    /// }
+    /// ```
+    ///
+    /// `config` and `elements` are the serde forms of the config and of the input
+    /// boxes, so deserializing them and the page reproduces the run. JSON has no NaN or
+    /// infinities; they are written as `null`.
+    ///
+    /// Regions are listed in visiting order. Their coordinates are in the frame the
+    /// page was cut in, which differs from the input frame for rotated or deskewed pages.
+    pub fn dump_state<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> String {
+        let mut trace = Trace {
+            record_state: true,
+            ..Trace::default()
+        };
+        let order = self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace);
+
+        let elements: Vec<Element> = elements.iter().map(Element::from_box).collect();
+        let regions: Vec<Value> = trace.regions.iter().map(region_json).collect();
+        let insertions: Vec<Value> = trace
+            .insertions
+            .iter()
+            .map(|insertion| {
+                json!({
+                    "id": insertion.id,
+                    "position": insertion.position,
+                    "before": insertion.before,
+                    "distance": insertion.distance,
+                })
+            })
+            .collect();
+
+        json!({
+            "version": ALGORITHM_VERSION,
+            "fingerprint": self.fingerprint(),
+            "config": self.config,
+            "page": [x_min, y_min, x_max, y_max],
+            "elements": elements,
+            "partition": {"regular": trace.partition.0, "masked": trace.partition.1},
+            "regions": regions,
+            "insertions": insertions,
+            "order": order,
+        })
+        .to_string()
+    }
+}
+
+fn region_json(region: &RegionState) -> Value {
+    let decision = match region.decision {
+        Some(Decision::Cut(Cut::Projection {
+            axis,
+            position,
+            tolerant,
+        })) => json!({
+            "kind": "cut",
+            "axis": match axis {
+                Axis::X => "x",
+                Axis::Y => "y",
+            },
+            "position": position,
+            "tolerant": tolerant,
+        }),
+        Some(Decision::Cut(Cut::Obstacle(id))) => json!({"kind": "obstacle", "id": id}),
+        Some(Decision::Fallback) => json!({"kind": "fallback"}),
+        None => Value::Null,
+    };
+    let boxes: Vec<[f32; 4]> = region
+        .boxes
+        .iter()
+        .map(|&(x1, y1, x2, y2)| [x1, y1, x2, y2])
+        .collect();
+    let (x1, y1, x2, y2) = region.bounds;
+    json!({
+        "bounds": [x1, y1, x2, y2],
+        "ids": region.ids,
+        "boxes": boxes,
+        "labels": region.labels,
+        "histograms": {"x": region.histograms.0, "y": region.histograms.1},
+        "density": region.density,
+        "decision": decision,
+    })
+}
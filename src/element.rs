@@ -0,0 +1,235 @@
+use crate::traits::{BoundingBox, SemanticLabel};
+
+/// Plain owned layout element implementing [`BoundingBox`]
+///
+/// Useful when no domain type exists yet, and used internally wherever the
+/// algorithm needs to reshape boxes (merged units, transformed coordinates).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Element {
+    pub id: usize,
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+    pub label: SemanticLabel,
+
+    /// Whether the element is masked during cutting (titles, figures, tables)
+    pub mask: bool,
+
+    /// Detector confidence, if known
+    pub confidence: Option<f32>,
+
+    /// Stacking position, higher drawn on top, if known
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub z_index: Option<i32>,
+
+    /// Shape group the element belongs to, if any
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub group: Option<usize>,
+
+    /// Language of the element's text, as an index, if known
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub language: Option<usize>,
+}
+
+impl Element {
+    /// Create an element; titles, vision elements and formulas are masked by default
+    pub fn new(id: usize, x1: f32, y1: f32, x2: f32, y2: f32, label: SemanticLabel) -> Self {
+        Self {
+            id,
+            x1,
+            y1,
+            x2,
+            y2,
+            label,
+            mask: label.is_title()
+                || matches!(label, SemanticLabel::Vision | SemanticLabel::Formula),
+            confidence: None,
+            z_index: None,
+            group: None,
+            language: None,
+        }
+    }
+
+    /// Snapshot any [`BoundingBox`] into an owned element
+    pub fn from_box<T: BoundingBox>(element: &T) -> Self {
+        let (x1, y1, x2, y2) = element.bounds();
+        Self {
+            id: element.id(),
+            x1,
+            y1,
+            x2,
+            y2,
+            label: element.semantic_label(),
+            mask: element.should_mask(),
+            confidence: element.confidence(),
+            z_index: element.z_index(),
+            group: element.group(),
+            language: element.language(),
+        }
+    }
+
+    pub fn width(&self) -> f32 {
+        self.x2 - self.x1
+    }
+
+    pub fn height(&self) -> f32 {
+        self.y2 - self.y1
+    }
+
+    /// Grow this element's box to also cover `other`
+    pub fn extend(&mut self, other: &impl BoundingBox) {
+        let (x1, y1, x2, y2) = other.bounds();
+        self.x1 = self.x1.min(x1);
+        self.y1 = self.y1.min(y1);
+        self.x2 = self.x2.max(x2);
+        self.y2 = self.y2.max(y2);
+    }
+}
+
+impl BoundingBox for Element {
+    fn id(&self) -> usize {
+        self.id
+    }
+
+    fn center(&self) -> (f32, f32) {
+        ((self.x1 + self.x2) / 2.0, (self.y1 + self.y2) / 2.0)
+    }
+
+    fn bounds(&self) -> (f32, f32, f32, f32) {
+        (self.x1, self.y1, self.x2, self.y2)
+    }
+
+    fn iou(&self, other: &Self) -> f32 {
+        let x_overlap = (self.x2.min(other.x2) - self.x1.max(other.x1)).max(0.0);
+        let y_overlap = (self.y2.min(other.y2) - self.y1.max(other.y1)).max(0.0);
+        let intersection = x_overlap * y_overlap;
+        let union = self.width() * self.height() + other.width() * other.height() - intersection;
+        if union > 0.0 {
+            intersection / union
+        } else {
+            0.0
+        }
+    }
+
+    fn should_mask(&self) -> bool {
+        self.mask
+    }
+
+    fn semantic_label(&self) -> SemanticLabel {
+        self.label
+    }
+
+    fn confidence(&self) -> Option<f32> {
+        self.confidence
+    }
+
+    fn z_index(&self) -> Option<i32> {
+        self.z_index
+    }
+
+    fn group(&self) -> Option<usize> {
+        self.group
+    }
+
+    fn language(&self) -> Option<usize> {
+        self.language
+    }
+}
+
+/// `geo::Rect` of the element's box
+#[cfg(feature = "geo")]
+impl From<&Element> for geo_types::Rect<f32> {
+    fn from(element: &Element) -> Self {
+        Self::new((element.x1, element.y1), (element.x2, element.y2))
+    }
+}
+
+#[cfg(feature = "geo")]
+impl From<Element> for geo_types::Rect<f32> {
+    fn from(element: Element) -> Self {
+        Self::from(&element)
+    }
+}
+
+/// Element with id, box and label, e.g. `Element::from((id, rect, label))`
+#[cfg(feature = "geo")]
+impl From<(usize, geo_types::Rect<f32>, SemanticLabel)> for Element {
+    fn from((id, rect, label): (usize, geo_types::Rect<f32>, SemanticLabel)) -> Self {
+        let (min, max) = (rect.min(), rect.max());
+        Self::new(id, min.x, min.y, max.x, max.y, label)
+    }
+}
+
+/// `euclid::Box2D` of the element's box, in any unit
+#[cfg(feature = "euclid")]
+impl<U> From<&Element> for euclid::Box2D<f32, U> {
+    fn from(element: &Element) -> Self {
+        Self::new(
+            euclid::point2(element.x1, element.y1),
+            euclid::point2(element.x2, element.y2),
+        )
+    }
+}
+
+#[cfg(feature = "euclid")]
+impl<U> From<Element> for euclid::Box2D<f32, U> {
+    fn from(element: Element) -> Self {
+        Self::from(&element)
+    }
+}
+
+/// Element with id, box and label, e.g. `Element::from((id, box2d, label))`
+#[cfg(feature = "euclid")]
+impl<U> From<(usize, euclid::Box2D<f32, U>, SemanticLabel)> for Element {
+    fn from((id, rect, label): (usize, euclid::Box2D<f32, U>, SemanticLabel)) -> Self {
+        Self::new(id, rect.min.x, rect.min.y, rect.max.x, rect.max.y, label)
+    }
+}
+
+/// `kurbo::Rect` of the element's box
+#[cfg(feature = "kurbo")]
+impl From<&Element> for kurbo::Rect {
+    fn from(element: &Element) -> Self {
+        Self::new(
+            element.x1.into(),
+            element.y1.into(),
+            element.x2.into(),
+            element.y2.into(),
+        )
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl From<Element> for kurbo::Rect {
+    fn from(element: Element) -> Self {
+        Self::from(&element)
+    }
+}
+
+/// Element with id, box and label; kurbo's `f64` coordinates are narrowed to `f32`
+/// and the rectangle is normalized so `x1 <= x2` and `y1 <= y2`
+#[cfg(feature = "kurbo")]
+impl From<(usize, kurbo::Rect, SemanticLabel)> for Element {
+    fn from((id, rect, label): (usize, kurbo::Rect, SemanticLabel)) -> Self {
+        let rect = rect.abs();
+        Self::new(
+            id,
+            rect.x0 as f32,
+            rect.y0 as f32,
+            rect.x1 as f32,
+            rect.y1 as f32,
+            label,
+        )
+    }
+}
@@ -0,0 +1,219 @@
+//! Readers for public reading-order benchmark annotations
+//!
+//! Paths are supplied by the caller; no data is bundled or downloaded.
+
+use std::fmt;
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::AnnotatedPage;
+use crate::element::Element;
+use crate::labels::LabelMap;
+use crate::traits::SemanticLabel;
+
+/// Failure reading a dataset file
+#[derive(Debug)]
+pub enum DatasetError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// The JSON parsed but does not match the expected format
+    Format(String),
+}
+
+impl fmt::Display for DatasetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "cannot read dataset: {error}"),
+            Self::Json(error) => write!(f, "invalid dataset JSON: {error}"),
+            Self::Format(message) => write!(f, "unexpected dataset format: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for DatasetError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Json(error) => Some(error),
+            Self::Format(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for DatasetError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for DatasetError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+fn read_json(path: &Path) -> Result<Value, DatasetError> {
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn format_error(message: impl Into<String>) -> DatasetError {
+    DatasetError::Format(message.into())
+}
+
+fn number(value: &Value, what: &str) -> Result<f32, DatasetError> {
+    value
+        .as_f64()
+        .map(|v| v as f32)
+        .ok_or_else(|| format_error(format!("{what} is not a number")))
+}
+
+/// Load an OmniDocBench annotation file (`OmniDocBench.json`)
+///
+/// Every page becomes an [`AnnotatedPage`]: boxes are the bounding rectangles of the
+/// `layout_dets` polygons (ignored ones skipped), ids are their `anno_id`, labels come
+/// from `labels` (see [`LabelMap::omnidocbench`]) and the ground truth lists the boxes
+/// with a non-null `order` by ascending order.
+pub fn load_omnidocbench(
+    path: impl AsRef<Path>,
+    labels: &LabelMap,
+) -> Result<Vec<AnnotatedPage>, DatasetError> {
+    let root = read_json(path.as_ref())?;
+    let pages = root
+        .as_array()
+        .ok_or_else(|| format_error("top level is not an array of pages"))?;
+
+    pages
+        .iter()
+        .map(|page| {
+            let info = &page["page_info"];
+            let name = info["image_path"].as_str().unwrap_or_default().to_string();
+            let width = number(&info["width"], "page_info.width")?;
+            let height = number(&info["height"], "page_info.height")?;
+
+            let dets = page["layout_dets"]
+                .as_array()
+                .ok_or_else(|| format_error(format!("{name}: layout_dets is not an array")))?;
+            let mut elements = Vec::new();
+            let mut ranked = Vec::new();
+            for det in dets {
+                if det["ignore"].as_bool().unwrap_or(false) {
+                    continue;
+                }
+                let id = det["anno_id"]
+                    .as_u64()
+                    .ok_or_else(|| format_error(format!("{name}: anno_id is not an id")))?
+                    as usize;
+                let poly = det["poly"]
+                    .as_array()
+                    .filter(|poly| poly.len() >= 4 && poly.len() % 2 == 0)
+                    .ok_or_else(|| format_error(format!("{name}: box {id} has no polygon")))?;
+                let coords = poly
+                    .iter()
+                    .map(|v| number(v, "poly coordinate"))
+                    .collect::<Result<Vec<f32>, _>>()?;
+                let xs = coords.iter().step_by(2);
+                let ys = coords.iter().skip(1).step_by(2);
+                let label = labels.map_name(det["category_type"].as_str().unwrap_or_default());
+                elements.push(Element::new(
+                    id,
+                    xs.clone().copied().fold(f32::INFINITY, f32::min),
+                    ys.clone().copied().fold(f32::INFINITY, f32::min),
+                    xs.copied().fold(f32::NEG_INFINITY, f32::max),
+                    ys.copied().fold(f32::NEG_INFINITY, f32::max),
+                    label,
+                ));
+                if let Some(rank) = det["order"].as_f64() {
+                    ranked.push((rank, id));
+                }
+            }
+            ranked.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+            Ok(AnnotatedPage {
+                name,
+                page: (0.0, 0.0, width, height),
+                elements,
+                order: ranked.into_iter().map(|(_, id)| id).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Load a page in the tool-neutral annotation schema written by
+/// [`to_annotation_json`](crate::export::to_annotation_json)
+///
+/// This is also the format for DocBench-style sets converted by hand: boxes with
+/// `SemanticLabel` names and a ground-truth `order`. The page bounds are the union
+/// of the boxes unless a `"page": [x1, y1, x2, y2]` entry is present.
+pub fn load_annotation_json(path: impl AsRef<Path>) -> Result<AnnotatedPage, DatasetError> {
+    let path = path.as_ref();
+    let root = read_json(path)?;
+    let name = path.display().to_string();
+
+    let entries = root["elements"]
+        .as_array()
+        .ok_or_else(|| format_error(format!("{name}: elements is not an array")))?;
+    let elements = entries
+        .iter()
+        .map(|entry| {
+            let id = entry["id"]
+                .as_u64()
+                .ok_or_else(|| format_error(format!("{name}: element id is not an id")))?
+                as usize;
+            let bbox = entry["bbox"]
+                .as_array()
+                .filter(|bbox| bbox.len() == 4)
+                .ok_or_else(|| format_error(format!("{name}: element {id} has no bbox")))?;
+            let b = bbox
+                .iter()
+                .map(|v| number(v, "bbox coordinate"))
+                .collect::<Result<Vec<f32>, _>>()?;
+            let label = match entry["label"].as_str().unwrap_or("Regular") {
+                "CrossLayout" => SemanticLabel::CrossLayout,
+                "HorizontalTitle" => SemanticLabel::HorizontalTitle,
+                "VerticalTitle" => SemanticLabel::VerticalTitle,
+                "Vision" => SemanticLabel::Vision,
+                "Formula" => SemanticLabel::Formula,
+                "Regular" => SemanticLabel::Regular,
+                other => return Err(format_error(format!("{name}: unknown label {other}"))),
+            };
+            Ok(Element::new(id, b[0], b[1], b[2], b[3], label))
+        })
+        .collect::<Result<Vec<Element>, DatasetError>>()?;
+
+    let order = root["order"]
+        .as_array()
+        .ok_or_else(|| format_error(format!("{name}: order is not an array")))?
+        .iter()
+        .map(|id| {
+            id.as_u64()
+                .map(|id| id as usize)
+                .ok_or_else(|| format_error(format!("{name}: order entry is not an id")))
+        })
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    let page = match root["page"].as_array() {
+        Some(page) if page.len() == 4 => (
+            number(&page[0], "page x1")?,
+            number(&page[1], "page y1")?,
+            number(&page[2], "page x2")?,
+            number(&page[3], "page y2")?,
+        ),
+        _ => elements.iter().fold(
+            (
+                f32::INFINITY,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                f32::NEG_INFINITY,
+            ),
+            |(x1, y1, x2, y2), e| (x1.min(e.x1), y1.min(e.y1), x2.max(e.x2), y2.max(e.y2)),
+        ),
+    };
+
+    Ok(AnnotatedPage {
+        name,
+        page,
+        elements,
+        order,
+    })
+}
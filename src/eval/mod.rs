@@ -0,0 +1,106 @@
+//! Metrics comparing a predicted reading order with ground truth
+
+#[cfg(feature = "io-json")]
+pub mod datasets;
+
+use std::collections::HashMap;
+
+use crate::element::Element;
+
+/// A page with its ground-truth reading order, read from a benchmark (see `datasets`)
+/// or built by hand
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnotatedPage {
+    /// Page identifier from the dataset (image path or file name)
+    pub name: String,
+
+    /// Page bounds `(x_min, y_min, x_max, y_max)`
+    pub page: (f32, f32, f32, f32),
+
+    /// Every layout box of the page, including unordered ones (headers, margins)
+    pub elements: Vec<Element>,
+
+    /// Ground-truth reading order over the ordered boxes
+    pub order: Vec<usize>,
+}
+
+/// Kendall's τ rank correlation between a predicted order and the ground truth
+///
+/// Only ids present in both orders are compared. Returns 1.0 for identical
+/// orders, -1.0 for reversed ones, and 1.0 when fewer than two ids are shared.
+pub fn kendall_tau(predicted: &[usize], truth: &[usize]) -> f32 {
+    let rank: HashMap<usize, usize> = truth.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+    let ranks: Vec<usize> = predicted
+        .iter()
+        .filter_map(|id| rank.get(id).copied())
+        .collect();
+    let n = ranks.len();
+    if n < 2 {
+        return 1.0;
+    }
+
+    let mut concordant = 0i64;
+    let mut discordant = 0i64;
+    for i in 0..n {
+        for j in i + 1..n {
+            if ranks[i] < ranks[j] {
+                concordant += 1;
+            } else {
+                discordant += 1;
+            }
+        }
+    }
+    (concordant - discordant) as f32 / (concordant + discordant) as f32
+}
+
+/// Average relative distance (ARD): mean displacement of each ground-truth id in the
+/// predicted order, normalized by the length of the truth; ids missing from the
+/// prediction count as displaced by the full length
+pub fn average_relative_distance(predicted: &[usize], truth: &[usize]) -> f32 {
+    if truth.is_empty() {
+        return 0.0;
+    }
+    let position: HashMap<usize, usize> = predicted
+        .iter()
+        .enumerate()
+        .map(|(i, &id)| (id, i))
+        .collect();
+    let n = truth.len() as f32;
+
+    let total: f32 = truth
+        .iter()
+        .enumerate()
+        .map(|(i, id)| match position.get(id) {
+            Some(&j) => (i as f32 - j as f32).abs() / n,
+            None => 1.0,
+        })
+        .sum();
+    total / n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kendall_tau_of_known_pairs() {
+        let truth = [0, 1, 2, 3];
+        assert_eq!(kendall_tau(&truth, &truth), 1.0);
+        assert_eq!(kendall_tau(&[3, 2, 1, 0], &truth), -1.0);
+        // One swapped pair out of six
+        assert!((kendall_tau(&[1, 0, 2, 3], &truth) - 4.0 / 6.0).abs() < 1e-6);
+        // Ids outside the truth are ignored
+        assert_eq!(kendall_tau(&[0, 9, 1, 2, 3], &truth), 1.0);
+    }
+
+    #[test]
+    fn average_relative_distance_of_known_pairs() {
+        let truth = [0, 1, 2, 3];
+        assert_eq!(average_relative_distance(&truth, &truth), 0.0);
+        // Two ids displaced by one position each: (1/4 + 1/4) / 4
+        assert_eq!(average_relative_distance(&[1, 0, 2, 3], &truth), 0.125);
+        // A missing id counts as displaced by the full length
+        assert_eq!(average_relative_distance(&[0, 1, 2], &truth), 0.25);
+    }
+}
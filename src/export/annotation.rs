@@ -0,0 +1,139 @@
+use super::{json_number, json_string};
+use crate::report::ALGORITHM_VERSION;
+use crate::traits::BoundingBox;
+
+/// Render the ordered page as a Label Studio task with predictions
+///
+/// Every element becomes a `rectanglelabels` region (id `"e<element id>"`, label
+/// from its semantic label, coordinates in percent of `page`), and consecutive
+/// elements of `order` are linked by `next` relations. The labeling config must
+/// name the rectangle control `label` and the image `image`:
+///
+/// ```text
+/// <View>
+///   <Image name="image" value="$image"/>
+///   <RectangleLabels name="label" toName="image">
+///     <Label value="CrossLayout"/><Label value="HorizontalTitle"/><Label value="VerticalTitle"/>
+///     <Label value="Vision"/><Label value="Formula"/><Label value="Regular"/>
+///   </RectangleLabels>
+///   <Relations><Relation value="next"/></Relations>
+/// </View>
+/// ```
+pub fn to_label_studio<T: BoundingBox>(
+    elements: &[T],
+    order: &[usize],
+    page: (f32, f32, f32, f32),
+    image: &str,
+) -> String {
+    let (x_min, y_min, x_max, y_max) = page;
+    let (width, height) = (x_max - x_min, y_max - y_min);
+
+    let regions = elements.iter().map(|e| {
+        let (x1, y1, x2, y2) = e.bounds();
+        format!(
+            "{{\"id\":\"e{}\",\"type\":\"rectanglelabels\",\"from_name\":\"label\",\"to_name\":\"image\",\
+             \"original_width\":{},\"original_height\":{},\"value\":{{\"x\":{},\"y\":{},\"width\":{},\
+             \"height\":{},\"rotation\":0,\"rectanglelabels\":[\"{:?}\"]}}}}",
+            e.id(),
+            json_number(width),
+            json_number(height),
+            json_number((x1 - x_min) / width * 100.0),
+            json_number((y1 - y_min) / height * 100.0),
+            json_number((x2 - x1) / width * 100.0),
+            json_number((y2 - y1) / height * 100.0),
+            e.semantic_label()
+        )
+    });
+    let relations = order.windows(2).map(|pair| {
+        format!(
+            "{{\"type\":\"relation\",\"from_id\":\"e{}\",\"to_id\":\"e{}\",\"direction\":\"right\",\"labels\":[\"next\"]}}",
+            pair[0], pair[1]
+        )
+    });
+    let result: Vec<String> = regions.chain(relations).collect();
+
+    format!(
+        "{{\"data\":{{\"image\":{}}},\"predictions\":[{{\"model_version\":\"xycut-plus-plus/{}\",\"result\":[{}]}}]}}",
+        json_string(image),
+        ALGORITHM_VERSION,
+        result.join(",")
+    )
+}
+
+/// Render the ordered page as a tool-neutral annotation document
+///
+/// Schema:
+///
+/// ```text
+/// {
+///   "version": <ALGORITHM_VERSION>,
+///   "elements": [{"id": <usize>, "bbox": [x1, y1, x2, y2], "label": <SemanticLabel name>, "rank": <usize | null>}],
+///   "order": [<id>, ...]
+/// }
+/// ```
+///
+/// `rank` is the element's position in `order`, `null` for elements left out of
+/// the order (noise, dropped background). Coordinates are in the input frame;
+/// non-finite ones are written as `null`.
+pub fn to_annotation_json<T: BoundingBox>(elements: &[T], order: &[usize]) -> String {
+    let entries: Vec<String> = elements
+        .iter()
+        .map(|e| {
+            let (x1, y1, x2, y2) = e.bounds();
+            let rank = order
+                .iter()
+                .position(|&id| id == e.id())
+                .map_or("null".to_string(), |rank| rank.to_string());
+            format!(
+                "{{\"id\":{},\"bbox\":[{},{},{},{}],\"label\":\"{:?}\",\"rank\":{}}}",
+                e.id(),
+                json_number(x1),
+                json_number(y1),
+                json_number(x2),
+                json_number(y2),
+                e.semantic_label(),
+                rank
+            )
+        })
+        .collect();
+    let ids: Vec<String> = order.iter().map(|id| id.to_string()).collect();
+
+    format!(
+        "{{\"version\":{},\"elements\":[{}],\"order\":[{}]}}",
+        ALGORITHM_VERSION,
+        entries.join(","),
+        ids.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    #[test]
+    fn non_finite_coordinates_stay_valid_json() {
+        let elements = vec![
+            Element::new(0, 10.0, 10.0, 90.0, 20.0, SemanticLabel::Regular),
+            Element::new(
+                1,
+                f32::NAN,
+                30.0,
+                f32::INFINITY,
+                40.0,
+                SemanticLabel::Regular,
+            ),
+        ];
+        let annotation: serde_json::Value =
+            serde_json::from_str(&to_annotation_json(&elements, &[0])).unwrap();
+        assert_eq!(
+            annotation["elements"][1]["bbox"],
+            serde_json::json!([null, 30, null, 40])
+        );
+
+        // A zero-width page turns every percentage into NaN
+        let task = to_label_studio(&elements, &[0, 1], (0.0, 0.0, 0.0, 100.0), "page.png");
+        assert!(serde_json::from_str::<serde_json::Value>(&task).is_ok());
+    }
+}
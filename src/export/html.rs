@@ -0,0 +1,193 @@
+use super::{blocks, Block, BlockKind};
+use crate::paragraph::ParagraphConfig;
+use crate::text::ListMarker;
+use crate::traits::{BoundingBox, TextContent};
+
+/// Render the page as semantic HTML in reading order
+///
+/// Produces an `<article>` fragment with `<h1>`/`<h2>` titles, `<p>` paragraphs,
+/// `<ol>`/`<ul>` lists, `<figure>` placeholders (captioned with their text) and
+/// `<table>` skeletons for Vision elements whose text is tab-separated rows.
+/// Every block carries `data-ids` (member element ids) and `data-bbox`
+/// (`x1,y1,x2,y2` union of the member boxes) for visual QA.
+pub fn to_html<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+    config: &ParagraphConfig,
+) -> String {
+    let mut output = String::from("<article>\n");
+    let mut open_list: Option<&'static str> = None;
+
+    for block in blocks(elements, order, config) {
+        let list_tag = match block.kind {
+            BlockKind::ListItem(ListMarker::Number(_)) => Some("ol"),
+            BlockKind::ListItem(_) => Some("ul"),
+            _ => None,
+        };
+
+        // Open or close list containers around consecutive list items
+        if open_list != list_tag {
+            if let Some(tag) = open_list {
+                output.push_str(&format!("</{}>\n", tag));
+            }
+            if let Some(tag) = list_tag {
+                output.push_str(&format!("<{}>\n", tag));
+            }
+            open_list = list_tag;
+        }
+
+        let attributes = data_attributes(&block);
+        let element = match block.kind {
+            BlockKind::Heading(level) => format!(
+                "<h{level}{attributes}>{}</h{level}>",
+                escape_html(&block.text)
+            ),
+            BlockKind::Figure if block.text.is_empty() => {
+                format!("<figure{}></figure>", attributes)
+            }
+            BlockKind::Figure => format!(
+                "<figure{}><figcaption>{}</figcaption></figure>",
+                attributes,
+                escape_html(&block.text)
+            ),
+            BlockKind::Table => format!("<table{}>{}</table>", attributes, table_rows(&block.text)),
+            // Lettered items sit in a `<ul>` and keep their marker
+            BlockKind::ListItem(ListMarker::Letter(_)) => {
+                format!("<li{}>{}</li>", attributes, escape_html(&block.text))
+            }
+            BlockKind::ListItem(_) => {
+                format!("<li{}>{}</li>", attributes, escape_html(block.item_text()))
+            }
+            BlockKind::Paragraph => format!("<p{}>{}</p>", attributes, escape_html(&block.text)),
+        };
+
+        if list_tag.is_some() {
+            output.push_str("  ");
+        }
+        output.push_str(&element);
+        output.push('\n');
+    }
+
+    if let Some(tag) = open_list {
+        output.push_str(&format!("</{}>\n", tag));
+    }
+    output.push_str("</article>\n");
+    output
+}
+
+fn data_attributes(block: &Block) -> String {
+    let ids: Vec<String> = block.ids.iter().map(|id| id.to_string()).collect();
+    let (x1, y1, x2, y2) = block.bounds;
+    format!(
+        " data-ids=\"{}\" data-bbox=\"{},{},{},{}\"",
+        ids.join(" "),
+        x1,
+        y1,
+        x2,
+        y2
+    )
+}
+
+fn table_rows(text: &str) -> String {
+    text.lines()
+        .map(|row| {
+            let cells: String = row
+                .split('\t')
+                .map(|cell| format!("<td>{}</td>", escape_html(cell.trim())))
+                .collect();
+            format!("<tr>{}</tr>", cells)
+        })
+        .collect()
+}
+
+/// Escape text for use in HTML element content and attribute values
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{line, TextLine};
+    use crate::traits::SemanticLabel;
+
+    fn labelled(mut line: TextLine, label: SemanticLabel) -> TextLine {
+        line.element.label = label;
+        line.element.mask = true;
+        line
+    }
+
+    #[test]
+    fn renders_blocks_in_order() {
+        let elements = [
+            labelled(
+                line(0, (0.0, 0.0, 200.0, 20.0), "Report"),
+                SemanticLabel::HorizontalTitle,
+            ),
+            line(1, (0.0, 30.0, 200.0, 40.0), "Fish & <chips>"),
+            line(2, (0.0, 54.0, 200.0, 64.0), "1. first"),
+            line(3, (0.0, 68.0, 200.0, 78.0), "2. second"),
+            labelled(
+                line(4, (0.0, 90.0, 200.0, 150.0), "Figure 1"),
+                SemanticLabel::Vision,
+            ),
+            labelled(
+                line(5, (0.0, 160.0, 200.0, 200.0), "a\t\"b\"\nc\td"),
+                SemanticLabel::Vision,
+            ),
+            line(6, (0.0, 210.0, 200.0, 220.0), "• bullet"),
+        ];
+        let html = to_html(
+            &elements,
+            &[0, 1, 2, 3, 4, 5, 6],
+            &ParagraphConfig::default(),
+        );
+        assert_eq!(
+            html,
+            "<article>\n\
+             <h1 data-ids=\"0\" data-bbox=\"0,0,200,20\">Report</h1>\n\
+             <p data-ids=\"1\" data-bbox=\"0,30,200,40\">Fish &amp; &lt;chips&gt;</p>\n\
+             <ol>\n  \
+             <li data-ids=\"2\" data-bbox=\"0,54,200,64\">first</li>\n  \
+             <li data-ids=\"3\" data-bbox=\"0,68,200,78\">second</li>\n\
+             </ol>\n\
+             <figure data-ids=\"4\" data-bbox=\"0,90,200,150\"><figcaption>Figure 1</figcaption></figure>\n\
+             <table data-ids=\"5\" data-bbox=\"0,160,200,200\">\
+             <tr><td>a</td><td>&quot;b&quot;</td></tr><tr><td>c</td><td>d</td></tr></table>\n\
+             <ul>\n  \
+             <li data-ids=\"6\" data-bbox=\"0,210,200,220\">bullet</li>\n\
+             </ul>\n\
+             </article>\n"
+        );
+    }
+
+    #[test]
+    fn lettered_items_keep_their_marker() {
+        let elements = [
+            line(0, (0.0, 0.0, 200.0, 10.0), "(a) one"),
+            line(1, (0.0, 14.0, 200.0, 24.0), "(b) two"),
+        ];
+        let html = to_html(&elements, &[0, 1], &ParagraphConfig::default());
+        assert!(html.contains("<ul>\n  <li data-ids=\"0\" data-bbox=\"0,0,200,10\">(a) one</li>\n"));
+        assert!(html.ends_with("(b) two</li>\n</ul>\n</article>\n"));
+    }
+
+    #[test]
+    fn empty_page_is_an_empty_article() {
+        let elements: [TextLine; 0] = [];
+        assert_eq!(
+            to_html(&elements, &[], &ParagraphConfig::default()),
+            "<article>\n</article>\n"
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use super::json_string;
+use crate::traits::{BoundingBox, TextContent};
+
+/// Render the ordered page as LayoutLM / LiLT training tokens
+///
+/// Schema, as read by the Hugging Face token-classification scripts (FUNSD style):
+///
+/// ```text
+/// {"tokens": [<word>, ...], "bboxes": [[x1, y1, x2, y2], ...]}
+/// ```
+///
+/// Tokens are the whitespace-separated words of each element's text, following
+/// `order`; every word takes its element's box (segment-level positions, as LayoutLMv3
+/// and LiLT are pre-trained with). Boxes are integers normalized to 0–1000 over
+/// `page`. Elements without text or left out of the order are skipped.
+pub fn to_layoutlm<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+    page: (f32, f32, f32, f32),
+) -> String {
+    let (x_min, y_min, x_max, y_max) = page;
+    let normalize = |value: f32, min: f32, max: f32| {
+        let scaled = (value - min) / (max - min) * 1000.0;
+        if scaled.is_finite() {
+            scaled.round().clamp(0.0, 1000.0) as u32
+        } else {
+            0
+        }
+    };
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+
+    let mut tokens = Vec::new();
+    let mut bboxes = Vec::new();
+    for element in order.iter().filter_map(|id| by_id.get(id)) {
+        let (x1, y1, x2, y2) = element.bounds();
+        let bbox = format!(
+            "[{},{},{},{}]",
+            normalize(x1, x_min, x_max),
+            normalize(y1, y_min, y_max),
+            normalize(x2, x_min, x_max),
+            normalize(y2, y_min, y_max)
+        );
+        for word in element.text().split_whitespace() {
+            tokens.push(json_string(word));
+            bboxes.push(bbox.clone());
+        }
+    }
+
+    format!(
+        "{{\"tokens\":[{}],\"bboxes\":[{}]}}",
+        tokens.join(","),
+        bboxes.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::line;
+
+    #[test]
+    fn words_follow_the_order_with_normalized_boxes() {
+        let elements = [
+            line(0, (100.0, 400.0, 500.0, 440.0), "second \"line\""),
+            line(1, (100.0, 100.0, 500.0, 140.0), "First line"),
+            line(2, (100.0, 600.0, 500.0, 640.0), ""),
+            line(3, (100.0, 700.0, 500.0, 740.0), "dropped"),
+        ];
+        let json = to_layoutlm(&elements, &[1, 0, 2], (0.0, 0.0, 1000.0, 2000.0));
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "tokens": ["First", "line", "second", "\"line\""],
+                "bboxes": [
+                    [100, 50, 500, 70],
+                    [100, 50, 500, 70],
+                    [100, 200, 500, 220],
+                    [100, 200, 500, 220],
+                ],
+            })
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use super::{blocks, BlockKind};
+use crate::paragraph::ParagraphConfig;
+use crate::text::ListMarker;
+use crate::traits::{BoundingBox, TextContent};
+
+/// Render the page as reading-ordered plain text
+///
+/// Lines are merged into paragraphs (see [`crate::group_paragraphs_with_text`]),
+/// hyphenated line breaks are joined and paragraphs are separated by a blank line.
+/// Elements without text (e.g. figures) are skipped.
+pub fn to_plain_text<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+    config: &ParagraphConfig,
+) -> String {
+    blocks(elements, order, config)
+        .into_iter()
+        .map(|block| block.text)
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Render the page as reading-ordered Markdown
+///
+/// - Titles become headings: the tallest title on the page is `#`, the others `##`
+/// - List items become `-` bullets, numbered items keep their number
+/// - Vision elements become `[Figure <id>]` placeholders followed by their text if any,
+///   or a Markdown table when their text is tab-separated rows, with `|` and inline
+///   markup characters in cells backslash-escaped
+/// - Everything else is emitted as a paragraph
+///
+/// Text starting with a Markdown block marker ("#", ">", "-", "2024.") has the marker
+/// backslash-escaped, so it keeps the block kind it was detected as.
+pub fn to_markdown<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+    config: &ParagraphConfig,
+) -> String {
+    let mut output = String::new();
+    let mut previous_was_list = false;
+
+    for block in blocks(elements, order, config) {
+        let rendered = match block.kind {
+            BlockKind::Heading(level) => {
+                format!(
+                    "{} {}",
+                    "#".repeat(level as usize),
+                    escape_block_start(&block.text)
+                )
+            }
+            BlockKind::Figure if block.text.is_empty() => format!("[Figure {}]", block.ids[0]),
+            BlockKind::Figure => format!("[Figure {}] {}", block.ids[0], block.text),
+            BlockKind::Table => markdown_table(&block.text),
+            BlockKind::ListItem(ListMarker::Number(n)) => {
+                format!("{}. {}", n, escape_block_start(block.item_text()))
+            }
+            BlockKind::ListItem(ListMarker::Letter(c)) => {
+                format!("- {}) {}", c, escape_block_start(block.item_text()))
+            }
+            BlockKind::ListItem(ListMarker::Bullet) => {
+                format!("- {}", escape_block_start(block.item_text()))
+            }
+            BlockKind::Paragraph if block.text.is_empty() => continue,
+            BlockKind::Paragraph => escape_block_start(&block.text),
+        };
+        let is_list = matches!(block.kind, BlockKind::ListItem(_));
+
+        if !output.is_empty() {
+            // Keep consecutive list items in one Markdown list
+            output.push_str(if is_list && previous_was_list {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+        output.push_str(&rendered);
+        previous_was_list = is_list;
+    }
+
+    output
+}
+
+/// Render tab-separated rows as a Markdown table, the first row being the header
+fn markdown_table(text: &str) -> String {
+    let rows: Vec<Vec<&str>> = text
+        .lines()
+        .map(|row| row.split('\t').map(str::trim).collect())
+        .collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut lines = Vec::with_capacity(rows.len() + 1);
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = (0..columns)
+            .map(|c| escape_cell(row.get(c).copied().unwrap_or("")))
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+        if i == 0 {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Backslash-escape a leading block marker: headings, quotes, bullets, thematic
+/// breaks, code fences, tables and ordered list numbers ("2024. A year")
+fn escape_block_start(text: &str) -> String {
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        let (number, rest) = text.split_at(digits);
+        return if rest.starts_with(['.', ')']) {
+            format!("{}\\{}", number, rest)
+        } else {
+            text.to_string()
+        };
+    }
+
+    if text.starts_with(['#', '>', '-', '+', '*', '_', '=', '`', '~', '|']) {
+        format!("\\{}", text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Backslash-escape the characters that would end a table cell or start inline markup
+fn escape_cell(cell: &str) -> String {
+    let mut escaped = String::with_capacity(cell.len());
+    for c in cell.chars() {
+        if matches!(c, '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::line;
+
+    #[test]
+    fn table_cells_are_escaped() {
+        let table = markdown_table("Option\tPrice\nA | B\t*5* [net]\npath\\to\t<br>");
+        assert_eq!(
+            table,
+            "| Option | Price |\n\
+             | --- | --- |\n\
+             | A \\| B | \\*5\\* \\[net\\] |\n\
+             | path\\\\to | \\<br\\> |"
+        );
+    }
+
+    #[test]
+    fn short_rows_are_padded() {
+        assert_eq!(
+            markdown_table("a\tb\nc"),
+            "| a | b |\n| --- | --- |\n| c |  |"
+        );
+    }
+
+    #[test]
+    fn leading_block_markers_are_escaped() {
+        let lines = [
+            line(0, (0.0, 0.0, 200.0, 10.0), "# not a heading"),
+            line(1, (0.0, 30.0, 200.0, 40.0), "2024. A year"),
+            line(2, (0.0, 60.0, 200.0, 70.0), "> not a quote"),
+            line(3, (0.0, 90.0, 200.0, 100.0), "1. > quoted item"),
+            line(4, (0.0, 120.0, 200.0, 130.0), "12 apples"),
+        ];
+        let markdown = to_markdown(&lines, &[0, 1, 2, 3, 4], &ParagraphConfig::default());
+        assert_eq!(
+            markdown,
+            "\\# not a heading\n\n\
+             2024\\. A year\n\n\
+             \\> not a quote\n\n\
+             1. \\> quoted item\n\n\
+             12 apples"
+        );
+    }
+}
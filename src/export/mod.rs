@@ -0,0 +1,152 @@
+//! Exporters rendering a computed reading order into downstream formats
+
+pub mod annotation;
+pub mod html;
+pub mod layoutlm;
+pub mod markdown;
+pub mod structure;
+
+pub use annotation::{to_annotation_json, to_label_studio};
+pub use html::to_html;
+pub use layoutlm::to_layoutlm;
+pub use markdown::{to_markdown, to_plain_text};
+pub use structure::{structure_tree, structure_tree_with_text, StructElem, StructRole};
+
+use std::collections::HashMap;
+
+use crate::paragraph::{group_paragraphs, group_paragraphs_with_text, ParagraphConfig};
+use crate::text::{join_lines, split_list_marker, ListMarker};
+use crate::traits::{BoundingBox, SemanticLabel, TextContent};
+
+/// Structural role of an exported block
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BlockKind {
+    /// Title; level 1 for the tallest title on the page, 2 otherwise
+    Heading(u8),
+    /// Vision element without tabular text
+    Figure,
+    /// Vision element whose text is tab-separated rows
+    Table,
+    /// Paragraph starting with a list marker
+    ListItem(ListMarker),
+    /// Any other text
+    Paragraph,
+}
+
+/// A paragraph-level unit of the ordered page
+#[derive(Debug, Clone)]
+pub(crate) struct Block {
+    pub ids: Vec<usize>,
+    pub kind: BlockKind,
+    /// Joined text; tables keep their raw rows
+    pub text: String,
+    /// Union of the member bounding boxes
+    pub bounds: (f32, f32, f32, f32),
+}
+
+/// Group the ordered elements into paragraphs and classify each one
+pub(crate) fn blocks<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+    config: &ParagraphConfig,
+) -> Vec<Block> {
+    let groups = group_paragraphs_with_text(elements, order, config);
+    classify(elements, groups, |e| e.text())
+}
+
+/// Text-less variant of [`blocks`]: geometric paragraphs, kinds from semantic labels only
+pub(crate) fn geometric_blocks<T: BoundingBox>(
+    elements: &[T],
+    order: &[usize],
+    config: &ParagraphConfig,
+) -> Vec<Block> {
+    classify(elements, group_paragraphs(elements, order, config), |_| "")
+}
+
+fn classify<T, F>(elements: &[T], groups: Vec<Vec<usize>>, text_of: F) -> Vec<Block>
+where
+    T: BoundingBox,
+    F: Fn(&T) -> &str,
+{
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+
+    let top_title_height = elements
+        .iter()
+        .filter(|e| e.semantic_label().is_title())
+        .map(|e| {
+            let (_, y1, _, y2) = e.bounds();
+            y2 - y1
+        })
+        .fold(0.0, f32::max);
+
+    groups
+        .into_iter()
+        .map(|ids| {
+            let first = by_id[&ids[0]];
+            let text = join_lines(ids.iter().map(|id| text_of(by_id[id])));
+            let bounds = ids
+                .iter()
+                .map(|id| by_id[id].bounds())
+                .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))
+                .unwrap_or_default();
+
+            let (kind, text) = match first.semantic_label() {
+                label if label.is_title() => {
+                    let (_, y1, _, y2) = first.bounds();
+                    let level = if y2 - y1 >= top_title_height { 1 } else { 2 };
+                    (BlockKind::Heading(level), text)
+                }
+                SemanticLabel::Vision if text_of(first).contains('\t') => {
+                    (BlockKind::Table, text_of(first).trim().to_string())
+                }
+                SemanticLabel::Vision => (BlockKind::Figure, text),
+                _ => match split_list_marker(&text) {
+                    Some((marker, _)) => (BlockKind::ListItem(marker), text),
+                    None => (BlockKind::Paragraph, text),
+                },
+            };
+
+            Block {
+                ids,
+                kind,
+                text,
+                bounds,
+            }
+        })
+        .collect()
+}
+
+impl Block {
+    /// Block text with a leading list marker removed
+    pub fn item_text(&self) -> &str {
+        split_list_marker(&self.text).map_or(&self.text, |(_, item)| item)
+    }
+}
+
+/// `value` as a JSON number; JSON has no NaN or infinities, they are written as `null`
+pub(crate) fn json_number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Quote and escape `text` as a JSON string literal
+pub(crate) fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,285 @@
+use super::{blocks, geometric_blocks, json_number, json_string, Block, BlockKind};
+use crate::paragraph::ParagraphConfig;
+use crate::traits::{BoundingBox, TextContent};
+
+/// PDF standard structure type of a structure element (ISO 32000-1, section 14.8.4)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructRole {
+    Document,
+    H1,
+    H2,
+    P,
+    /// List
+    L,
+    /// List item
+    LI,
+    /// List item body
+    LBody,
+    Figure,
+    Table,
+    /// Table row
+    TR,
+    /// Table data cell
+    TD,
+}
+
+impl StructRole {
+    /// Structure type name as written in the `/S` entry of a StructElem
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Document => "Document",
+            Self::H1 => "H1",
+            Self::H2 => "H2",
+            Self::P => "P",
+            Self::L => "L",
+            Self::LI => "LI",
+            Self::LBody => "LBody",
+            Self::Figure => "Figure",
+            Self::Table => "Table",
+            Self::TR => "TR",
+            Self::TD => "TD",
+        }
+    }
+}
+
+/// Node of a PDF logical structure tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructElem {
+    pub role: StructRole,
+
+    /// Ids of the elements whose content is tagged by this node, in reading order
+    pub ids: Vec<usize>,
+
+    /// Union of the member boxes as (x1, y1, x2, y2), absent for grouping nodes
+    pub bbox: Option<(f32, f32, f32, f32)>,
+
+    /// Alt text for figures, ActualText for table cells
+    pub text: Option<String>,
+
+    /// Child structure elements in reading order
+    pub children: Vec<StructElem>,
+}
+
+impl StructElem {
+    fn new(role: StructRole) -> Self {
+        Self {
+            role,
+            ids: Vec::new(),
+            bbox: None,
+            text: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn leaf(role: StructRole, block: &Block) -> Self {
+        Self {
+            ids: block.ids.clone(),
+            bbox: Some(block.bounds),
+            ..Self::new(role)
+        }
+    }
+
+    /// Serialize the tree as compact JSON
+    ///
+    /// Each node is `{"role": ..., "ids": [...], "bbox": [x1, y1, x2, y2], "text": ..., "children": [...]}`;
+    /// empty `ids`/`children` and absent `bbox`/`text` are omitted. Non-finite
+    /// coordinates are written as `null`.
+    pub fn to_json(&self) -> String {
+        let mut fields = vec![format!("\"role\":\"{}\"", self.role.as_str())];
+
+        if !self.ids.is_empty() {
+            let ids: Vec<String> = self.ids.iter().map(|id| id.to_string()).collect();
+            fields.push(format!("\"ids\":[{}]", ids.join(",")));
+        }
+        if let Some((x1, y1, x2, y2)) = self.bbox {
+            let bbox: Vec<String> = [x1, y1, x2, y2].into_iter().map(json_number).collect();
+            fields.push(format!("\"bbox\":[{}]", bbox.join(",")));
+        }
+        if let Some(text) = &self.text {
+            fields.push(format!("\"text\":{}", json_string(text)));
+        }
+        if !self.children.is_empty() {
+            let children: Vec<String> = self.children.iter().map(Self::to_json).collect();
+            fields.push(format!("\"children\":[{}]", children.join(",")));
+        }
+
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+/// Build a structure tree from the reading order using semantic labels only
+///
+/// Titles map to `H1`/`H2`, Vision elements to `Figure`, and geometric
+/// paragraphs to `P`, all as children of a `Document` root in reading order.
+pub fn structure_tree<T: BoundingBox>(
+    elements: &[T],
+    order: &[usize],
+    config: &ParagraphConfig,
+) -> StructElem {
+    tree_from_blocks(geometric_blocks(elements, order, config))
+}
+
+/// Content-aware variant of [`structure_tree`]
+///
+/// Additionally detects lists (`L` > `LI` > `LBody`), tables from tab-separated
+/// Vision text (`Table` > `TR` > `TD`) and uses figure text as alt text.
+pub fn structure_tree_with_text<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+    config: &ParagraphConfig,
+) -> StructElem {
+    tree_from_blocks(blocks(elements, order, config))
+}
+
+fn tree_from_blocks(blocks: Vec<Block>) -> StructElem {
+    let mut document = StructElem::new(StructRole::Document);
+
+    for block in blocks {
+        let node = match block.kind {
+            BlockKind::Heading(1) => StructElem::leaf(StructRole::H1, &block),
+            BlockKind::Heading(_) => StructElem::leaf(StructRole::H2, &block),
+            BlockKind::Paragraph => StructElem::leaf(StructRole::P, &block),
+            BlockKind::Figure => StructElem {
+                text: Some(block.text.clone()).filter(|text| !text.is_empty()),
+                ..StructElem::leaf(StructRole::Figure, &block)
+            },
+            BlockKind::Table => StructElem {
+                children: block
+                    .text
+                    .lines()
+                    .map(|row| StructElem {
+                        children: row
+                            .split('\t')
+                            .map(|cell| StructElem {
+                                text: Some(cell.trim().to_string()),
+                                ..StructElem::new(StructRole::TD)
+                            })
+                            .collect(),
+                        ..StructElem::new(StructRole::TR)
+                    })
+                    .collect(),
+                ..StructElem::leaf(StructRole::Table, &block)
+            },
+            BlockKind::ListItem(_) => {
+                let item = StructElem {
+                    children: vec![StructElem::leaf(StructRole::LBody, &block)],
+                    bbox: Some(block.bounds),
+                    ..StructElem::new(StructRole::LI)
+                };
+
+                // Consecutive list items share one `L` node
+                match document.children.last_mut() {
+                    Some(list) if list.role == StructRole::L => {
+                        list.bbox = list.bbox.map(|(x1, y1, x2, y2)| {
+                            let (bx1, by1, bx2, by2) = block.bounds;
+                            (x1.min(bx1), y1.min(by1), x2.max(bx2), y2.max(by2))
+                        });
+                        list.children.push(item);
+                        continue;
+                    }
+                    _ => StructElem {
+                        bbox: Some(block.bounds),
+                        children: vec![item],
+                        ..StructElem::new(StructRole::L)
+                    },
+                }
+            }
+        };
+
+        document.children.push(node);
+    }
+
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{line, TextLine};
+    use crate::traits::SemanticLabel;
+
+    fn labelled(mut line: TextLine, label: SemanticLabel) -> TextLine {
+        line.element.label = label;
+        line.element.mask = true;
+        line
+    }
+
+    /// Tree shape as `Role(child,child)`
+    fn shape(node: &StructElem) -> String {
+        let children: Vec<String> = node.children.iter().map(shape).collect();
+        if children.is_empty() {
+            node.role.as_str().to_string()
+        } else {
+            format!("{}({})", node.role.as_str(), children.join(","))
+        }
+    }
+
+    fn page() -> Vec<TextLine> {
+        vec![
+            labelled(
+                line(0, (0.0, 0.0, 200.0, 20.0), "Report"),
+                SemanticLabel::HorizontalTitle,
+            ),
+            labelled(
+                line(1, (0.0, 30.0, 200.0, 42.0), "Scope"),
+                SemanticLabel::HorizontalTitle,
+            ),
+            line(2, (0.0, 54.0, 200.0, 64.0), "1. first"),
+            line(3, (0.0, 68.0, 180.0, 78.0), "2. second"),
+            labelled(
+                line(4, (0.0, 90.0, 200.0, 150.0), "A chart"),
+                SemanticLabel::Vision,
+            ),
+            labelled(
+                line(5, (0.0, 160.0, 200.0, 200.0), "a\tb\nc\td"),
+                SemanticLabel::Vision,
+            ),
+        ]
+    }
+
+    #[test]
+    fn content_aware_tree() {
+        let tree = structure_tree_with_text(&page(), &[0, 1, 2, 3, 4, 5], &Default::default());
+        assert_eq!(
+            shape(&tree),
+            "Document(H1,H2,L(LI(LBody),LI(LBody)),Figure,Table(TR(TD,TD),TR(TD,TD)))"
+        );
+
+        let list = &tree.children[2];
+        assert_eq!(list.bbox, Some((0.0, 54.0, 200.0, 78.0)));
+        assert_eq!(list.children[1].children[0].ids, vec![3]);
+        assert_eq!(tree.children[3].text.as_deref(), Some("A chart"));
+        let cells: Vec<_> = tree.children[4].children[1]
+            .children
+            .iter()
+            .map(|cell| cell.text.as_deref().unwrap())
+            .collect();
+        assert_eq!(cells, ["c", "d"]);
+    }
+
+    #[test]
+    fn geometric_tree_ignores_text() {
+        let tree = structure_tree(&page(), &[0, 1, 2, 3, 4, 5], &Default::default());
+        assert_eq!(shape(&tree), "Document(H1,H2,P,Figure,Figure)");
+        assert_eq!(tree.children[2].ids, vec![2, 3]);
+        assert_eq!(tree.children[3].text, None);
+    }
+
+    #[test]
+    fn non_finite_coordinates_stay_valid_json() {
+        let mut root = StructElem::new(StructRole::Document);
+        root.children.push(StructElem {
+            ids: vec![3],
+            bbox: Some((f32::NAN, 0.0, f32::INFINITY, 10.0)),
+            text: Some("a \"quoted\" caption".to_string()),
+            ..StructElem::new(StructRole::Figure)
+        });
+
+        let json: serde_json::Value = serde_json::from_str(&root.to_json()).unwrap();
+        assert_eq!(
+            json["children"][0]["bbox"],
+            serde_json::json!([null, 0, null, 10])
+        );
+        assert_eq!(json["children"][0]["text"], "a \"quoted\" caption");
+    }
+}
@@ -0,0 +1,122 @@
+//! Ordering of regions that no cut can split
+
+use std::cmp::Ordering;
+
+use crate::core::LabelPriorities;
+use crate::traits::BoundingBox;
+use crate::utils::{compare_position, sort_by_rows};
+
+/// Share of the shorter of an element and a row that must overlap vertically for
+/// the element to join the row under [`RowGrouping::Overlap`]
+const ROW_OVERLAP_RATIO: f32 = 0.5;
+
+/// How the fallback sort groups a region's elements into rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RowGrouping {
+    /// A row holds the elements within `same_row_tolerance` of its first element
+    #[default]
+    Tolerance,
+    /// A row holds the elements whose vertical extent overlaps it by at least half
+    /// the shorter height, independent of any pixel tolerance; suits pages mixing
+    /// very different font sizes
+    Overlap,
+}
+
+/// How the fallback sort reads a region without a cut
+///
+/// The default reproduces the plain position sort: rows grouped by center within
+/// `same_row_tolerance`, each row left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FallbackSort {
+    /// Group rows by element tops instead of centers, so a short title and a tall
+    /// paragraph starting at the same height share a row
+    pub use_tops: bool,
+
+    /// Within a row, read elements by label priority (`label_priorities`: by default
+    /// cross-layout, titles, vision, regular) before left to right, so a title is
+    /// never read after the body text level with it
+    pub label_priority: bool,
+
+    /// How elements are grouped into rows
+    pub rows: RowGrouping,
+}
+
+impl FallbackSort {
+    /// Sort `elements` into reading order
+    pub fn sort<T: BoundingBox>(
+        &self,
+        elements: &mut [T],
+        same_row_tolerance: f32,
+        priorities: &LabelPriorities,
+    ) {
+        if *self == Self::default() {
+            sort_by_rows(elements, same_row_tolerance);
+            return;
+        }
+
+        let top = |e: &T| {
+            if self.use_tops || self.rows == RowGrouping::Overlap {
+                e.bounds().1
+            } else {
+                e.center().1
+            }
+        };
+        elements.sort_by(|a, b| top(a).total_cmp(&top(b)).then(compare_position(a, b)));
+
+        let mut start = 0;
+        while start < elements.len() {
+            let row = &elements[start..];
+            let len = match self.rows {
+                RowGrouping::Tolerance => {
+                    let row_y = top(&row[0]);
+                    row.iter()
+                        .skip(1)
+                        .position(|e| top(e) - row_y >= same_row_tolerance)
+                        .map_or(row.len(), |offset| 1 + offset)
+                }
+                RowGrouping::Overlap => overlapping_row_len(row),
+            };
+            elements[start..start + len].sort_by(|a, b| self.compare_in_row(a, b, priorities));
+            start += len;
+        }
+    }
+
+    fn compare_in_row<T: BoundingBox>(
+        &self,
+        a: &T,
+        b: &T,
+        priorities: &LabelPriorities,
+    ) -> Ordering {
+        let priority = |e: &T| {
+            if self.label_priority {
+                priorities.rank(e.semantic_label())
+            } else {
+                0
+            }
+        };
+        priority(a)
+            .cmp(&priority(b))
+            .then(a.center().0.total_cmp(&b.center().0))
+            .then(compare_position(a, b))
+    }
+}
+
+/// Number of leading `elements` (sorted by top) forming one row of overlapping
+/// vertical extents
+fn overlapping_row_len<T: BoundingBox>(elements: &[T]) -> usize {
+    let (_, mut row_top, _, mut row_bottom) = elements[0].bounds();
+    for (i, e) in elements.iter().enumerate().skip(1) {
+        let (_, y1, _, y2) = e.bounds();
+        let overlap = row_bottom.min(y2) - row_top.max(y1);
+        let shorter = (y2 - y1).min(row_bottom - row_top);
+        if overlap < 0.0 || overlap < ROW_OVERLAP_RATIO * shorter {
+            return i;
+        }
+        row_top = row_top.min(y1);
+        row_bottom = row_bottom.max(y2);
+    }
+    elements.len()
+}
@@ -0,0 +1,124 @@
+//! Fixed-point and quantized coordinates, for orders that are bit-identical across
+//! platforms or robust to detection jitter
+//!
+//! Both modes share one path: every coordinate is rounded to a grid, then the page
+//! is ordered by the regular float pipeline. Neither does integer arithmetic; what
+//! the fixed-point mode guarantees follows from the float pipeline using only basic
+//! IEEE-754 operations (see [`XYCutPlusPlus::compute_order_fixed`]).
+
+use crate::core::{XYCutConfig, XYCutPlusPlus};
+use crate::element::Element;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+/// Largest grid index; every integer up to it is exact in an `f32`
+const MAX_GRID: f32 = (1 << 24) as f32;
+
+/// Grid of `step` units anchored at `origin` that coordinates are rounded to
+#[derive(Debug, Clone, Copy)]
+struct Grid {
+    origin: (f32, f32),
+    step: f32,
+}
+
+impl Grid {
+    /// Round `v` to a grid line of the axis anchored at `origin`; non-finite values
+    /// are left for the float pipeline to reject
+    fn snap(&self, v: f32, origin: f32, round: fn(f32) -> f32) -> f32 {
+        if !v.is_finite() {
+            return v;
+        }
+        let index = round((v - origin) / self.step).clamp(-MAX_GRID, MAX_GRID);
+        origin + index * self.step
+    }
+}
+
+impl XYCutPlusPlus {
+    /// Order the page with every coordinate snapped to a grid of `1 / scale` units
+    ///
+    /// This is not an integer implementation: histograms, cuts and distances are
+    /// still computed in `f32`. Orders are bit-identical across platforms because,
+    /// once the input is on the grid, the pipeline only uses `+`, `-`, `*`, `/` and
+    /// `sqrt`, which IEEE-754 requires to be correctly rounded and which Rust never
+    /// fuses or evaluates in extended precision. Deskewing needs platform
+    /// trigonometry and is skipped. Float noise below half a step in the input
+    /// cannot change the order.
+    pub(crate) fn compute_order_fixed<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        page: (f32, f32, f32, f32),
+        scale: u32,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        let grid = Grid {
+            origin: (0.0, 0.0),
+            step: 1.0 / scale.max(1) as f32,
+        };
+        let config = XYCutConfig {
+            fixed_point: None,
+            deskew: false,
+            ..self.config.clone()
+        };
+        Self::compute_order_on_grid(elements, page, grid, config, trace)
+    }
+
+    /// Order the page with every coordinate rounded to a grid of `step` units
+    /// anchored at the page's top-left corner
+    ///
+    /// The order lists the same ids as the input.
+    pub(crate) fn compute_order_quantized<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        page: (f32, f32, f32, f32),
+        step: f32,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        let grid = Grid {
+            origin: (page.0, page.1),
+            step,
+        };
+        let config = XYCutConfig {
+            quantization: None,
+            ..self.config.clone()
+        };
+        Self::compute_order_on_grid(elements, page, grid, config, trace)
+    }
+
+    /// Round the boxes to the nearest grid lines and the page outward, so boxes on
+    /// the page stay on it and a page narrower than one step keeps its size, then
+    /// order with `config`
+    fn compute_order_on_grid<T: BoundingBox>(
+        elements: &[T],
+        page: (f32, f32, f32, f32),
+        grid: Grid,
+        config: XYCutConfig,
+        trace: &mut Trace,
+    ) -> Vec<usize> {
+        let (ox, oy) = grid.origin;
+        let snapped: Vec<Element> = elements
+            .iter()
+            .map(|e| {
+                let mut element = Element::from_box(e);
+                (element.x1, element.x2) = (
+                    grid.snap(element.x1, ox, f32::round),
+                    grid.snap(element.x2, ox, f32::round),
+                );
+                (element.y1, element.y2) = (
+                    grid.snap(element.y1, oy, f32::round),
+                    grid.snap(element.y2, oy, f32::round),
+                );
+                element
+            })
+            .collect();
+
+        let (x_min, y_min, x_max, y_max) = page;
+        XYCutPlusPlus::new(config).compute_order_traced(
+            &snapped,
+            grid.snap(x_min, ox, f32::floor),
+            grid.snap(y_min, oy, f32::floor),
+            grid.snap(x_max, ox, f32::ceil),
+            grid.snap(y_max, oy, f32::ceil),
+            trace,
+        )
+    }
+}
@@ -0,0 +1,102 @@
+//! Inline and display formulas
+
+use crate::traits::{BoundingBox, SemanticLabel};
+
+/// Minimum fraction of a formula's area inside a text block for it to be inline
+const INLINE_INSIDE_RATIO: f32 = 0.5;
+
+/// Minimum vertical overlap, relative to the taller box, of a formula and a text
+/// box sharing its line
+const INLINE_LINE_OVERLAP_RATIO: f32 = 0.5;
+
+/// Maximum horizontal gap between a formula and a text box on its line, relative
+/// to the formula's height (a few spaces; narrower than a column gutter)
+const INLINE_GAP_RATIO: f32 = 0.5;
+
+/// How elements labelled [`SemanticLabel::Formula`] are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FormulaHandling {
+    /// Formulas within a text block or on a line of text are inline; the others
+    /// are display equations
+    #[default]
+    Auto,
+    /// Every formula is a display equation
+    Display,
+    /// Every formula is ordered as text
+    Inline,
+}
+
+impl FormulaHandling {
+    /// Whether `formula` is ordered as text rather than masked
+    pub(crate) fn is_inline<T: BoundingBox>(self, formula: &T, elements: &[T]) -> bool {
+        match self {
+            Self::Display => false,
+            Self::Inline => true,
+            Self::Auto => is_inline_formula(formula, elements),
+        }
+    }
+}
+
+/// Whether `formula` lies mostly inside a text block or shares a line with text
+fn is_inline_formula<T: BoundingBox>(formula: &T, elements: &[T]) -> bool {
+    let (fx1, fy1, fx2, fy2) = formula.bounds();
+    let (width, height) = (fx2 - fx1, fy2 - fy1);
+    elements
+        .iter()
+        .filter(|e| e.id() != formula.id() && e.semantic_label() == SemanticLabel::Regular)
+        .any(|e| {
+            let (x1, y1, x2, y2) = e.bounds();
+            let overlap_x = x2.min(fx2) - x1.max(fx1);
+            let overlap_y = y2.min(fy2) - y1.max(fy1);
+            let inside = overlap_x.max(0.0) * overlap_y.max(0.0);
+            let gap = -overlap_x;
+            let same_line = overlap_y >= INLINE_LINE_OVERLAP_RATIO * height.max(y2 - y1)
+                && gap <= INLINE_GAP_RATIO * height;
+            (width * height > 0.0 && inside >= INLINE_INSIDE_RATIO * width * height) || same_line
+        })
+}
+
+/// Insertion position in `order` keeping a display formula between the text
+/// around it
+///
+/// The formula goes right after the nearest text (or formula) above it that it
+/// overlaps horizontally, so an equation ending a column stays in that column;
+/// with none above, right before the nearest one below. `None` when the formula
+/// overlaps no text horizontally.
+pub(crate) fn display_formula_position<T: BoundingBox>(
+    formula: &T,
+    order: &[usize],
+    placed: &[&[T]],
+) -> Option<usize> {
+    let (fx1, _, fx2, _) = formula.bounds();
+    let center = formula.center().1;
+    let text: Vec<(usize, (f32, f32, f32, f32))> = order
+        .iter()
+        .enumerate()
+        .filter_map(|(position, id)| {
+            let e = placed
+                .iter()
+                .flat_map(|p| p.iter())
+                .find(|e| e.id() == *id)?;
+            let (x1, _, x2, _) = e.bounds();
+            let text = matches!(
+                e.semantic_label(),
+                SemanticLabel::Regular | SemanticLabel::Formula
+            );
+            (text && x1 < fx2 && x2 > fx1).then_some((position, e.bounds()))
+        })
+        .collect();
+
+    let above = text
+        .iter()
+        .filter(|(_, (_, _, _, y2))| *y2 <= center)
+        .max_by(|a, b| a.1 .3.total_cmp(&b.1 .3).then(a.0.cmp(&b.0)));
+    if let Some((position, _)) = above {
+        return Some(position + 1);
+    }
+    text.iter()
+        .filter(|(_, (_, y1, _, _))| *y1 >= center)
+        .min_by(|a, b| a.1 .1.total_cmp(&b.1 .1).then(a.0.cmp(&b.0)))
+        .map(|(position, _)| *position)
+}
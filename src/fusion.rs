@@ -0,0 +1,213 @@
+//! Fuse the boxes of several detectors trusted to different degrees
+//!
+//! A layout model, an OCR engine and a table detector each see part of the page:
+//! the layout model labels coarse regions, OCR finds every line, the table
+//! detector finds tables the others miss or split. [`fuse`] reconciles them into
+//! one element set for `compute_order`, preferring the more trusted source
+//! wherever they disagree.
+
+use crate::element::Element;
+use crate::sources::{
+    next_free_id, unique_ids, DuplicateIdError, DuplicateIds, MergedSources, SourceId,
+};
+use crate::traits::{BoundingBox, SemanticLabel};
+
+/// Boxes of one detector with how much they are trusted
+#[derive(Debug, Clone, Copy)]
+pub struct Detections<'a, T> {
+    pub elements: &'a [T],
+
+    /// Relative trust, compared between sources and multiplied with each box's
+    /// confidence (unknown counts as 1)
+    pub trust: f32,
+
+    /// Whether the boxes' labels mean anything; OCR lines are all `Regular` and
+    /// take the label of the layout region they fall in
+    pub labels: bool,
+}
+
+/// Thresholds of [`fuse`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FusionConfig {
+    /// IoU from which boxes of different sources are the same detection
+    pub overlap: f32,
+
+    /// Share of a box's area inside a box of another source from which it is
+    /// contained in it (a line in a paragraph, a cell in a table); above 1, boxes
+    /// are never contained
+    pub containment: f32,
+
+    /// How ids repeated across sources are resolved
+    pub duplicates: DuplicateIds,
+}
+
+impl Default for FusionConfig {
+    fn default() -> Self {
+        Self {
+            overlap: 0.7,
+            containment: 0.8,
+            duplicates: DuplicateIds::Reassign,
+        }
+    }
+}
+
+/// A box kept so far, with the trust of the source its label came from (negative
+/// infinity for a source without labels)
+struct Kept {
+    index: usize,
+    label: SemanticLabel,
+    label_trust: f32,
+}
+
+/// Fuse the boxes of several detectors into one element set with unique ids
+///
+/// Boxes of different sources overlapping by `overlap` are one detection: the box
+/// of the highest trust × confidence is kept, with the label of the most trusted
+/// source that has labels. Then boxes contained in a box of another source are
+/// dropped in favor of it, unless one of them is more trusted than the container:
+/// the container is dropped instead, and its contents take its label where it is
+/// more trusted than theirs, so OCR lines inherit the layout model's title or
+/// paragraph label. Ids are finally made unique according to `duplicates`;
+/// `MergedSources::fused` maps each dropped box to the element that replaced it.
+pub fn fuse<T: BoundingBox>(
+    detections: &[Detections<T>],
+    config: &FusionConfig,
+) -> Result<MergedSources, DuplicateIdError> {
+    let all: Vec<(usize, &T)> = detections
+        .iter()
+        .enumerate()
+        .flat_map(|(source, d)| d.elements.iter().map(move |e| (source, e)))
+        .collect();
+    let trust = |i: usize| detections[all[i].0].trust;
+    let label_trust = |i: usize| {
+        if detections[all[i].0].labels {
+            trust(i)
+        } else {
+            f32::NEG_INFINITY
+        }
+    };
+    let score = |i: usize| trust(i) * all[i].1.confidence().unwrap_or(1.0);
+
+    // Duplicate detections, best scored first
+    let mut by_score: Vec<usize> = (0..all.len()).collect();
+    by_score.sort_by(|&a, &b| score(b).total_cmp(&score(a)));
+    let mut kept: Vec<Kept> = Vec::new();
+    // Each dropped box with the index in `all` of the box replacing it
+    let mut dropped: Vec<(usize, usize)> = Vec::new();
+    for i in by_score {
+        let (source, e) = all[i];
+        let duplicate = kept.iter_mut().find(|k| {
+            let (other_source, other) = all[k.index];
+            other_source != source && e.iou(other) >= config.overlap
+        });
+        match duplicate {
+            Some(k) => {
+                if label_trust(i) > k.label_trust {
+                    k.label = e.semantic_label();
+                    k.label_trust = label_trust(i);
+                }
+                dropped.push((i, k.index));
+            }
+            None => kept.push(Kept {
+                index: i,
+                label: e.semantic_label(),
+                label_trust: label_trust(i),
+            }),
+        }
+    }
+
+    // Containment, best scored containers first
+    let mut removed = vec![false; kept.len()];
+    for b in 0..kept.len() {
+        if removed[b] {
+            continue;
+        }
+        let (container_source, container) = all[kept[b].index];
+        let contents: Vec<usize> = (0..kept.len())
+            .filter(|&c| c != b && !removed[c] && all[kept[c].index].0 != container_source)
+            .filter(|&c| inside_share(all[kept[c].index].1, container) >= config.containment)
+            .collect();
+        if contents.is_empty() {
+            continue;
+        }
+        let container_trust = trust(kept[b].index);
+        let trusted: Vec<usize> = contents
+            .iter()
+            .copied()
+            .filter(|&c| trust(kept[c].index) > container_trust)
+            .collect();
+        if trusted.is_empty() {
+            for c in contents {
+                removed[c] = true;
+                dropped.push((kept[c].index, kept[b].index));
+            }
+        } else {
+            removed[b] = true;
+            dropped.push((kept[b].index, kept[trusted[0]].index));
+            let (label, label_trust) = (kept[b].label, kept[b].label_trust);
+            for c in trusted {
+                if kept[c].label_trust < label_trust {
+                    kept[c].label = label;
+                    kept[c].label_trust = label_trust;
+                }
+            }
+        }
+    }
+
+    // Kept boxes in source order, relabelled where a more trusted label won
+    let mut kept: Vec<Kept> = kept
+        .into_iter()
+        .zip(removed)
+        .filter_map(|(k, removed)| (!removed).then_some(k))
+        .collect();
+    kept.sort_by_key(|k| k.index);
+    dropped.sort_unstable();
+    let position = |index: usize| kept.binary_search_by_key(&index, |k| k.index).ok();
+    // A box dropped into one that was dropped in turn follows the chain
+    let replacement = |mut index: usize| loop {
+        if let Some(k) = position(index) {
+            return Some(k);
+        }
+        index = dropped.iter().find(|(i, _)| *i == index)?.1;
+    };
+    let fused = dropped
+        .iter()
+        .filter_map(|&(i, _)| {
+            let (source, e) = all[i];
+            Some((SourceId { source, id: e.id() }, replacement(i)?))
+        })
+        .collect();
+    let elements = kept
+        .iter()
+        .map(|k| {
+            let (source, e) = all[k.index];
+            let mut element = Element::from_box(e);
+            if k.label != element.label {
+                let (x1, y1, x2, y2) = e.bounds();
+                element = Element {
+                    confidence: e.confidence(),
+                    z_index: e.z_index(),
+                    group: e.group(),
+                    language: e.language(),
+                    ..Element::new(e.id(), x1, y1, x2, y2, k.label)
+                };
+            }
+            (SourceId { source, id: e.id() }, element)
+        })
+        .collect();
+    unique_ids(elements, fused, next_free_id(&all), config.duplicates)
+}
+
+/// Share of `inner`'s area inside `outer`
+fn inside_share<T: BoundingBox>(inner: &T, outer: &T) -> f32 {
+    let (x1, y1, x2, y2) = inner.bounds();
+    let (ox1, oy1, ox2, oy2) = outer.bounds();
+    let inside = (x2.min(ox2) - x1.max(ox1)).max(0.0) * (y2.min(oy2) - y1.max(oy1)).max(0.0);
+    let area = (x2 - x1) * (y2 - y1);
+    if area > 0.0 {
+        inside / area
+    } else {
+        0.0
+    }
+}
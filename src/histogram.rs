@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use crate::traits::BoundingBox;
 
 /// Build a horizontal projection histogram to find row gaps
@@ -8,22 +10,29 @@ pub fn build_horizontal_histogram<T: BoundingBox>(
     y_max: f32,
     resolution: usize,
 ) -> Vec<usize> {
-    let mut histogram = vec![0; resolution];
-    let bin_height = (y_max - y_min) / resolution as f32;
+    let mut histogram = Vec::new();
+    fill_horizontal_histogram(elements, y_min, y_max, resolution, &mut histogram);
+    histogram
+}
+
+/// [`build_horizontal_histogram`] into an existing buffer, reusing its allocation
+pub fn fill_horizontal_histogram<T: BoundingBox>(
+    elements: &[T],
+    y_min: f32,
+    y_max: f32,
+    resolution: usize,
+    histogram: &mut Vec<usize>,
+) {
+    histogram.clear();
+    histogram.resize(resolution, 0);
+    let bin_height = local_bin_size(y_min, y_max, resolution);
 
     for element in elements {
         let (_, y1, _, y2) = element.bounds();
-        let start_bin = ((y1 - y_min) / bin_height).floor().max(0.0) as usize;
-        let end_bin = ((y2 - y_min) / bin_height).ceil().min(resolution as f32) as usize;
-
-        for bin in start_bin..end_bin.min(resolution) {
-            if bin < histogram.len() {
-                histogram[bin] += 1;
-            }
+        for bin in covered_bins(y1, y2, y_min, bin_height, resolution) {
+            histogram[bin] += 1;
         }
     }
-
-    histogram
 }
 
 /// Build a vertical projection histogram to find column gaps
@@ -34,26 +43,54 @@ pub fn build_vertical_histogram<T: BoundingBox>(
     x_max: f32,
     resolution: usize,
 ) -> Vec<usize> {
-    let mut histogram = vec![0; resolution];
-    let bin_width = (x_max - x_min) / resolution as f32;
+    let mut histogram = Vec::new();
+    fill_vertical_histogram(elements, x_min, x_max, resolution, &mut histogram);
+    histogram
+}
+
+/// [`build_vertical_histogram`] into an existing buffer, reusing its allocation
+pub fn fill_vertical_histogram<T: BoundingBox>(
+    elements: &[T],
+    x_min: f32,
+    x_max: f32,
+    resolution: usize,
+    histogram: &mut Vec<usize>,
+) {
+    histogram.clear();
+    histogram.resize(resolution, 0);
+    let bin_width = local_bin_size(x_min, x_max, resolution);
 
     for element in elements {
         let (x1, _, x2, _) = element.bounds();
-        let start_bin = ((x1 - x_min) / bin_width).floor().max(0.0) as usize;
-        let end_bin = ((x2 - x_min) / bin_width).ceil().min(resolution as f32) as usize;
-
-        // TODO: Add bounds checking to prevent panic
-        // Change to: if bin < histogram.len() { histogram[bin] += 1; }
-
-        // TEMPORARY: Unsafe array access
-        for bin in start_bin..end_bin.min(resolution) {
-            if bin < histogram.len() {
-                histogram[bin] += 1;
-            }
+        for bin in covered_bins(x1, x2, x_min, bin_width, resolution) {
+            histogram[bin] += 1;
         }
     }
+}
 
-    histogram
+/// Size of one of `resolution` bins over `min..max`
+///
+/// Histograms are computed in the region's local frame, in f64: at coordinates in
+/// the 1e7 range (CAD units) f32 is only a unit precise, which would shift bin
+/// boundaries and cut positions by whole units.
+fn local_bin_size(min: f32, max: f32, resolution: usize) -> f64 {
+    (f64::from(max) - f64::from(min)) / resolution as f64
+}
+
+/// Bins covered by the span `v1..v2` of a region starting at `min`
+fn covered_bins(v1: f32, v2: f32, min: f32, bin_size: f64, resolution: usize) -> Range<usize> {
+    let start = ((f64::from(v1) - f64::from(min)) / bin_size)
+        .floor()
+        .max(0.0) as usize;
+    let end = ((f64::from(v2) - f64::from(min)) / bin_size)
+        .ceil()
+        .min(resolution as f64) as usize;
+    start.min(resolution)..end
+}
+
+/// Coordinate where `bin` of a histogram of `resolution` bins over `min..max` starts
+pub(crate) fn bin_coordinate(min: f32, max: f32, bin: usize, resolution: usize) -> f32 {
+    (f64::from(min) + bin as f64 * local_bin_size(min, max, resolution)) as f32
 }
 
 /// Find the largest gap in a histogram (consecutive bins with 0 count)
@@ -79,9 +116,9 @@ pub fn find_largest_gap(histogram: &[usize], min_gap_size: usize) -> Option<usiz
                 if let Some(start) = current_gap_start {
                     max_gap_center = Some(start + current_gap_size / 2);
                 }
-                current_gap_size = 0;
-                current_gap_start = None
             }
+            current_gap_size = 0;
+            current_gap_start = None
         }
     }
 
@@ -94,3 +131,66 @@ pub fn find_largest_gap(histogram: &[usize], min_gap_size: usize) -> Option<usiz
 
     max_gap_center
 }
+
+/// Count the gaps of at least `min_gap_size` empty bins between occupied bins
+///
+/// Empty runs at either end of the histogram (margins) are not gaps.
+pub fn count_gaps(histogram: &[usize], min_gap_size: usize) -> usize {
+    let mut gaps = 0;
+    let mut current_gap_size = 0;
+    let mut seen_occupied = false;
+    for &count in histogram {
+        if count == 0 {
+            current_gap_size += 1;
+        } else {
+            if seen_occupied && current_gap_size >= min_gap_size.max(1) {
+                gaps += 1;
+            }
+            seen_occupied = true;
+            current_gap_size = 0;
+        }
+    }
+    gaps
+}
+
+/// Find the best near-empty run in a histogram, tolerating bins covered by up to
+/// `max_occupancy` elements (boxes slightly overlapping a gutter)
+///
+/// Runs are penalized by their peak occupancy: the run with the fewest straddling
+/// elements wins, the longest among equals. Returns the center of that run.
+pub fn find_tolerant_gap(
+    histogram: &[usize],
+    min_gap_size: usize,
+    max_occupancy: usize,
+) -> Option<usize> {
+    // (peak occupancy, length, center) of the best run so far
+    let mut best: Option<(usize, usize, usize)> = None;
+    let mut run_start = None;
+    let mut run_peak = 0;
+
+    for i in 0..=histogram.len() {
+        let count = histogram.get(i).copied();
+        match (count, run_start) {
+            (Some(count), None) if count <= max_occupancy => {
+                run_start = Some(i);
+                run_peak = count;
+            }
+            (Some(count), Some(_)) if count <= max_occupancy => {
+                run_peak = run_peak.max(count);
+            }
+            (_, Some(start)) => {
+                let length = i - start;
+                let better = best.is_none_or(|(peak, best_length, _)| {
+                    run_peak < peak || (run_peak == peak && length > best_length)
+                });
+                if length >= min_gap_size && better {
+                    best = Some((run_peak, length, start + length / 2));
+                }
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    best.map(|(_, _, center)| center)
+}
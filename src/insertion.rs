@@ -0,0 +1,46 @@
+//! Order in which masked elements are inserted
+
+use crate::traits::BoundingBox;
+use crate::utils::compare_position;
+
+/// Order of insertions within a priority group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InsertionOrder {
+    /// Row by row, left to right within a row (the paper's order)
+    #[default]
+    Rows,
+    /// Column by column, topmost first within a column, so an element is inserted
+    /// after those above it; every insertion is then re-evaluated against the
+    /// complete order, letting an element anchor to one inserted after it
+    Dependency,
+}
+
+/// Sort `group` column by column, left to right, and top to bottom within a column
+///
+/// Columns are the connected runs of horizontally overlapping elements.
+pub(crate) fn sort_by_columns<T: BoundingBox>(group: &mut [T]) {
+    let mut extents: Vec<(f32, f32)> = group
+        .iter()
+        .map(|e| {
+            let (x1, _, x2, _) = e.bounds();
+            (x1, x2)
+        })
+        .collect();
+    extents.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut columns: Vec<(f32, f32)> = Vec::new();
+    for (x1, x2) in extents {
+        match columns.last_mut() {
+            Some(column) if x1 < column.1 => column.1 = column.1.max(x2),
+            _ => columns.push((x1, x2)),
+        }
+    }
+
+    let column = |e: &T| columns.partition_point(|c| c.0 <= e.bounds().0);
+    group.sort_by(|a, b| {
+        column(a)
+            .cmp(&column(b))
+            .then(a.bounds().1.total_cmp(&b.bounds().1))
+            .then(compare_position(a, b))
+    });
+}
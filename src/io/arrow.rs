@@ -0,0 +1,238 @@
+//! Arrow `RecordBatch` adapter for columnar pipelines (Spark, Polars, Parquet readers)
+//!
+//! A batch holds one page: `id`, `x1`, `y1`, `x2`, `y2` and an optional `label` column.
+//! Ids may be any integer type and coordinates any float or integer type; labels are
+//! class names (`Utf8`, `LargeUtf8`, `Utf8View`) or class ids (integers), mapped through a
+//! [`LabelMap`]. Without a `label` column every box gets the map's fallback label.
+
+use std::fmt;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use arrow_array::{Array, ArrayRef, RecordBatch, UInt64Array};
+use arrow_schema::DataType;
+
+use crate::core::XYCutPlusPlus;
+use crate::element::Element;
+use crate::labels::LabelMap;
+
+/// Failure reading a batch
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrowInputError {
+    /// A required column is absent
+    MissingColumn(&'static str),
+    /// A column has a type the adapter cannot read
+    ColumnType {
+        column: &'static str,
+        data_type: DataType,
+    },
+    /// A column holds a null
+    Null { column: &'static str, row: usize },
+    /// An id is negative or does not fit in `usize`
+    InvalidId { row: usize },
+}
+
+impl fmt::Display for ArrowInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingColumn(column) => write!(f, "missing column `{column}`"),
+            Self::ColumnType { column, data_type } => {
+                write!(f, "column `{column}` has unsupported type {data_type}")
+            }
+            Self::Null { column, row } => write!(f, "column `{column}` is null at row {row}"),
+            Self::InvalidId { row } => write!(f, "id at row {row} is not a valid id"),
+        }
+    }
+}
+
+impl std::error::Error for ArrowInputError {}
+
+/// Read the boxes of a batch, one [`Element`] per row
+pub fn read_record_batch(
+    batch: &RecordBatch,
+    labels: &LabelMap,
+) -> Result<Vec<Element>, ArrowInputError> {
+    let ids = integers(column(batch, "id")?, "id")?;
+    let x1 = floats(column(batch, "x1")?, "x1")?;
+    let y1 = floats(column(batch, "y1")?, "y1")?;
+    let x2 = floats(column(batch, "x2")?, "x2")?;
+    let y2 = floats(column(batch, "y2")?, "y2")?;
+    let label_column = batch.column_by_name("label");
+
+    (0..batch.num_rows())
+        .map(|row| {
+            let id = usize::try_from(ids[row]).map_err(|_| ArrowInputError::InvalidId { row })?;
+            let label = match label_column {
+                Some(array) => {
+                    if array.is_null(row) {
+                        return Err(ArrowInputError::Null {
+                            column: "label",
+                            row,
+                        });
+                    }
+                    match array.data_type() {
+                        DataType::Utf8 => labels.map_name(array.as_string::<i32>().value(row)),
+                        DataType::LargeUtf8 => labels.map_name(array.as_string::<i64>().value(row)),
+                        DataType::Utf8View => labels.map_name(array.as_string_view().value(row)),
+                        _ => {
+                            let class_id = integer(array, "label", row)?;
+                            usize::try_from(class_id)
+                                .map_or(labels.map_name(""), |id| labels.map_class_id(id))
+                        }
+                    }
+                }
+                None => labels.map_name(""),
+            };
+            Ok(Element::new(id, x1[row], y1[row], x2[row], y2[row], label))
+        })
+        .collect()
+}
+
+impl XYCutPlusPlus {
+    /// Order the boxes of a batch, returning their ids in reading order
+    ///
+    /// `bounds` is the page `(x_min, y_min, x_max, y_max)`. See [`read_record_batch`]
+    /// for the expected columns.
+    pub fn order_record_batch(
+        &self,
+        batch: &RecordBatch,
+        labels: &LabelMap,
+        bounds: (f32, f32, f32, f32),
+    ) -> Result<UInt64Array, ArrowInputError> {
+        let elements = read_record_batch(batch, labels)?;
+        let (x_min, y_min, x_max, y_max) = bounds;
+        let order = self.compute_order(&elements, x_min, y_min, x_max, y_max);
+        Ok(order.into_iter().map(|id| id as u64).collect())
+    }
+}
+
+fn column<'a>(batch: &'a RecordBatch, name: &'static str) -> Result<&'a ArrayRef, ArrowInputError> {
+    batch
+        .column_by_name(name)
+        .ok_or(ArrowInputError::MissingColumn(name))
+}
+
+fn check_nulls(array: &ArrayRef, column: &'static str) -> Result<(), ArrowInputError> {
+    match array
+        .logical_nulls()
+        .and_then(|nulls| (0..nulls.len()).find(|&row| nulls.is_null(row)))
+    {
+        Some(row) => Err(ArrowInputError::Null { column, row }),
+        None => Ok(()),
+    }
+}
+
+/// Values of an integer column, widened to `i128`
+fn integers(array: &ArrayRef, column: &'static str) -> Result<Vec<i128>, ArrowInputError> {
+    check_nulls(array, column)?;
+    (0..array.len())
+        .map(|row| integer(array, column, row))
+        .collect()
+}
+
+fn integer(array: &ArrayRef, column: &'static str, row: usize) -> Result<i128, ArrowInputError> {
+    Ok(match array.data_type() {
+        DataType::Int8 => array.as_primitive::<Int8Type>().value(row).into(),
+        DataType::Int16 => array.as_primitive::<Int16Type>().value(row).into(),
+        DataType::Int32 => array.as_primitive::<Int32Type>().value(row).into(),
+        DataType::Int64 => array.as_primitive::<Int64Type>().value(row).into(),
+        DataType::UInt8 => array.as_primitive::<UInt8Type>().value(row).into(),
+        DataType::UInt16 => array.as_primitive::<UInt16Type>().value(row).into(),
+        DataType::UInt32 => array.as_primitive::<UInt32Type>().value(row).into(),
+        DataType::UInt64 => array.as_primitive::<UInt64Type>().value(row).into(),
+        data_type => {
+            return Err(ArrowInputError::ColumnType {
+                column,
+                data_type: data_type.clone(),
+            })
+        }
+    })
+}
+
+/// Values of a coordinate column as `f32`
+fn floats(array: &ArrayRef, column: &'static str) -> Result<Vec<f32>, ArrowInputError> {
+    check_nulls(array, column)?;
+    Ok(match array.data_type() {
+        DataType::Float32 => array.as_primitive::<Float32Type>().values().to_vec(),
+        DataType::Float64 => array
+            .as_primitive::<Float64Type>()
+            .values()
+            .iter()
+            .map(|&v| v as f32)
+            .collect(),
+        _ => integers(array, column)?
+            .into_iter()
+            .map(|v| v as f32)
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow_array::{Float64Array, Int32Array, StringArray};
+
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::traits::SemanticLabel;
+
+    fn batch(label: Option<ArrayRef>) -> RecordBatch {
+        let mut columns: Vec<(&str, ArrayRef)> = vec![
+            ("id", Arc::new(Int32Array::from(vec![7, 3]))),
+            ("x1", Arc::new(Float64Array::from(vec![50.0, 50.0]))),
+            ("y1", Arc::new(Float64Array::from(vec![300.0, 50.0]))),
+            ("x2", Arc::new(Int32Array::from(vec![550, 550]))),
+            ("y2", Arc::new(Int32Array::from(vec![400, 100]))),
+        ];
+        columns.extend(label.map(|label| ("label", label)));
+        RecordBatch::try_from_iter(columns).unwrap()
+    }
+
+    #[test]
+    fn rows_become_elements() {
+        let labels = Arc::new(StringArray::from(vec!["Text", "Title"]));
+        let elements = read_record_batch(&batch(Some(labels)), &LabelMap::doclaynet()).unwrap();
+        assert_eq!(
+            elements,
+            vec![
+                Element::new(7, 50.0, 300.0, 550.0, 400.0, SemanticLabel::Regular),
+                Element::new(3, 50.0, 50.0, 550.0, 100.0, SemanticLabel::HorizontalTitle),
+            ]
+        );
+    }
+
+    #[test]
+    fn class_id_labels_and_order_output() {
+        let labels = Arc::new(Int32Array::from(vec![0, 1]));
+        let batch = batch(Some(labels));
+        let elements = read_record_batch(&batch, &LabelMap::layoutlmv3()).unwrap();
+        assert_eq!(elements[1].label, SemanticLabel::HorizontalTitle);
+
+        let order = XYCutPlusPlus::new(XYCutConfig::default())
+            .order_record_batch(&batch, &LabelMap::layoutlmv3(), (0.0, 0.0, 600.0, 500.0))
+            .unwrap();
+        assert_eq!(order.values().to_vec(), vec![3, 7]);
+    }
+
+    #[test]
+    fn missing_and_null_columns_are_errors() {
+        let no_id = batch(None).project(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(
+            read_record_batch(&no_id, &LabelMap::default()),
+            Err(ArrowInputError::MissingColumn("id"))
+        );
+
+        let labels = Arc::new(StringArray::from(vec![Some("text"), None]));
+        assert_eq!(
+            read_record_batch(&batch(Some(labels)), &LabelMap::default()),
+            Err(ArrowInputError::Null {
+                column: "label",
+                row: 1
+            })
+        );
+    }
+}
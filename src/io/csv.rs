@@ -0,0 +1,226 @@
+//! CSV boxes in, ordered ids and ranks out, for spreadsheet-based debugging
+//!
+//! The first line is a header naming the columns; `id`, `x1`, `y1`, `x2` and `y2` are
+//! required, `label` is optional and any other column is ignored. Fields may be quoted
+//! with `"` (doubling `""` inside quotes); quoted fields cannot span lines.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::element::Element;
+use crate::labels::LabelMap;
+use crate::traits::{BoundingBox, SemanticLabel};
+
+/// Failure reading a CSV file
+#[derive(Debug, Clone, PartialEq)]
+pub enum CsvError {
+    /// The header lacks a required column
+    MissingColumn(&'static str),
+    /// A row is malformed; `line` counts from 1 including the header
+    Row { line: usize, message: String },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingColumn(column) => write!(f, "missing column `{column}`"),
+            Self::Row { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+/// Read one page of boxes, one [`Element`] per non-empty row
+///
+/// A `label` is taken as a [`SemanticLabel`] name (`HorizontalTitle`, as written by
+/// [`write_elements_csv`]) when it is one, as a class id when it is a number, and as a
+/// class name otherwise; ids and names go through `labels`. Rows without a label get
+/// the map's fallback.
+pub fn read_csv(text: &str, labels: &LabelMap) -> Result<Vec<Element>, CsvError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((_, header)) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let header = split_row(header).map_err(|message| CsvError::Row { line: 1, message })?;
+    let find = |name: &'static str| header.iter().position(|column| column == name);
+    let required = |name: &'static str| find(name).ok_or(CsvError::MissingColumn(name));
+    let columns = [
+        required("id")?,
+        required("x1")?,
+        required("y1")?,
+        required("x2")?,
+        required("y2")?,
+    ];
+    let label_column = find("label");
+
+    lines
+        .map(|(index, row)| {
+            let line = index + 1;
+            let row_error = |message: String| CsvError::Row { line, message };
+            let fields = split_row(row).map_err(row_error)?;
+            let field = |column: usize| fields.get(column).map_or("", String::as_str);
+
+            let id = field(columns[0])
+                .parse::<usize>()
+                .map_err(|_| row_error(format!("id `{}` is not an id", field(columns[0]))))?;
+            let mut coords = [0.0; 4];
+            for (coord, &column) in coords.iter_mut().zip(&columns[1..]) {
+                *coord = field(column).parse::<f32>().map_err(|_| {
+                    row_error(format!(
+                        "`{}` is not a coordinate of {}",
+                        field(column),
+                        header[column]
+                    ))
+                })?;
+            }
+            let label = match label_column.map(field).unwrap_or_default() {
+                "" => labels.map_name(""),
+                name => semantic_label(name)
+                    .or_else(|| {
+                        name.parse()
+                            .ok()
+                            .map(|class_id| labels.map_class_id(class_id))
+                    })
+                    .unwrap_or_else(|| labels.map_name(name)),
+            };
+            let [x1, y1, x2, y2] = coords;
+            Ok(Element::new(id, x1, y1, x2, y2, label))
+        })
+        .collect()
+}
+
+/// Write an order as `rank,id` rows
+pub fn write_order_csv(order: &[usize]) -> String {
+    let mut csv = String::from("rank,id\n");
+    for (rank, id) in order.iter().enumerate() {
+        csv.push_str(&format!("{rank},{id}\n"));
+    }
+    csv
+}
+
+/// Write the page's boxes with their rank in `order`, readable back by [`read_csv`]
+///
+/// Columns are `id,x1,y1,x2,y2,label,rank`; `rank` is empty for elements left out of
+/// the order (noise, dropped background). Rows keep the order of `elements`, so sorting
+/// the sheet by `rank` shows the reading order.
+pub fn write_elements_csv<T: BoundingBox>(elements: &[T], order: &[usize]) -> String {
+    let ranks: HashMap<usize, usize> = order
+        .iter()
+        .enumerate()
+        .map(|(rank, &id)| (id, rank))
+        .collect();
+    let mut csv = String::from("id,x1,y1,x2,y2,label,rank\n");
+    for element in elements {
+        let (x1, y1, x2, y2) = element.bounds();
+        let rank = ranks
+            .get(&element.id())
+            .map_or(String::new(), |rank| rank.to_string());
+        csv.push_str(&format!(
+            "{},{x1},{y1},{x2},{y2},{:?},{rank}\n",
+            element.id(),
+            element.semantic_label()
+        ));
+    }
+    csv
+}
+
+fn semantic_label(name: &str) -> Option<SemanticLabel> {
+    Some(match name {
+        "CrossLayout" => SemanticLabel::CrossLayout,
+        "HorizontalTitle" => SemanticLabel::HorizontalTitle,
+        "VerticalTitle" => SemanticLabel::VerticalTitle,
+        "Vision" => SemanticLabel::Vision,
+        "Formula" => SemanticLabel::Formula,
+        "Regular" => SemanticLabel::Regular,
+        _ => return None,
+    })
+}
+
+/// Split a line into trimmed fields, unquoting quoted ones
+fn split_row(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        let mut field = String::new();
+        while chars.next_if(|c| *c == ' ' || *c == '\t').is_some() {}
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('"') if chars.next_if_eq(&'"').is_some() => field.push('"'),
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return Err("unterminated quoted field".to_string()),
+                }
+            }
+            while chars.next_if(|c| *c != ',').is_some() {}
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                field.push(c);
+            }
+        }
+        fields.push(field.trim().to_string());
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_a_minimal_sheet() {
+        let text = "page,id,x1,y1,x2,y2,label\n\
+                    1,0,50,300,550,400,\"section header\"\n\
+                    1,1, 50 ,50,550,100,Vision\n\
+                    \n\
+                    1,2,50,120,550,280,3\n\
+                    1,3,50,410,550,500,\n";
+        let elements = read_csv(text, &LabelMap::doclaynet()).unwrap();
+        assert_eq!(
+            elements,
+            vec![
+                Element::new(0, 50.0, 300.0, 550.0, 400.0, SemanticLabel::HorizontalTitle),
+                Element::new(1, 50.0, 50.0, 550.0, 100.0, SemanticLabel::Vision),
+                // Class id 3 of DocLayNet is `list-item`
+                Element::new(2, 50.0, 120.0, 550.0, 280.0, SemanticLabel::Regular),
+                Element::new(3, 50.0, 410.0, 550.0, 500.0, SemanticLabel::Regular),
+            ]
+        );
+    }
+
+    #[test]
+    fn written_elements_read_back() {
+        let elements = vec![
+            Element::new(4, 10.5, 20.0, 30.0, 40.25, SemanticLabel::Formula),
+            Element::new(9, 0.0, 0.0, 5.0, 5.0, SemanticLabel::Regular),
+        ];
+        let csv = write_elements_csv(&elements, &[9]);
+        assert!(csv.ends_with("4,10.5,20,30,40.25,Formula,\n9,0,0,5,5,Regular,0\n"));
+        assert_eq!(read_csv(&csv, &LabelMap::default()).unwrap(), elements);
+        assert_eq!(write_order_csv(&[9, 4]), "rank,id\n0,9\n1,4\n");
+    }
+
+    #[test]
+    fn malformed_rows_report_their_line() {
+        assert_eq!(
+            read_csv("id,x1,y1,x2\n", &LabelMap::default()),
+            Err(CsvError::MissingColumn("y2"))
+        );
+        let error = read_csv(
+            "id,x1,y1,x2,y2\n0,1,2,3,4\n1,1,two,3,4\n",
+            &LabelMap::default(),
+        );
+        assert!(matches!(error, Err(CsvError::Row { line: 3, .. })));
+        let error = read_csv(
+            "id,x1,y1,x2,y2,label\n0,1,2,3,4,\"open\n",
+            &LabelMap::default(),
+        );
+        assert!(matches!(error, Err(CsvError::Row { line: 2, .. })));
+    }
+}
@@ -0,0 +1,284 @@
+//! Google Document AI reader (`Document` JSON from OCR, Form and Layout processors)
+
+use serde_json::Value;
+
+use crate::element::Element;
+use crate::traits::{BoundingBox, SemanticLabel, TextContent};
+
+/// Which text layout of a Document AI page becomes elements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DocumentAiLevel {
+    #[default]
+    Blocks,
+    Paragraphs,
+    Lines,
+    Tokens,
+}
+
+impl DocumentAiLevel {
+    fn field(self) -> &'static str {
+        match self {
+            Self::Blocks => "blocks",
+            Self::Paragraphs => "paragraphs",
+            Self::Lines => "lines",
+            Self::Tokens => "tokens",
+        }
+    }
+}
+
+/// A layout box read from Document AI, with the text it anchors
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentAiElement {
+    pub element: Element,
+
+    /// Where the box came from: the text level's field (`blocks`, `paragraphs`, ...),
+    /// `table`, or the `type` of a visual element (`image`, `math_formula`, ...)
+    pub kind: String,
+
+    /// Text of the box's text anchor, whitespace collapsed
+    pub text: String,
+}
+
+/// One page of a Document AI `Document`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentAiPage {
+    /// Page bounds `(0, 0, width, height)` in the page's `dimension` units
+    pub bounds: (f32, f32, f32, f32),
+
+    /// Text boxes of the chosen level, then tables and visual elements; ids number
+    /// them from 0
+    pub elements: Vec<DocumentAiElement>,
+}
+
+/// Read every page of a Document AI `Document`, or of a process response wrapping
+/// one in `document`
+///
+/// Boxes come from `normalizedVertices` scaled by the page `dimension`, or from pixel
+/// `vertices` when only those are present; items without either are skipped. Text
+/// boxes are `Regular`; tables and visual elements are `Vision` (checkboxes stay
+/// `Regular`), and text boxes whose center lies inside a table are dropped so the
+/// table is ordered as one unit.
+pub fn read_document_ai(
+    json: &str,
+    level: DocumentAiLevel,
+) -> Result<Vec<DocumentAiPage>, serde_json::Error> {
+    let root: Value = serde_json::from_str(json)?;
+    let document = root.get("document").unwrap_or(&root);
+    let text = document["text"].as_str().unwrap_or_default();
+    // Text anchors index characters; map them to byte offsets once
+    let offsets: Vec<usize> = text
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain([text.len()])
+        .collect();
+
+    let pages = document["pages"].as_array().map_or(&[][..], Vec::as_slice);
+    Ok(pages
+        .iter()
+        .map(|page| {
+            let dimension = &page["dimension"];
+            let width = dimension["width"].as_f64().unwrap_or_default() as f32;
+            let height = dimension["height"].as_f64().unwrap_or_default() as f32;
+            let items = |field: &str| page[field].as_array().map_or(&[][..], Vec::as_slice);
+            let bbox = |item: &Value| bounding_box(&item["layout"]["boundingPoly"], width, height);
+
+            let mut elements: Vec<DocumentAiElement> = Vec::new();
+            let mut push = |(x1, y1, x2, y2), label, kind: &str, item: &Value| {
+                elements.push(DocumentAiElement {
+                    element: Element::new(elements.len(), x1, y1, x2, y2, label),
+                    kind: kind.to_string(),
+                    text: anchor_text(text, &offsets, &item["layout"]["textAnchor"]),
+                });
+            };
+            let tables: Vec<_> = items("tables").iter().filter_map(bbox).collect();
+            for item in items(level.field()) {
+                let Some(b) = bbox(item) else {
+                    continue;
+                };
+                let center = ((b.0 + b.2) / 2.0, (b.1 + b.3) / 2.0);
+                if tables.iter().any(|t| contains(*t, center)) {
+                    continue;
+                }
+                push(b, SemanticLabel::Regular, level.field(), item);
+            }
+            for item in items("tables") {
+                if let Some(b) = bbox(item) {
+                    push(b, SemanticLabel::Vision, "table", item);
+                }
+            }
+            for item in items("visualElements") {
+                if let Some(b) = bbox(item) {
+                    let kind = item["type"].as_str().unwrap_or("visual_element");
+                    let label = if kind.contains("checkbox") {
+                        SemanticLabel::Regular
+                    } else if kind.contains("formula") {
+                        SemanticLabel::Formula
+                    } else {
+                        SemanticLabel::Vision
+                    };
+                    push(b, label, kind, item);
+                }
+            }
+
+            DocumentAiPage {
+                bounds: (0.0, 0.0, width, height),
+                elements,
+            }
+        })
+        .collect())
+}
+
+/// Bounding rectangle of a `boundingPoly`
+fn bounding_box(poly: &Value, width: f32, height: f32) -> Option<(f32, f32, f32, f32)> {
+    let (vertices, scale) = match poly["normalizedVertices"].as_array() {
+        Some(vertices) if !vertices.is_empty() => (vertices, (width, height)),
+        _ => (poly["vertices"].as_array()?, (1.0, 1.0)),
+    };
+    // Document AI omits zero coordinates
+    let points: Vec<(f32, f32)> = vertices
+        .iter()
+        .map(|v| {
+            (
+                v["x"].as_f64().unwrap_or_default() as f32 * scale.0,
+                v["y"].as_f64().unwrap_or_default() as f32 * scale.1,
+            )
+        })
+        .collect();
+    if points.is_empty() {
+        return None;
+    }
+    Some(points.iter().fold(
+        (
+            f32::INFINITY,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NEG_INFINITY,
+        ),
+        |(x1, y1, x2, y2), &(x, y)| (x1.min(x), y1.min(y), x2.max(x), y2.max(y)),
+    ))
+}
+
+fn contains(bounds: (f32, f32, f32, f32), (x, y): (f32, f32)) -> bool {
+    let (x1, y1, x2, y2) = bounds;
+    x >= x1 && x <= x2 && y >= y1 && y <= y2
+}
+
+/// Text of a `textAnchor`'s segments, whitespace collapsed
+fn anchor_text(text: &str, offsets: &[usize], anchor: &Value) -> String {
+    // int64 indices are serialized as strings, and a zero start is omitted
+    let index = |value: &Value| {
+        value
+            .as_u64()
+            .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+            .unwrap_or_default() as usize
+    };
+    let segments = anchor["textSegments"]
+        .as_array()
+        .map_or(&[][..], Vec::as_slice);
+    let mut joined = String::new();
+    for segment in segments {
+        let start = index(&segment["startIndex"]).min(offsets.len() - 1);
+        let end = index(&segment["endIndex"]).clamp(start, offsets.len() - 1);
+        joined.push_str(&text[offsets[start]..offsets[end]]);
+        joined.push(' ');
+    }
+    joined.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl BoundingBox for DocumentAiElement {
+    fn id(&self) -> usize {
+        self.element.id()
+    }
+
+    fn center(&self) -> (f32, f32) {
+        self.element.center()
+    }
+
+    fn bounds(&self) -> (f32, f32, f32, f32) {
+        self.element.bounds()
+    }
+
+    fn iou(&self, other: &Self) -> f32 {
+        self.element.iou(&other.element)
+    }
+
+    fn should_mask(&self) -> bool {
+        self.element.should_mask()
+    }
+
+    fn semantic_label(&self) -> SemanticLabel {
+        self.element.semantic_label()
+    }
+}
+
+impl TextContent for DocumentAiElement {
+    fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = r#"{
+      "document": {
+        "text": "Café menu\nSoup 4\n",
+        "pages": [{
+          "dimension": {"width": 1000, "height": 2000},
+          "paragraphs": [
+            {"layout": {
+              "textAnchor": {"textSegments": [{"endIndex": "9"}]},
+              "boundingPoly": {"normalizedVertices": [{"y": 0.1}, {"x": 0.5, "y": 0.1}, {"x": 0.5, "y": 0.15}, {"y": 0.15}]}
+            }},
+            {"layout": {
+              "textAnchor": {"textSegments": [{"startIndex": "10", "endIndex": "17"}]},
+              "boundingPoly": {"normalizedVertices": [{"x": 0.1, "y": 0.5}, {"x": 0.3, "y": 0.55}]}
+            }},
+            {"layout": {"textAnchor": {}}}
+          ],
+          "tables": [
+            {"layout": {"boundingPoly": {"normalizedVertices": [{"y": 0.4}, {"x": 1, "y": 0.6}]}}}
+          ],
+          "visualElements": [
+            {"type": "math_formula", "layout": {"boundingPoly": {"vertices": [{"x": 100, "y": 1500}, {"x": 300, "y": 1600}]}}}
+          ]
+        }]
+      }
+    }"#;
+
+    #[test]
+    fn reads_text_tables_and_visual_elements() {
+        let pages = read_document_ai(RESPONSE, DocumentAiLevel::Paragraphs).unwrap();
+        assert_eq!(pages.len(), 1);
+        let page = &pages[0];
+        assert_eq!(page.bounds, (0.0, 0.0, 1000.0, 2000.0));
+
+        let summary: Vec<(&str, &str, SemanticLabel)> = page
+            .elements
+            .iter()
+            .map(|e| (e.kind.as_str(), e.text.as_str(), e.element.label))
+            .collect();
+        // The paragraph inside the table and the one without a box are left out
+        assert_eq!(
+            summary,
+            vec![
+                ("paragraphs", "Café menu", SemanticLabel::Regular),
+                ("table", "", SemanticLabel::Vision),
+                ("math_formula", "", SemanticLabel::Formula),
+            ]
+        );
+        assert_eq!(page.elements[0].bounds(), (0.0, 200.0, 500.0, 300.0));
+        assert_eq!(page.elements[2].bounds(), (100.0, 1500.0, 300.0, 1600.0));
+        let ids: Vec<usize> = page.elements.iter().map(|e| e.id()).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn empty_and_invalid_documents() {
+        assert!(read_document_ai("{}", DocumentAiLevel::Blocks)
+            .unwrap()
+            .is_empty());
+        assert!(read_document_ai("{", DocumentAiLevel::Blocks).is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! hOCR reader (Tesseract, OCRopus, Kraken and most OCR engines' HTML output)
+
+use crate::element::Element;
+use crate::traits::{BoundingBox, SemanticLabel, TextContent};
+
+/// Elements without an end tag
+const VOID_TAGS: &[&str] = &[
+    "area", "br", "col", "hr", "img", "input", "link", "meta", "wbr",
+];
+
+/// A layout box read from hOCR, with the text it contains
+#[derive(Debug, Clone, PartialEq)]
+pub struct HocrElement {
+    pub element: Element,
+
+    /// The element's hOCR class, e.g. `ocr_par` or `ocr_header`
+    pub class: String,
+
+    /// Text of the element with whitespace collapsed
+    pub text: String,
+}
+
+/// One `ocr_page` of an hOCR document
+#[derive(Debug, Clone, PartialEq)]
+pub struct HocrPage {
+    /// Page bounds `(x_min, y_min, x_max, y_max)` from the page's `bbox`
+    pub bounds: (f32, f32, f32, f32),
+
+    /// Boxes of the requested classes in document order; ids number them from 0
+    pub elements: Vec<HocrElement>,
+}
+
+/// Read every page of an hOCR document, keeping the elements whose class is one of
+/// `classes` (e.g. `&["ocr_par"]`, or `&["ocr_line", "ocr_header", "ocr_caption",
+/// "ocr_textfloat"]` for Tesseract lines)
+///
+/// Headers and titles (`ocr_header`, `ocr_title`, `ocr_chapter`, `ocr_section`,
+/// `ocr_subsection`) are labelled `HorizontalTitle`; images, tables and floats are
+/// `Vision`; everything else is `Regular`. Elements without a `bbox` are skipped.
+pub fn read_hocr(html: &str, classes: &[&str]) -> Vec<HocrPage> {
+    let mut pages: Vec<HocrPage> = Vec::new();
+    // Open tags, with the index of the element they started if it is being kept
+    let mut open: Vec<(String, Option<usize>)> = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        append_text(&mut pages, &open, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_lowercase();
+            if let Some(position) = open.iter().rposition(|(open_name, _)| *open_name == name) {
+                open.truncate(position);
+            }
+            continue;
+        }
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        let name: String = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let class = attribute(tag, "class").unwrap_or_default();
+        let bbox = attribute(tag, "title").and_then(|title| title_bbox(&title));
+        let own: Vec<&str> = class.split_whitespace().collect();
+
+        let mut kept = None;
+        if own.contains(&"ocr_page") {
+            pages.push(HocrPage {
+                bounds: bbox.unwrap_or_default(),
+                elements: Vec::new(),
+            });
+        } else if let (Some(class), Some((x1, y1, x2, y2)), Some(page)) = (
+            own.iter().find(|c| classes.contains(c)),
+            bbox,
+            pages.last_mut(),
+        ) {
+            let id = page.elements.len();
+            page.elements.push(HocrElement {
+                element: Element::new(id, x1, y1, x2, y2, label(&own)),
+                class: class.to_string(),
+                text: String::new(),
+            });
+            kept = Some(id);
+        }
+
+        if !tag.ends_with('/') && !VOID_TAGS.contains(&name.as_str()) {
+            open.push((name, kept));
+        }
+    }
+
+    for page in &mut pages {
+        for element in &mut page.elements {
+            element.text = element
+                .text
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+    }
+    pages
+}
+
+/// Add text to every kept element enclosing it, so a paragraph kept together with
+/// its lines holds their text too
+fn append_text(pages: &mut [HocrPage], open: &[(String, Option<usize>)], text: &str) {
+    let Some(page) = pages.last_mut() else {
+        return;
+    };
+    for id in open.iter().filter_map(|(_, kept)| *kept) {
+        let element = &mut page.elements[id];
+        element.text.push_str(&decode_entities(text));
+        // Tags separate words even without whitespace between them
+        element.text.push(' ');
+    }
+}
+
+fn label(classes: &[&str]) -> SemanticLabel {
+    let any = |names: &[&str]| classes.iter().any(|c| names.contains(c));
+    if any(&[
+        "ocr_header",
+        "ocr_title",
+        "ocr_chapter",
+        "ocr_section",
+        "ocr_subsection",
+    ]) {
+        SemanticLabel::HorizontalTitle
+    } else if any(&[
+        "ocr_photo",
+        "ocr_image",
+        "ocr_linedrawing",
+        "ocr_table",
+        "ocr_float",
+    ]) {
+        SemanticLabel::Vision
+    } else if any(&["ocr_math", "ocr_chem"]) {
+        SemanticLabel::Formula
+    } else {
+        SemanticLabel::Regular
+    }
+}
+
+/// Value of the attribute `name` in the inside of a start tag
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let mut rest = tag;
+    while let Some(position) = rest.find(name) {
+        let before = rest[..position].chars().last();
+        let after = rest[position + name.len()..].trim_start();
+        rest = &rest[position + name.len()..];
+        if !before.is_some_and(char::is_whitespace) {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let quote = value.chars().next()?;
+        let value = if quote == '"' || quote == '\'' {
+            let value = &value[1..];
+            &value[..value.find(quote)?]
+        } else {
+            value
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+        };
+        return Some(decode_entities(value));
+    }
+    None
+}
+
+/// The `bbox x1 y1 x2 y2` property of an hOCR `title`
+fn title_bbox(title: &str) -> Option<(f32, f32, f32, f32)> {
+    let property = title
+        .split(';')
+        .map(str::trim)
+        .find_map(|property| property.strip_prefix("bbox "))?;
+    let coords: Vec<f32> = property
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    match coords[..] {
+        [x1, y1, x2, y2] => Some((x1, y1, x2, y2)),
+        _ => None,
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest[1..].find(';').map(|end| &rest[1..end + 1]);
+        let character = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let code = entity.strip_prefix('#')?;
+                let code = match code.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => code.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (entity, character) {
+            (Some(entity), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+impl BoundingBox for HocrElement {
+    fn id(&self) -> usize {
+        self.element.id()
+    }
+
+    fn center(&self) -> (f32, f32) {
+        self.element.center()
+    }
+
+    fn bounds(&self) -> (f32, f32, f32, f32) {
+        self.element.bounds()
+    }
+
+    fn iou(&self, other: &Self) -> f32 {
+        self.element.iou(&other.element)
+    }
+
+    fn should_mask(&self) -> bool {
+        self.element.should_mask()
+    }
+
+    fn semantic_label(&self) -> SemanticLabel {
+        self.element.semantic_label()
+    }
+}
+
+impl TextContent for HocrElement {
+    fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOCR: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html><body>
+  <div class='ocr_page' id='page_1' title='image "scan.png"; bbox 0 0 1000 1400; ppageno 0'>
+    <!-- <span class='ocr_line' title='bbox 1 1 2 2'>commented out</span> -->
+    <h1 class='ocr_header' title="bbox 100 50 900 120">Fish &amp; Chips</h1>
+    <p class='ocr_par' title='bbox 100 200 900 400'>
+      <span class='ocr_line' title='bbox 100 200 900 240; baseline 0 -8'>
+        <span class='ocrx_word' title='bbox 100 200 300 240; x_wconf 96'>Hello</span><span class='ocrx_word' title='bbox 310 200 500 240'>world</span>
+      </span>
+      <span class='ocr_line' title='bbox 100 250 900 290'>caf&#xe9;<br/>&lt;3</span>
+    </p>
+    <div class='ocr_float' title='bbox 100 500 900 900'><img src="a.png"></div>
+    <p class='ocr_par'>no box</p>
+  </div>
+  <div class='ocr_page' title='bbox 0 0 500 700'>
+    <p class='ocr_par' title='bbox 10 10 490 100'>Second page</p>
+  </div>
+</body></html>"#;
+
+    #[test]
+    fn reads_pages_boxes_and_text() {
+        let pages = read_hocr(HOCR, &["ocr_header", "ocr_par", "ocr_float"]);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].bounds, (0.0, 0.0, 1000.0, 1400.0));
+
+        let summary: Vec<(&str, &str, SemanticLabel)> = pages[0]
+            .elements
+            .iter()
+            .map(|e| (e.class.as_str(), e.text.as_str(), e.element.label))
+            .collect();
+        // The paragraph without a bbox is skipped
+        assert_eq!(
+            summary,
+            vec![
+                ("ocr_header", "Fish & Chips", SemanticLabel::HorizontalTitle),
+                ("ocr_par", "Hello world café <3", SemanticLabel::Regular),
+                ("ocr_float", "", SemanticLabel::Vision),
+            ]
+        );
+        assert_eq!(pages[0].elements[1].bounds(), (100.0, 200.0, 900.0, 400.0));
+
+        assert_eq!(pages[1].bounds, (0.0, 0.0, 500.0, 700.0));
+        assert_eq!(pages[1].elements[0].id(), 0);
+        assert_eq!(pages[1].elements[0].text, "Second page");
+    }
+
+    #[test]
+    fn nested_classes_are_all_kept() {
+        let pages = read_hocr(HOCR, &["ocr_par", "ocr_line"]);
+        let texts: Vec<&str> = pages[0].elements.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["Hello world café <3", "Hello world", "café <3"]);
+        let ids: Vec<usize> = pages[0].elements.iter().map(|e| e.id()).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn title_bbox_needs_four_coordinates() {
+        assert_eq!(
+            title_bbox("image \"a.png\"; bbox 1 2 3 4; ppageno 0"),
+            Some((1.0, 2.0, 3.0, 4.0))
+        );
+        assert_eq!(title_bbox("bbox 1 2 3"), None);
+        assert_eq!(title_bbox("x_wconf 90"), None);
+    }
+
+    #[test]
+    fn unknown_entities_are_kept_verbatim() {
+        assert_eq!(
+            decode_entities("a &bogus; b & c &#65;"),
+            "a &bogus; b & c A"
+        );
+    }
+}
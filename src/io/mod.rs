@@ -0,0 +1,14 @@
+//! Adapters reading layouts from OCR and annotation tool formats
+//!
+//! Each adapter sits behind its own `io-*` cargo feature.
+
+#[cfg(feature = "io-arrow")]
+pub mod arrow;
+#[cfg(feature = "io-csv")]
+pub mod csv;
+#[cfg(feature = "io-gdocai")]
+pub mod gdocai;
+#[cfg(feature = "io-hocr")]
+pub mod hocr;
+#[cfg(feature = "io-ndarray")]
+pub mod ndarray;
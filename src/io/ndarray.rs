@@ -0,0 +1,123 @@
+//! `ndarray` input for boxes coming straight out of a detector's tensors
+//!
+//! Boxes are the rows of an `(n, 4)` matrix `[x1, y1, x2, y2]` with one label per row;
+//! row `i` becomes element id `i`, so the returned order indexes the matrix.
+
+use std::fmt;
+
+use ndarray::ArrayView2;
+
+use crate::core::XYCutPlusPlus;
+use crate::element::Element;
+use crate::traits::SemanticLabel;
+
+/// Boxes and labels whose shapes do not fit together
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArrayInputError {
+    /// The box matrix does not have 4 columns
+    Columns(usize),
+    /// The number of labels differs from the number of boxes
+    Labels { boxes: usize, labels: usize },
+}
+
+impl fmt::Display for ArrayInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Columns(columns) => write!(f, "expected 4 box columns, got {columns}"),
+            Self::Labels { boxes, labels } => write!(f, "{boxes} boxes but {labels} labels"),
+        }
+    }
+}
+
+impl std::error::Error for ArrayInputError {}
+
+/// Read the rows of `boxes` as elements numbered by row
+pub fn read_array(
+    boxes: ArrayView2<'_, f32>,
+    labels: &[SemanticLabel],
+) -> Result<Vec<Element>, ArrayInputError> {
+    let (rows, columns) = boxes.dim();
+    if columns != 4 {
+        return Err(ArrayInputError::Columns(columns));
+    }
+    if labels.len() != rows {
+        return Err(ArrayInputError::Labels {
+            boxes: rows,
+            labels: labels.len(),
+        });
+    }
+    Ok(boxes
+        .rows()
+        .into_iter()
+        .zip(labels)
+        .enumerate()
+        .map(|(id, (row, &label))| Element::new(id, row[0], row[1], row[2], row[3], label))
+        .collect())
+}
+
+impl XYCutPlusPlus {
+    /// Order the rows of an `(n, 4)` box matrix, returning row indices in reading order
+    ///
+    /// Accepts any view, so `array.view()` of an owned `Array2<f32>` and row slices of
+    /// a larger batch both work without the caller building per-box structs.
+    pub fn compute_order_array(
+        &self,
+        boxes: ArrayView2<'_, f32>,
+        labels: &[SemanticLabel],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Result<Vec<usize>, ArrayInputError> {
+        let elements = read_array(boxes, labels)?;
+        Ok(self.compute_order(&elements, x_min, y_min, x_max, y_max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, s};
+
+    use super::*;
+    use crate::core::XYCutConfig;
+
+    #[test]
+    fn rows_become_elements_numbered_by_row() {
+        let boxes = array![[50.0, 300.0, 550.0, 400.0], [50.0, 50.0, 550.0, 100.0]];
+        let labels = [SemanticLabel::Regular, SemanticLabel::HorizontalTitle];
+        assert_eq!(
+            read_array(boxes.view(), &labels).unwrap(),
+            vec![
+                Element::new(0, 50.0, 300.0, 550.0, 400.0, SemanticLabel::Regular),
+                Element::new(1, 50.0, 50.0, 550.0, 100.0, SemanticLabel::HorizontalTitle),
+            ]
+        );
+
+        let order = XYCutPlusPlus::new(XYCutConfig::default())
+            .compute_order_array(boxes.view(), &labels, 0.0, 0.0, 600.0, 500.0)
+            .unwrap();
+        assert_eq!(order, vec![1, 0]);
+    }
+
+    #[test]
+    fn shapes_must_fit_together() {
+        let boxes = array![[0.0, 0.0, 1.0, 1.0, 0.9], [0.0, 2.0, 1.0, 3.0, 0.8]];
+        let labels = [SemanticLabel::Regular; 2];
+        assert_eq!(
+            read_array(boxes.view(), &labels),
+            Err(ArrayInputError::Columns(5))
+        );
+        // A column slice of the detector output is accepted as is
+        assert_eq!(
+            read_array(boxes.slice(s![.., ..4]), &labels).unwrap().len(),
+            2
+        );
+        assert_eq!(
+            read_array(boxes.slice(s![.., ..4]), &labels[..1]),
+            Err(ArrayInputError::Labels {
+                boxes: 2,
+                labels: 1
+            })
+        );
+    }
+}
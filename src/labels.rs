@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use crate::traits::SemanticLabel;
+
+/// Converts detector-specific class names or class ids into [`SemanticLabel`]s
+///
+/// Names are matched case-insensitively with `-` and spaces treated as `_`,
+/// so "Section-header", "section header" and "SECTION_HEADER" are the same class.
+/// Unknown classes map to the fallback label (`Regular` for the presets).
+#[derive(Debug, Clone)]
+pub struct LabelMap {
+    by_name: HashMap<String, SemanticLabel>,
+    by_class_id: HashMap<usize, SemanticLabel>,
+    fallback: SemanticLabel,
+}
+
+impl Default for LabelMap {
+    fn default() -> Self {
+        Self::new(SemanticLabel::Regular)
+    }
+}
+
+impl LabelMap {
+    /// Create an empty map returning `fallback` for every class
+    pub fn new(fallback: SemanticLabel) -> Self {
+        Self {
+            by_name: HashMap::new(),
+            by_class_id: HashMap::new(),
+            fallback,
+        }
+    }
+
+    /// Map a class name to `label`
+    pub fn with_name(mut self, name: &str, label: SemanticLabel) -> Self {
+        self.insert_name(name, label);
+        self
+    }
+
+    /// Map a numeric class id to `label`
+    pub fn with_class_id(mut self, class_id: usize, label: SemanticLabel) -> Self {
+        self.insert_class_id(class_id, label);
+        self
+    }
+
+    /// Map a class name to `label`, replacing any previous mapping
+    pub fn insert_name(&mut self, name: &str, label: SemanticLabel) {
+        self.by_name.insert(normalize(name), label);
+    }
+
+    /// Map a numeric class id to `label`, replacing any previous mapping
+    pub fn insert_class_id(&mut self, class_id: usize, label: SemanticLabel) {
+        self.by_class_id.insert(class_id, label);
+    }
+
+    /// Look up a class name
+    pub fn map_name(&self, name: &str) -> SemanticLabel {
+        self.by_name
+            .get(&normalize(name))
+            .copied()
+            .unwrap_or(self.fallback)
+    }
+
+    /// Look up a numeric class id
+    pub fn map_class_id(&self, class_id: usize) -> SemanticLabel {
+        self.by_class_id
+            .get(&class_id)
+            .copied()
+            .unwrap_or(self.fallback)
+    }
+
+    /// Build a map from an ordered class list: index `i` is class id `i`
+    pub fn from_classes(classes: &[(&str, SemanticLabel)]) -> Self {
+        let mut map = Self::default();
+        for (class_id, (name, label)) in classes.iter().enumerate() {
+            map.insert_name(name, *label);
+            map.insert_class_id(class_id, *label);
+        }
+        map
+    }
+
+    /// PP-DocLayout (PaddleX, 23 classes)
+    pub fn pp_doclayout() -> Self {
+        use SemanticLabel::*;
+        Self::from_classes(&[
+            ("paragraph_title", HorizontalTitle),
+            ("image", Vision),
+            ("text", Regular),
+            ("number", Regular),
+            ("abstract", Regular),
+            ("content", Regular),
+            ("figure_title", Regular),
+            ("formula", Formula),
+            ("table", Vision),
+            ("table_title", Regular),
+            ("reference", Regular),
+            ("doc_title", HorizontalTitle),
+            ("footnote", Regular),
+            ("header", Regular),
+            ("algorithm", Vision),
+            ("footer", Regular),
+            ("seal", Vision),
+            ("chart_title", Regular),
+            ("chart", Vision),
+            ("formula_number", Regular),
+            ("header_image", Vision),
+            ("footer_image", Vision),
+            ("aside_text", Regular),
+        ])
+    }
+
+    /// DocLayout-YOLO (DocStructBench, 10 classes)
+    pub fn doclayout_yolo() -> Self {
+        use SemanticLabel::*;
+        Self::from_classes(&[
+            ("title", HorizontalTitle),
+            ("plain text", Regular),
+            ("abandon", Regular),
+            ("figure", Vision),
+            ("figure_caption", Regular),
+            ("table", Vision),
+            ("table_caption", Regular),
+            ("table_footnote", Regular),
+            ("isolate_formula", Formula),
+            ("formula_caption", Regular),
+        ])
+    }
+
+    /// PubLayNet classes as used by LayoutLMv3 layout fine-tunes (5 classes)
+    pub fn layoutlmv3() -> Self {
+        use SemanticLabel::*;
+        Self::from_classes(&[
+            ("text", Regular),
+            ("title", HorizontalTitle),
+            ("list", Regular),
+            ("table", Vision),
+            ("figure", Vision),
+        ])
+    }
+
+    /// OmniDocBench layout annotation categories (`category_type`)
+    pub fn omnidocbench() -> Self {
+        use SemanticLabel::*;
+        Self::from_classes(&[
+            ("title", HorizontalTitle),
+            ("text_block", Regular),
+            ("figure", Vision),
+            ("figure_caption", Regular),
+            ("figure_footnote", Regular),
+            ("table", Vision),
+            ("table_caption", Regular),
+            ("table_footnote", Regular),
+            ("equation_isolated", Formula),
+            ("equation_caption", Regular),
+            ("header", Regular),
+            ("footer", Regular),
+            ("page_number", Regular),
+            ("page_footnote", Regular),
+            ("abandon", Regular),
+            ("code_txt", Regular),
+            ("code_txt_caption", Regular),
+            ("reference", Regular),
+        ])
+    }
+
+    /// DocLayNet (11 classes)
+    pub fn doclaynet() -> Self {
+        use SemanticLabel::*;
+        Self::from_classes(&[
+            ("caption", Regular),
+            ("footnote", Regular),
+            ("formula", Formula),
+            ("list-item", Regular),
+            ("page-footer", Regular),
+            ("page-header", Regular),
+            ("picture", Vision),
+            ("section-header", HorizontalTitle),
+            ("table", Vision),
+            ("text", Regular),
+            ("title", HorizontalTitle),
+        ])
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.trim()
+        .chars()
+        .map(|c| match c {
+            '-' | ' ' => '_',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_and_class_ids_round_trip() {
+        let map = LabelMap::new(SemanticLabel::Regular)
+            .with_name("Figure", SemanticLabel::Vision)
+            .with_class_id(3, SemanticLabel::HorizontalTitle);
+
+        assert_eq!(map.map_name("Figure"), SemanticLabel::Vision);
+        assert_eq!(map.map_class_id(3), SemanticLabel::HorizontalTitle);
+        // Unknown classes take the fallback
+        assert_eq!(map.map_name("caption"), SemanticLabel::Regular);
+        assert_eq!(map.map_class_id(4), SemanticLabel::Regular);
+    }
+
+    #[test]
+    fn names_match_regardless_of_case_and_separators() {
+        let map = LabelMap::doclaynet();
+        for name in [
+            "Section-header",
+            "section header",
+            "SECTION_HEADER",
+            " section-header ",
+        ] {
+            assert_eq!(map.map_name(name), SemanticLabel::HorizontalTitle, "{name}");
+        }
+    }
+
+    #[test]
+    fn preset_class_ids_follow_the_class_list() {
+        let map = LabelMap::doclayout_yolo();
+        assert_eq!(map.map_class_id(0), SemanticLabel::HorizontalTitle);
+        assert_eq!(map.map_class_id(3), SemanticLabel::Vision);
+        assert_eq!(map.map_class_id(8), SemanticLabel::Formula);
+        assert_eq!(map.map_class_id(10), SemanticLabel::Regular);
+    }
+}
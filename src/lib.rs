@@ -7,21 +7,86 @@
 //! Youmeng Li*, liyoumeng@tju.edu.cn
 //! Jizeng Wei, weijizeng@tju.edu.cn
 
+pub mod ambiguity;
+pub mod audit;
+pub mod baselines;
+mod batch;
+pub mod cache;
+pub mod chunking;
+pub mod columns;
+pub mod confidence;
 pub mod core;
+pub mod corpus;
+pub mod density;
+pub mod document;
+#[cfg(feature = "dump")]
+mod dump;
+pub mod element;
+pub mod eval;
+pub mod export;
+pub mod fallback;
+mod fixed;
+pub mod formula;
+pub mod fusion;
 pub mod histogram;
+pub mod insertion;
+pub mod io;
+pub mod labels;
+pub mod marginalia;
 pub mod matching;
+pub mod modes;
+pub mod observer;
+pub mod paragraph;
+#[cfg(feature = "async")]
+pub mod pipeline;
+pub mod precedence;
+pub mod preference;
+pub mod preprocess;
+pub mod profile;
+pub mod repair;
+pub mod report;
+pub mod sources;
+mod stream;
+#[cfg(test)]
+mod testing;
+pub mod text;
+mod trace;
 pub mod traits;
+pub mod tuning;
+pub mod unanchored;
 pub mod utils;
+pub mod validate;
+#[cfg(feature = "viz-svg")]
+pub mod viz;
+mod workspace;
+pub mod zones;
 
-pub use core::{XYCutPlusPlus, XYCutConfig};
-pub use traits::BoundingBox;
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn it_works() {
-        // TODO: Add real tests
-    }
-}
+pub use ambiguity::{Ambiguity, AmbiguityKind};
+pub use audit::{AuditReport, PageAudit};
+pub use batch::PageInput;
+pub use cache::OrderCache;
+pub use columns::ColumnHints;
+pub use confidence::Transition;
+pub use core::{order, Algorithm, LabelPriorities, SplitAssignment, XYCutConfig, XYCutPlusPlus};
+pub use density::DensityEstimator;
+pub use document::{DocumentModel, PageZone, StrippedOrders, ZonedElement};
+pub use element::Element;
+pub use fallback::{FallbackSort, RowGrouping};
+pub use formula::FormulaHandling;
+pub use fusion::{fuse, Detections, FusionConfig};
+pub use insertion::InsertionOrder;
+pub use labels::LabelMap;
+pub use marginalia::MarginaliaPolicy;
+pub use observer::OrderingObserver;
+pub use paragraph::{group_paragraphs, group_paragraphs_with_text, ParagraphConfig};
+pub use preference::{CutPreference, RegionStats};
+pub use preprocess::{NoiseFilter, PageOrientation};
+pub use profile::ProfileReport;
+pub use report::{OrderingReport, ALGORITHM_VERSION};
+pub use sources::{merge_sources, DuplicateIdError, DuplicateIds, MergedSources, SourceId};
+pub use traits::{BoundingBox, ReadingOrder, SemanticLabel, TextContent};
+pub use unanchored::{Unanchored, UnanchoredError, UnanchoredPolicy, UnanchoredReason};
+pub use utils::{Continuity, DistanceWeights};
+pub use validate::{validate_order, OrderValidationError};
+pub use workspace::Workspace;
+pub use zones::Zone;
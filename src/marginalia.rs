@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use crate::paragraph::{group_paragraphs, ParagraphConfig};
+use crate::traits::BoundingBox;
+use crate::utils::{compare_position, compute_median_width};
+
+/// Maximum width of a margin note relative to the median element width
+const MARGIN_NOTE_WIDTH_RATIO: f32 = 0.6;
+
+/// How side notes found in the page margins are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarginaliaPolicy {
+    /// No special handling: marginalia are cut and ordered like any other element
+    #[default]
+    Inline,
+    /// Right after the paragraph they sit next to
+    AfterParagraph,
+    /// After the whole page, top to bottom
+    AfterPage,
+    /// Dropped from the order
+    Exclude,
+}
+
+/// Detect side notes: narrow unmasked elements lying entirely outside the
+/// horizontal extent of the main text block
+///
+/// The main text block spans every unmasked element wider than
+/// 0.6 times the median width.
+pub fn detect_marginalia<T: BoundingBox>(elements: &[T]) -> Vec<usize> {
+    let text: Vec<T> = elements
+        .iter()
+        .filter(|e| !e.should_mask())
+        .cloned()
+        .collect();
+    let median_width = compute_median_width(&text);
+    let narrow = |e: &T| {
+        let (x1, _, x2, _) = e.bounds();
+        x2 - x1 <= MARGIN_NOTE_WIDTH_RATIO * median_width
+    };
+
+    let (main_x1, main_x2) = text.iter().filter(|e| !narrow(e)).map(|e| e.bounds()).fold(
+        (f32::INFINITY, f32::NEG_INFINITY),
+        |(a, b), (x1, _, x2, _)| (a.min(x1), b.max(x2)),
+    );
+    if main_x1 > main_x2 {
+        return Vec::new();
+    }
+
+    text.iter()
+        .filter(|e| narrow(e))
+        .filter(|e| {
+            let (x1, _, x2, _) = e.bounds();
+            x2 <= main_x1 || x1 >= main_x2
+        })
+        .map(|e| e.id())
+        .collect()
+}
+
+/// Merge the marginalia `notes` back into the `order` of the remaining elements
+///
+/// Never called with [`MarginaliaPolicy::Inline`]: inline notes are not taken out of the flow.
+pub(crate) fn place_marginalia<T: BoundingBox>(
+    elements: &[T],
+    order: Vec<usize>,
+    notes: &[usize],
+    policy: MarginaliaPolicy,
+) -> Vec<usize> {
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+
+    let mut notes: Vec<&T> = notes
+        .iter()
+        .filter_map(|id| by_id.get(id).copied())
+        .collect();
+    notes.sort_by(|a, b| compare_position(*a, *b));
+
+    match policy {
+        MarginaliaPolicy::Exclude => order,
+        MarginaliaPolicy::Inline => unreachable!("inline marginalia stay in the main flow"),
+        MarginaliaPolicy::AfterPage => {
+            let mut result = order;
+            result.extend(notes.iter().map(|note| note.id()));
+            result
+        }
+        MarginaliaPolicy::AfterParagraph => {
+            let body: Vec<T> = order.iter().map(|id| by_id[id].clone()).collect();
+            let paragraphs = group_paragraphs(&body, &order, &ParagraphConfig::default());
+
+            // Vertical extent of every paragraph
+            let spans: Vec<(f32, f32)> = paragraphs
+                .iter()
+                .map(|ids| {
+                    ids.iter().map(|id| by_id[id].bounds()).fold(
+                        (f32::INFINITY, f32::NEG_INFINITY),
+                        |(a, b), (_, y1, _, y2)| (a.min(y1), b.max(y2)),
+                    )
+                })
+                .collect();
+
+            let mut attached: Vec<Vec<usize>> = vec![Vec::new(); paragraphs.len()];
+            let mut leading = Vec::new();
+            for note in notes {
+                match adjacent_paragraph(note, &spans) {
+                    Some(index) => attached[index].push(note.id()),
+                    None => leading.push(note.id()),
+                }
+            }
+
+            let mut result = leading;
+            for (paragraph, notes) in paragraphs.into_iter().zip(attached) {
+                result.extend(paragraph);
+                result.extend(notes);
+            }
+            result
+        }
+    }
+}
+
+/// Paragraph overlapping the note the most vertically, else the last one starting above it
+fn adjacent_paragraph<T: BoundingBox>(note: &T, spans: &[(f32, f32)]) -> Option<usize> {
+    let (_, ny1, _, ny2) = note.bounds();
+
+    let best_overlap = spans
+        .iter()
+        .enumerate()
+        .map(|(i, &(y1, y2))| (i, ny2.min(y2) - ny1.max(y1)))
+        .filter(|&(_, overlap)| overlap > 0.0)
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    best_overlap
+        .map(|(i, _)| i)
+        .or_else(|| spans.iter().rposition(|&(y1, _)| y1 <= ny1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{XYCutConfig, XYCutPlusPlus};
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    /// Two body paragraphs with a side note level with the first one
+    fn page() -> Vec<Element> {
+        vec![
+            Element::new(0, 100.0, 100.0, 700.0, 300.0, SemanticLabel::Regular),
+            Element::new(1, 100.0, 500.0, 700.0, 700.0, SemanticLabel::Regular),
+            Element::new(2, 720.0, 120.0, 780.0, 200.0, SemanticLabel::Regular),
+            // Short last line inside the text block: narrow, but not a margin note
+            Element::new(3, 100.0, 710.0, 300.0, 730.0, SemanticLabel::Regular),
+        ]
+    }
+
+    fn order(policy: MarginaliaPolicy) -> Vec<usize> {
+        let config = XYCutConfig {
+            marginalia: policy,
+            ..XYCutConfig::default()
+        };
+        XYCutPlusPlus::new(config).compute_order(&page(), 0.0, 0.0, 800.0, 1000.0)
+    }
+
+    #[test]
+    fn only_narrow_boxes_outside_the_text_block_are_marginalia() {
+        assert_eq!(detect_marginalia(&page()), vec![2]);
+    }
+
+    #[test]
+    fn notes_follow_the_paragraph_they_sit_next_to() {
+        assert_eq!(order(MarginaliaPolicy::AfterParagraph), vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn notes_go_after_the_page_or_are_dropped() {
+        assert_eq!(order(MarginaliaPolicy::AfterPage), vec![0, 1, 3, 2]);
+        assert_eq!(order(MarginaliaPolicy::Exclude), vec![0, 1, 3]);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::traits::BoundingBox;
+use crate::core::XYCutConfig;
+use crate::traits::{BoundingBox, SemanticLabel};
 use crate::utils::{compute_median_width, count_overlap, distance_to_nearest_text};
 
 /// Isolation threshold in pixels for Equation 3.
@@ -10,6 +11,10 @@ use crate::utils::{compute_median_width, count_overlap, distance_to_nearest_text
 /// Paper reference: Section 3.1, Equation 3
 const ISOLATION_THRESHOLD_PX: f32 = 50.0;
 
+/// Width, relative to the median element width, above which an element overlapping
+/// two others is treated as cross-layout
+pub(crate) const CROSS_LAYOUT_WIDTH_RATIO: f32 = 1.3;
+
 /// Result of pre-mask processing
 #[derive(Debug)]
 pub struct MaskPartition<T: BoundingBox> {
@@ -19,22 +24,28 @@ pub struct MaskPartition<T: BoundingBox> {
 
 /// Partition elements into masked titles, figures, tables and regular text
 /// This is Step 1 of XY-Cut++: Pre-mask processing
-// TODO: Add page_width parameter to function signature
+///
+/// An element's `should_mask` is only trusted when its detector confidence (if any)
+/// reaches `config.mask_confidence_threshold`; low-confidence elements stay regular.
+/// `page` is the `(x_min, y_min, x_max, y_max)` area used for the centrality check.
 pub fn partition_by_mask<T: BoundingBox>(
     elements: &[T],
-    page_width: f32,
-    page_height: f32,
+    page: (f32, f32, f32, f32),
+    config: &XYCutConfig,
 ) -> MaskPartition<T> {
     let mut masked_elements = Vec::new();
     let mut regular_elements = Vec::new();
 
     let median_width = compute_median_width(elements);
-    let threshold = 1.3 * median_width;
+    let threshold = CROSS_LAYOUT_WIDTH_RATIO * median_width;
 
     // Equation 3 - geometric pre-segmentation
     // Calculate page center
-    let page_center_x = page_width / 2.0;
-    let page_center_y = page_height / 2.0;
+    let (page_x1, page_y1, page_x2, page_y2) = page;
+    let page_width = page_x2 - page_x1;
+    let page_height = page_y2 - page_y1;
+    let page_center_x = (page_x1 + page_x2) / 2.0;
+    let page_center_y = (page_y1 + page_y2) / 2.0;
 
     // Calculate page diagonal for normalization
     let page_diagonal = (page_width * page_width + page_height * page_height).sqrt();
@@ -53,8 +64,8 @@ pub fn partition_by_mask<T: BoundingBox>(
         // (only for visual elements)
         let (cx, cy) = element.center();
 
-        let distance_to_center =
-            ((cx - page_center_x).powi(2) + (cy - page_center_y).powi(2)).sqrt();
+        let (dx, dy) = (cx - page_center_x, cy - page_center_y);
+        let distance_to_center = (dx * dx + dy * dy).sqrt();
 
         // Normalize by page diagonal
         let normalized_distance = distance_to_center / page_diagonal;
@@ -66,10 +77,19 @@ pub fn partition_by_mask<T: BoundingBox>(
         let dist_to_text = distance_to_nearest_text(element, elements);
         let is_isolated = dist_to_text > ISOLATION_THRESHOLD_PX;
 
+        // Only trust the mask label when the detector is confident enough; inline
+        // formulas are read as text
+        let should_mask = element.should_mask()
+            && element
+                .confidence()
+                .is_none_or(|c| c >= config.mask_confidence_threshold)
+            && !(element.semantic_label() == SemanticLabel::Formula
+                && config.formulas.is_inline(element, elements));
+
         // Apply Equation 3 - mask if central AND isolated AND visual element
-        let is_geometric_mask = is_central && is_isolated && element.should_mask();
+        let is_geometric_mask = is_central && is_isolated && should_mask;
 
-        if element.should_mask() || is_cross_layout || is_geometric_mask {
+        if should_mask || is_cross_layout || is_geometric_mask {
             masked_elements.push(element.clone());
         } else {
             regular_elements.push(element.clone());
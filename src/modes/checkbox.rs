@@ -0,0 +1,263 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::core::XYCutPlusPlus;
+use crate::element::Element;
+use crate::modes::table::{layout_table_cells, TableConfig};
+use crate::preprocess::tiles::{find, union};
+use crate::traits::BoundingBox;
+
+/// Configuration for checkbox-grid / questionnaire mode
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CheckboxConfig {
+    /// Maximum width and height (pixels) of an option box
+    pub max_option_size: f32,
+
+    /// Maximum ratio of an option's longer side to its shorter (checkboxes and radio
+    /// buttons are square)
+    pub max_aspect_ratio: f32,
+
+    /// Maximum difference of the widths, and of the heights, of two options in one
+    /// grid, relative to the larger
+    pub size_tolerance: f32,
+
+    /// Maximum gap between neighboring options of a grid, relative to the larger
+    /// option's longer side
+    pub max_gap_ratio: f32,
+
+    /// Minimum number of options in a grid
+    pub min_options: usize,
+}
+
+impl Default for CheckboxConfig {
+    fn default() -> Self {
+        Self {
+            max_option_size: 40.0,
+            max_aspect_ratio: 1.5,
+            size_tolerance: 0.25,
+            max_gap_ratio: 20.0,
+            min_options: 3,
+        }
+    }
+}
+
+/// Detect checkbox grids: clusters of small, equally sized boxes aligned in rows
+/// and columns
+///
+/// Unmasked, roughly square boxes no larger than `max_option_size` are linked to
+/// options of about their size (within `size_tolerance`) sharing a row or a column
+/// with them at most `max_gap_ratio` sides away, unless another box lies between
+/// them (option labels on the row do not count, question text between two
+/// questions does). Clusters of at least `min_options` are grids, unless each of
+/// their boxes sits right after an option of another grid: those are short labels.
+/// Returns the option ids of each grid, in row-major order (see
+/// [`layout_table_cells`]).
+pub fn detect_checkbox_grids<T: BoundingBox>(
+    elements: &[T],
+    config: &CheckboxConfig,
+) -> Vec<Vec<usize>> {
+    let (options, others): (Vec<&T>, Vec<&T>) = elements.iter().partition(|e| {
+        let (x1, y1, x2, y2) = e.bounds();
+        let (short, long) = ((x2 - x1).min(y2 - y1), (x2 - x1).max(y2 - y1));
+        !e.should_mask()
+            && short > 0.0
+            && long <= config.max_option_size
+            && long <= config.max_aspect_ratio * short
+    });
+
+    let mut parent: Vec<usize> = (0..options.len()).collect();
+    for (i, a) in options.iter().enumerate() {
+        for (j, b) in options.iter().enumerate().skip(i + 1) {
+            if neighbors(*a, *b, &others, config) {
+                union(&mut parent, i, j);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<&T>> = vec![Vec::new(); options.len()];
+    for (i, option) in options.iter().enumerate() {
+        clusters[find(&mut parent, i)].push(*option);
+    }
+    clusters.retain(|cluster| cluster.len() >= config.min_options.max(1));
+
+    // Short labels ("No") are as square as options and line up into a grid of their
+    // own, each right after an option of the real grid
+    let label_grids: Vec<bool> = clusters
+        .iter()
+        .enumerate()
+        .map(|(i, cluster)| {
+            cluster.iter().all(|label| {
+                clusters.iter().enumerate().any(|(j, other)| {
+                    j != i && other.iter().any(|option| is_label_of(*label, *option))
+                })
+            })
+        })
+        .collect();
+    clusters
+        .into_iter()
+        .zip(label_grids)
+        .filter(|(_, label_grid)| !label_grid)
+        .map(|(cluster, _)| {
+            let cluster: Vec<T> = cluster.into_iter().cloned().collect();
+            layout_table_cells(&cluster, &TableConfig::default())
+                .iter()
+                .map(|c| c.id)
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether `label` sits right after `option` on its row, at most one option side away
+fn is_label_of<T: BoundingBox>(label: &T, option: &T) -> bool {
+    let (lx1, ly1, _, ly2) = label.bounds();
+    let (ox1, oy1, ox2, oy2) = option.bounds();
+    let side = (ox2 - ox1).max(oy2 - oy1);
+    let overlap = ly2.min(oy2) - ly1.max(oy1);
+    overlap >= 0.5 * (ly2 - ly1).min(oy2 - oy1) && lx1 >= ox2 - 0.5 * side && lx1 - ox2 <= side
+}
+
+/// Whether options `a` and `b` are about the same size and close on a shared row or
+/// column, with none of `others` between them
+fn neighbors<T: BoundingBox>(a: &T, b: &T, others: &[&T], config: &CheckboxConfig) -> bool {
+    let (ax1, ay1, ax2, ay2) = a.bounds();
+    let (bx1, by1, bx2, by2) = b.bounds();
+    let alike = |p: f32, q: f32| (p - q).abs() <= config.size_tolerance * p.max(q);
+    if !alike(ax2 - ax1, bx2 - bx1) || !alike(ay2 - ay1, by2 - by1) {
+        return false;
+    }
+
+    let side = (ax2 - ax1).max(ay2 - ay1).max(bx2 - bx1).max(by2 - by1);
+    let overlap = |start: f32, end: f32, other_start: f32, other_end: f32| {
+        end.min(other_end) - start.max(other_start)
+    };
+    let aligned = |start: f32, end: f32, other_start: f32, other_end: f32| {
+        overlap(start, end, other_start, other_end)
+            >= 0.5 * (end - start).min(other_end - other_start)
+    };
+    // The space between the two options
+    let (gx1, gx2) = (ax2.min(bx2), ax1.max(bx1));
+    let (gy1, gy2) = (ay2.min(by2), ay1.max(by1));
+
+    if aligned(ay1, ay2, by1, by2) {
+        // Labels sit on the row; only a box reaching well above or below it blocks
+        let (top, bottom) = (ay1.min(by1), ay2.max(by2));
+        gx2 - gx1 <= config.max_gap_ratio * side
+            && !others.iter().any(|e| {
+                let (ex1, ey1, ex2, ey2) = e.bounds();
+                overlap(gx1, gx2, ex1, ex2) > 0.0
+                    && overlap(top, bottom, ey1, ey2) > 0.0
+                    && (ey1 < top - 0.5 * side || ey2 > bottom + 0.5 * side)
+            })
+    } else {
+        aligned(ax1, ax2, bx1, bx2)
+            && gy2 - gy1 <= config.max_gap_ratio * side
+            && !others.iter().any(|e| {
+                let (ex1, ey1, ex2, ey2) = e.bounds();
+                overlap(ax1.max(bx1), ax2.min(bx2), ex1, ex2) > 0.0
+                    && overlap(gy1, gy2, ey1, ey2) > 0.0
+            })
+    }
+}
+
+/// Pair options with their label: the nearest box to their right on their row,
+/// before the next option
+fn option_labels<T: BoundingBox>(
+    elements: &[T],
+    grids: &[Vec<usize>],
+    config: &CheckboxConfig,
+) -> HashMap<usize, usize> {
+    let options: HashSet<usize> = grids.iter().flatten().copied().collect();
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+    let mut labels = HashMap::new();
+    let mut taken: HashSet<usize> = HashSet::new();
+    for &id in grids.iter().flatten() {
+        let (ox1, oy1, ox2, oy2) = by_id[&id].bounds();
+        let side = (ox2 - ox1).max(oy2 - oy1);
+        let on_row = |e: &&T| {
+            let (_, y1, _, y2) = e.bounds();
+            (y2.min(oy2) - y1.max(oy1)) >= 0.5 * (y2 - y1).min(oy2 - oy1)
+        };
+        let right = |e: &&T| e.bounds().0 >= ox2 - 0.5 * side;
+        let next_option = elements
+            .iter()
+            .filter(|e| options.contains(&e.id()) && e.id() != id && on_row(e) && right(e))
+            .map(|e| e.bounds().0)
+            .fold(f32::INFINITY, f32::min);
+        let label = elements
+            .iter()
+            .filter(|e| !options.contains(&e.id()) && !e.should_mask() && !taken.contains(&e.id()))
+            .filter(|e| on_row(e) && right(e))
+            .filter(|e| {
+                let x1 = e.bounds().0;
+                x1 < next_option && x1 - ox2 <= config.max_gap_ratio * side
+            })
+            .min_by(|a, b| {
+                a.bounds()
+                    .0
+                    .total_cmp(&b.bounds().0)
+                    .then(a.id().cmp(&b.id()))
+            });
+        if let Some(label) = label {
+            taken.insert(label.id());
+            labels.insert(id, label.id());
+        }
+    }
+    labels
+}
+
+impl XYCutPlusPlus {
+    /// Checkbox mode: read each grid of options row by row, each option before its
+    /// label
+    ///
+    /// Checkbox grids (see [`detect_checkbox_grids`]) are merged with the labels to
+    /// the right of their options into one box, placed by the algorithm like any
+    /// other, then expanded as `option, label` row by row. Tiny isolated options no
+    /// longer scatter across the cuts of the questionnaire around them.
+    pub fn compute_checkbox_order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        checkbox: &CheckboxConfig,
+    ) -> Vec<usize> {
+        let grids = detect_checkbox_grids(elements, checkbox);
+        let labels = option_labels(elements, &grids, checkbox);
+        let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+        let members_of: HashMap<usize, Vec<usize>> = grids
+            .iter()
+            .map(|options| {
+                let members = options
+                    .iter()
+                    .flat_map(|id| std::iter::once(*id).chain(labels.get(id).copied()))
+                    .collect();
+                (options[0], members)
+            })
+            .collect();
+        let grouped: HashSet<usize> = members_of
+            .iter()
+            .flat_map(|(unit, members)| members.iter().filter(move |id| *id != unit))
+            .copied()
+            .collect();
+
+        // One unit per grid (keyed by its first option) plus every element outside grids
+        let units: Vec<Element> = elements
+            .iter()
+            .filter(|e| !grouped.contains(&e.id()))
+            .map(|e| {
+                let mut unit = Element::from_box(e);
+                for member in members_of.get(&e.id()).into_iter().flatten() {
+                    unit.extend(by_id[member]);
+                }
+                unit
+            })
+            .collect();
+
+        self.compute_order(&units, x_min, y_min, x_max, y_max)
+            .into_iter()
+            .flat_map(|id| members_of.get(&id).cloned().unwrap_or_else(|| vec![id]))
+            .collect()
+    }
+}
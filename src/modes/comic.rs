@@ -0,0 +1,131 @@
+use crate::core::XYCutPlusPlus;
+use crate::element::Element;
+use crate::traits::{BoundingBox, SemanticLabel};
+
+/// Overlap (fraction of the smaller panel's area) above which the smaller panel is treated as an inset
+const INSET_OVERLAP_RATIO: f32 = 0.1;
+
+/// Reading direction of comic panels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PanelDirection {
+    /// Western comics: left-to-right, top-to-bottom
+    #[default]
+    LeftToRight,
+    /// Manga: right-to-left, top-to-bottom
+    RightToLeft,
+}
+
+impl XYCutPlusPlus {
+    /// Comic mode: order panels for Western or manga reading conventions
+    ///
+    /// Manga order is obtained by mirroring the page horizontally before cutting.
+    /// Panels that substantially overlap a larger panel (insets, panels breaking
+    /// out of the grid) are masked like figures and reinserted with
+    /// the distance metric, so irregular shapes don't block the cuts between the
+    /// regular panels.
+    pub fn compute_panel_order<T: BoundingBox>(
+        &self,
+        panels: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        direction: PanelDirection,
+    ) -> Vec<usize> {
+        let mut proxies: Vec<Element> = panels
+            .iter()
+            .map(|panel| {
+                let mut proxy = Element::from_box(panel);
+                proxy.label = SemanticLabel::Regular;
+                proxy.mask = false;
+                if direction == PanelDirection::RightToLeft {
+                    (proxy.x1, proxy.x2) = (x_min + x_max - proxy.x2, x_min + x_max - proxy.x1);
+                }
+                proxy
+            })
+            .collect();
+
+        for i in 0..proxies.len() {
+            let is_inset = proxies
+                .iter()
+                .enumerate()
+                .any(|(j, other)| i != j && is_inset_of(&proxies[i], other));
+            if is_inset {
+                proxies[i].label = SemanticLabel::Vision;
+                proxies[i].mask = true;
+            }
+        }
+
+        self.compute_order(&proxies, x_min, y_min, x_max, y_max)
+    }
+}
+
+/// Whether `panel` overlaps the larger `other` by more than [`INSET_OVERLAP_RATIO`] of its area
+fn is_inset_of(panel: &Element, other: &Element) -> bool {
+    let area = panel.width() * panel.height();
+    let other_area = other.width() * other.height();
+    // Ties go to the later panel by id so exactly one of two equal panels is masked
+    if area > other_area || (area == other_area && panel.id < other.id) {
+        return false;
+    }
+
+    let x_overlap = (panel.x2.min(other.x2) - panel.x1.max(other.x1)).max(0.0);
+    let y_overlap = (panel.y2.min(other.y2) - panel.y1.max(other.y1)).max(0.0);
+    area > 0.0 && x_overlap * y_overlap / area > INSET_OVERLAP_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+
+    /// A tall panel on the left spanning two tiers, two stacked panels on
+    /// the right, and a full-width bottom tier
+    fn page() -> Vec<Element> {
+        vec![
+            Element::new(0, 0.0, 0.0, 400.0, 800.0, SemanticLabel::Regular),
+            Element::new(1, 420.0, 0.0, 800.0, 390.0, SemanticLabel::Regular),
+            Element::new(2, 420.0, 410.0, 800.0, 800.0, SemanticLabel::Regular),
+            Element::new(3, 0.0, 820.0, 800.0, 1100.0, SemanticLabel::Regular),
+        ]
+    }
+
+    fn order(direction: PanelDirection) -> Vec<usize> {
+        XYCutPlusPlus::new(XYCutConfig::default()).compute_panel_order(
+            &page(),
+            0.0,
+            0.0,
+            800.0,
+            1100.0,
+            direction,
+        )
+    }
+
+    #[test]
+    fn western_order_reads_the_tall_panel_first() {
+        assert_eq!(order(PanelDirection::LeftToRight), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn manga_order_reads_the_right_column_first() {
+        assert_eq!(order(PanelDirection::RightToLeft), vec![1, 2, 0, 3]);
+    }
+
+    #[test]
+    fn grid_rows_reverse_under_manga_order() {
+        let grid: Vec<Element> = (0..4)
+            .map(|i| {
+                let (x, y) = ((i % 2) as f32 * 410.0, (i / 2) as f32 * 410.0);
+                Element::new(i, x, y, x + 390.0, y + 390.0, SemanticLabel::Regular)
+            })
+            .collect();
+        let engine = XYCutPlusPlus::new(XYCutConfig::default());
+        let ltr =
+            engine.compute_panel_order(&grid, 0.0, 0.0, 800.0, 800.0, PanelDirection::LeftToRight);
+        let rtl =
+            engine.compute_panel_order(&grid, 0.0, 0.0, 800.0, 800.0, PanelDirection::RightToLeft);
+        assert_eq!(ltr, vec![0, 1, 2, 3]);
+        assert_eq!(rtl, vec![1, 0, 3, 2]);
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::core::XYCutPlusPlus;
+use crate::element::Element;
+use crate::traits::BoundingBox;
+
+/// Configuration for form / key-value layout mode
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FormConfig {
+    /// Maximum horizontal gap (pixels) between a field label and its value box
+    pub max_pair_gap: f32,
+
+    /// Minimum vertical overlap (fraction of the shorter box) for a label and value on one row
+    pub min_row_overlap: f32,
+}
+
+impl Default for FormConfig {
+    fn default() -> Self {
+        Self {
+            max_pair_gap: 40.0,
+            min_row_overlap: 0.5,
+        }
+    }
+}
+
+/// Detect field label → value pairs: unmasked boxes with a close right-hand neighbor on the same row
+///
+/// Pairs are formed greedily from the smallest gap, so every element belongs to at most one pair.
+/// Returns `(label_id, value_id)` tuples.
+pub fn detect_label_value_pairs<T: BoundingBox>(
+    elements: &[T],
+    config: &FormConfig,
+) -> Vec<(usize, usize)> {
+    let mut candidates: Vec<(f32, usize, usize)> = Vec::new();
+
+    for label in elements.iter().filter(|e| !e.should_mask()) {
+        let (lx1, ly1, lx2, ly2) = label.bounds();
+
+        for value in elements.iter().filter(|e| e.id() != label.id()) {
+            let (vx1, vy1, _, vy2) = value.bounds();
+
+            // Value must sit to the right of the label
+            if value.center().0 <= lx2 || vx1 <= lx1 {
+                continue;
+            }
+
+            let gap = (vx1 - lx2).max(0.0);
+            let row_overlap = (ly2.min(vy2) - ly1.max(vy1)).max(0.0);
+            let shorter = (ly2 - ly1).min(vy2 - vy1);
+            if gap <= config.max_pair_gap
+                && shorter > 0.0
+                && row_overlap / shorter >= config.min_row_overlap
+            {
+                candidates.push((gap, label.id(), value.id()));
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0).then((a.1, a.2).cmp(&(b.1, b.2))));
+
+    let mut paired: HashSet<usize> = HashSet::new();
+    let mut pairs = Vec::new();
+    for (_, label, value) in candidates {
+        if paired.contains(&label) || paired.contains(&value) {
+            continue;
+        }
+        paired.insert(label);
+        paired.insert(value);
+        pairs.push((label, value));
+    }
+
+    pairs
+}
+
+impl XYCutPlusPlus {
+    /// Form mode: order field labels immediately before their value boxes
+    ///
+    /// Label–value pairs (see [`detect_label_value_pairs`]) are merged into one
+    /// box before ordering, so no cut can separate them, then expanded back
+    /// as `label, value` in the resulting order.
+    pub fn compute_form_order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        form: &FormConfig,
+    ) -> Vec<usize> {
+        let pairs = detect_label_value_pairs(elements, form);
+        let value_of: HashMap<usize, usize> = pairs.iter().copied().collect();
+        let values: HashMap<usize, usize> = pairs.iter().map(|&(l, v)| (v, l)).collect();
+        let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+
+        // One unit per pair (keyed by the label id) plus every unpaired element
+        let units: Vec<Element> = elements
+            .iter()
+            .filter(|e| !values.contains_key(&e.id()))
+            .map(|e| {
+                let mut unit = Element::from_box(e);
+                if let Some(value) = value_of.get(&e.id()) {
+                    unit.extend(by_id[value]);
+                }
+                unit
+            })
+            .collect();
+
+        self.compute_order(&units, x_min, y_min, x_max, y_max)
+            .into_iter()
+            .flat_map(|id| std::iter::once(id).chain(value_of.get(&id).copied()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::traits::SemanticLabel;
+
+    /// Three rows of `label: value`, with a wide gutter between the columns
+    fn form_page() -> Vec<Element> {
+        (0..3)
+            .flat_map(|row| {
+                let y = 100.0 + row as f32 * 60.0;
+                [
+                    Element::new(row * 2, 50.0, y, 150.0, y + 30.0, SemanticLabel::Regular),
+                    Element::new(
+                        row * 2 + 1,
+                        180.0,
+                        y,
+                        500.0,
+                        y + 30.0,
+                        SemanticLabel::Regular,
+                    ),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn labels_pair_with_the_value_on_their_row() {
+        let mut pairs = detect_label_value_pairs(&form_page(), &FormConfig::default());
+        pairs.sort_unstable();
+        assert_eq!(pairs, vec![(0, 1), (2, 3), (4, 5)]);
+    }
+
+    #[test]
+    fn form_order_reads_row_by_row() {
+        let order = XYCutPlusPlus::new(XYCutConfig::default()).compute_form_order(
+            &form_page(),
+            0.0,
+            0.0,
+            600.0,
+            400.0,
+            &FormConfig::default(),
+        );
+        assert_eq!(order, vec![0, 1, 2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,22 @@
+//! Layout-specific ordering modes built on top of the core algorithm
+
+pub mod checkbox;
+pub mod comic;
+pub mod form;
+pub mod newspaper;
+pub mod parallel;
+pub mod poster;
+pub mod receipt;
+pub mod slide;
+pub mod spread;
+pub mod table;
+
+pub use checkbox::CheckboxConfig;
+pub use comic::PanelDirection;
+pub use form::FormConfig;
+pub use newspaper::Article;
+pub use parallel::ParallelOrder;
+pub use poster::{PosterConfig, PosterPath};
+pub use receipt::ReceiptConfig;
+pub use spread::{PageSequence, SpreadOrder};
+pub use table::{TableConfig, TableOrder};
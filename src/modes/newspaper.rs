@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::core::XYCutPlusPlus;
+use crate::traits::BoundingBox;
+
+/// Minimum horizontal overlap (fraction of the element width) for a title to head an element
+const TITLE_COLUMN_OVERLAP: f32 = 0.5;
+
+/// One article of a newspaper page
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Article {
+    /// Id of the headline, `None` for content preceding every title
+    pub title: Option<usize>,
+
+    /// Reading order of the article's elements, headline first
+    pub order: Vec<usize>,
+}
+
+impl XYCutPlusPlus {
+    /// Newspaper mode: thread the page into articles instead of one interleaved order
+    ///
+    /// Every title opens an article. Each other element joins the article of the
+    /// nearest title above it that covers its column (title proximity); elements
+    /// with no such title continue the article of the element preceding them in the
+    /// page order (column continuation, e.g. an article flowing into the next column).
+    ///
+    /// Articles are returned in sequence of first appearance in the page order.
+    pub fn compute_articles<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Vec<Article> {
+        let page_order = self.compute_order(elements, x_min, y_min, x_max, y_max);
+        let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+
+        let titles: Vec<&T> = elements
+            .iter()
+            .filter(|e| e.semantic_label().is_title())
+            .collect();
+
+        let mut articles: Vec<Article> = Vec::new();
+        let mut article_of_title: HashMap<usize, usize> = HashMap::new();
+        let mut current: Option<usize> = None;
+
+        for id in &page_order {
+            let element = by_id[id];
+
+            let heading = if element.semantic_label().is_title() {
+                Some(element)
+            } else {
+                nearest_title_above(element, &titles)
+            };
+
+            let index = match heading {
+                Some(title) => *article_of_title.entry(title.id()).or_insert_with(|| {
+                    articles.push(Article {
+                        title: Some(title.id()),
+                        order: Vec::new(),
+                    });
+                    articles.len() - 1
+                }),
+                None => match current {
+                    Some(index) => index,
+                    None => {
+                        articles.push(Article {
+                            title: None,
+                            order: Vec::new(),
+                        });
+                        articles.len() - 1
+                    }
+                },
+            };
+
+            articles[index].order.push(*id);
+            current = Some(index);
+        }
+
+        // Headline first, then the remaining members in page order
+        for article in &mut articles {
+            if let Some(title) = article.title {
+                article.order.retain(|id| *id != title);
+                article.order.insert(0, title);
+            }
+        }
+
+        articles
+    }
+}
+
+/// Closest title whose bottom edge is above `element` and that spans its column
+fn nearest_title_above<'a, T: BoundingBox>(element: &T, titles: &[&'a T]) -> Option<&'a T> {
+    let (x1, y1, x2, _) = element.bounds();
+    let width = (x2 - x1).max(f32::EPSILON);
+
+    titles
+        .iter()
+        .filter(|title| {
+            let (tx1, _, tx2, ty2) = title.bounds();
+            let overlap = (x2.min(tx2) - x1.max(tx1)).max(0.0);
+            ty2 <= y1 && overlap / width >= TITLE_COLUMN_OVERLAP
+        })
+        .max_by(|a, b| {
+            a.bounds()
+                .3
+                .total_cmp(&b.bounds().3)
+                .then(b.id().cmp(&a.id()))
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    #[test]
+    fn articles_follow_titles_and_column_continuations() {
+        let page = [
+            // Masthead preceding every headline
+            Element::new(7, 50.0, 0.0, 950.0, 30.0, SemanticLabel::Regular),
+            // Article A: headline over the first column, flowing on into the second
+            Element::new(0, 50.0, 50.0, 300.0, 90.0, SemanticLabel::HorizontalTitle),
+            Element::new(1, 50.0, 100.0, 300.0, 400.0, SemanticLabel::Regular),
+            Element::new(2, 50.0, 410.0, 300.0, 700.0, SemanticLabel::Regular),
+            Element::new(3, 350.0, 50.0, 600.0, 700.0, SemanticLabel::Regular),
+            // Article B in the third column
+            Element::new(4, 650.0, 50.0, 950.0, 90.0, SemanticLabel::HorizontalTitle),
+            Element::new(5, 650.0, 100.0, 950.0, 700.0, SemanticLabel::Regular),
+        ];
+        let articles = XYCutPlusPlus::new(XYCutConfig::default())
+            .compute_articles(&page, 0.0, 0.0, 1000.0, 1400.0);
+        assert_eq!(
+            articles,
+            vec![
+                Article {
+                    title: None,
+                    order: vec![7]
+                },
+                Article {
+                    title: Some(0),
+                    order: vec![0, 1, 2, 3]
+                },
+                Article {
+                    title: Some(4),
+                    order: vec![4, 5]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_headline_takes_over_the_column_below_it() {
+        let page = [
+            // Article A: headline spanning both columns
+            Element::new(0, 50.0, 50.0, 600.0, 90.0, SemanticLabel::HorizontalTitle),
+            Element::new(1, 50.0, 100.0, 300.0, 500.0, SemanticLabel::Regular),
+            Element::new(2, 350.0, 100.0, 600.0, 500.0, SemanticLabel::Regular),
+            // Article B starts lower down, across the same columns
+            Element::new(3, 50.0, 550.0, 600.0, 590.0, SemanticLabel::HorizontalTitle),
+            Element::new(4, 50.0, 600.0, 300.0, 900.0, SemanticLabel::Regular),
+            Element::new(5, 350.0, 600.0, 600.0, 900.0, SemanticLabel::Regular),
+        ];
+        let articles = XYCutPlusPlus::new(XYCutConfig::default())
+            .compute_articles(&page, 0.0, 0.0, 1000.0, 1400.0);
+        let orders: Vec<(Option<usize>, Vec<usize>)> = articles
+            .into_iter()
+            .map(|article| (article.title, article.order))
+            .collect();
+        assert_eq!(
+            orders,
+            vec![(Some(0), vec![0, 1, 2]), (Some(3), vec![3, 4, 5])]
+        );
+    }
+}
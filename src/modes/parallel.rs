@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+
+use crate::core::XYCutPlusPlus;
+use crate::traits::BoundingBox;
+
+/// Reading order of a bilingual page, one sequence per language column
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParallelOrder {
+    /// X-coordinate of the gutter between the columns, `None` if the columns come
+    /// from language hints or the page holds a single column
+    pub gutter: Option<f32>,
+
+    /// Reading order of each language column: by language index when the elements
+    /// carry [`language`](BoundingBox::language) hints, else left to right
+    pub columns: Vec<Vec<usize>>,
+}
+
+impl ParallelOrder {
+    /// All columns concatenated in sequence
+    pub fn order(&self) -> Vec<usize> {
+        self.columns.concat()
+    }
+}
+
+impl XYCutPlusPlus {
+    /// Parallel mode: order each language column of a bilingual page as one block
+    ///
+    /// Translations set side by side line up row for row, which invites cuts that
+    /// interleave them. With [`language`](BoundingBox::language) hints, each
+    /// language is a column and elements without a hint join the column whose
+    /// hinted elements lie nearest horizontally; without hints, the page is split
+    /// at its central gutter (see [`detect_gutter`](Self::detect_gutter)). Each
+    /// column is ordered on its own and returned separately.
+    pub fn compute_parallel_order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> ParallelOrder {
+        // Mean center x of each hinted language, in language order
+        let mut centers: BTreeMap<usize, (f32, usize)> = BTreeMap::new();
+        for e in elements {
+            if let Some(language) = e.language() {
+                let (sum, count) = centers.entry(language).or_insert((0.0, 0));
+                (*sum, *count) = (*sum + e.center().0, *count + 1);
+            }
+        }
+
+        if !centers.is_empty() {
+            let languages: Vec<(usize, f32)> = centers
+                .into_iter()
+                .map(|(language, (sum, count))| (language, sum / count as f32))
+                .collect();
+            let column_of = |e: &T| match e.language() {
+                Some(language) => languages.iter().position(|(l, _)| *l == language),
+                None => languages
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, (_, a)), (_, (_, b))| {
+                        (a - e.center().0)
+                            .abs()
+                            .total_cmp(&(b - e.center().0).abs())
+                    })
+                    .map(|(column, _)| column),
+            };
+            let columns = (0..languages.len())
+                .map(|column| {
+                    let members: Vec<T> = elements
+                        .iter()
+                        .filter(|e| column_of(e) == Some(column))
+                        .cloned()
+                        .collect();
+                    self.compute_order(&members, x_min, y_min, x_max, y_max)
+                })
+                .collect();
+            return ParallelOrder {
+                gutter: None,
+                columns,
+            };
+        }
+
+        let Some(gutter) = self.detect_gutter(elements, x_min, x_max) else {
+            return ParallelOrder {
+                gutter: None,
+                columns: vec![self.compute_order(elements, x_min, y_min, x_max, y_max)],
+            };
+        };
+        let (left, right): (Vec<T>, Vec<T>) = elements
+            .iter()
+            .cloned()
+            .partition(|e| e.center().0 < gutter);
+        ParallelOrder {
+            gutter: Some(gutter),
+            columns: vec![
+                self.compute_order(&left, x_min, y_min, gutter, y_max),
+                self.compute_order(&right, gutter, y_min, x_max, y_max),
+            ],
+        }
+    }
+}
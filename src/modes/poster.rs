@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use crate::core::{XYCutConfig, XYCutPlusPlus};
+use crate::element::Element;
+use crate::fallback::{FallbackSort, RowGrouping};
+use crate::modes::table::{layout_table_cells, TableConfig};
+use crate::preprocess::tiles::{find, union};
+use crate::traits::BoundingBox;
+use crate::utils::{compare_position, compute_median_height};
+
+/// Number of concentric rings of a spiral path, from the page edge to its center
+const SPIRAL_RINGS: usize = 3;
+
+/// Path through the clusters of a poster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PosterPath {
+    /// Column by column, left to right, each column top to bottom
+    #[default]
+    Columnar,
+    /// Clockwise from the top-left corner, outer ring first, spiralling to the center
+    Spiral,
+}
+
+/// Configuration for poster / infographic mode
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PosterConfig {
+    /// Order in which the clusters are read
+    pub path: PosterPath,
+
+    /// Maximum gap between boxes of one cluster, relative to the median box height
+    pub cluster_gap_ratio: f32,
+}
+
+impl Default for PosterConfig {
+    fn default() -> Self {
+        Self {
+            path: PosterPath::Columnar,
+            cluster_gap_ratio: 1.0,
+        }
+    }
+}
+
+impl XYCutConfig {
+    /// Preset for posters and infographics
+    ///
+    /// Posters mix very different font sizes, so regions without a cut group their
+    /// rows by vertical overlap and read a title before the text level with it.
+    /// Pair it with [`XYCutPlusPlus::compute_poster_order`], which reads the
+    /// clusters of the poster along a [`PosterPath`] instead of cutting the page.
+    pub fn poster() -> Self {
+        Self {
+            fallback: FallbackSort {
+                rows: RowGrouping::Overlap,
+                label_priority: true,
+                ..FallbackSort::default()
+            },
+            ..Self::default()
+        }
+    }
+}
+
+/// Detect the spatial clusters of a poster: boxes linked by gaps of at most
+/// `cluster_gap_ratio` median box heights
+///
+/// The gap between two boxes is the larger of their horizontal and vertical gaps,
+/// so a cluster is a patch of the poster separated from the others by clear
+/// space. Returns the ids of each cluster, by position of its first box.
+pub fn detect_poster_clusters<T: BoundingBox>(
+    elements: &[T],
+    config: &PosterConfig,
+) -> Vec<Vec<usize>> {
+    let max_gap = config.cluster_gap_ratio * compute_median_height(elements);
+    let mut sorted: Vec<&T> = elements.iter().collect();
+    sorted.sort_by(|a, b| compare_position(*a, *b));
+
+    let mut parent: Vec<usize> = (0..sorted.len()).collect();
+    for (i, a) in sorted.iter().enumerate() {
+        let (ax1, ay1, ax2, ay2) = a.bounds();
+        for (j, b) in sorted.iter().enumerate().skip(i + 1) {
+            let (bx1, by1, bx2, by2) = b.bounds();
+            let gap_x = bx1.max(ax1) - bx2.min(ax2);
+            let gap_y = by1.max(ay1) - by2.min(ay2);
+            if gap_x.max(gap_y) <= max_gap {
+                union(&mut parent, i, j);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = vec![Vec::new(); sorted.len()];
+    for (i, e) in sorted.iter().enumerate() {
+        clusters[find(&mut parent, i)].push(e.id());
+    }
+    clusters.retain(|cluster| !cluster.is_empty());
+    clusters
+}
+
+impl XYCutPlusPlus {
+    /// Poster mode: read the biggest title, then each cluster of the poster along
+    /// `poster.path`
+    ///
+    /// Posters have no columns for cuts to find. The largest title box is read
+    /// first; the other boxes are grouped into clusters (see
+    /// [`detect_poster_clusters`]), the clusters are visited column by column or
+    /// along a clockwise spiral from the top-left corner, and each cluster is
+    /// ordered by the algorithm on its own.
+    pub fn compute_poster_order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        poster: &PosterConfig,
+    ) -> Vec<usize> {
+        let area = |e: &T| {
+            let (x1, y1, x2, y2) = e.bounds();
+            (x2 - x1) * (y2 - y1)
+        };
+        let title = elements
+            .iter()
+            .filter(|e| e.semantic_label().is_title())
+            .max_by(|a, b| area(a).total_cmp(&area(b)).then(b.id().cmp(&a.id())))
+            .map(|e| e.id());
+
+        let rest: Vec<Element> = elements
+            .iter()
+            .filter(|e| Some(e.id()) != title)
+            .map(Element::from_box)
+            .collect();
+        let by_id: HashMap<usize, &Element> = rest.iter().map(|e| (e.id, e)).collect();
+        let clusters: Vec<Vec<Element>> = detect_poster_clusters(&rest, poster)
+            .into_iter()
+            .map(|ids| ids.iter().map(|id| by_id[id].clone()).collect())
+            .collect();
+
+        // One box per cluster, with the cluster's index as its id
+        let outlines: Vec<Element> = clusters
+            .iter()
+            .enumerate()
+            .map(|(index, cluster)| {
+                let mut outline = cluster[0].clone();
+                for e in cluster {
+                    outline.extend(e);
+                }
+                outline.id = index;
+                outline
+            })
+            .collect();
+        let path = match poster.path {
+            PosterPath::Columnar => {
+                let mut cells = layout_table_cells(&outlines, &TableConfig::default());
+                cells.sort_by_key(|cell| (cell.col, cell.row));
+                cells.into_iter().map(|cell| cell.id).collect()
+            }
+            PosterPath::Spiral => spiral_path(&outlines),
+        };
+
+        title
+            .into_iter()
+            .chain(
+                path.into_iter().flat_map(|index| {
+                    self.compute_order(&clusters[index], x_min, y_min, x_max, y_max)
+                }),
+            )
+            .collect()
+    }
+}
+
+/// Ids of `outlines` clockwise from the top-left corner, ring by ring from the
+/// outside inward
+///
+/// Positions are taken relative to the box spanned by the outlines' centers, and
+/// each ring is walked along its perimeter: the top edge left to right, the right
+/// edge down, the bottom edge right to left, then the left edge up.
+fn spiral_path(outlines: &[Element]) -> Vec<usize> {
+    let centers: Vec<(f32, f32)> = outlines.iter().map(|e| e.center()).collect();
+    let (x_min, x_max) = centers
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &(x, _)| {
+            (lo.min(x), hi.max(x))
+        });
+    let (y_min, y_max) = centers
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &(_, y)| {
+            (lo.min(y), hi.max(y))
+        });
+    let half_width = ((x_max - x_min) / 2.0).max(f32::MIN_POSITIVE);
+    let half_height = ((y_max - y_min) / 2.0).max(f32::MIN_POSITIVE);
+    let place = |(x, y): (f32, f32)| {
+        let nx = (x - (x_min + x_max) / 2.0) / half_width;
+        let ny = (y - (y_min + y_max) / 2.0) / half_height;
+        let depth = 1.0 - nx.abs().max(ny.abs());
+        let ring = ((depth * SPIRAL_RINGS as f32) as usize).min(SPIRAL_RINGS - 1);
+        let along = if ny.abs() >= nx.abs() && ny < 0.0 {
+            1.0 + nx
+        } else if nx.abs() > ny.abs() && nx > 0.0 {
+            3.0 + ny
+        } else if ny.abs() >= nx.abs() {
+            5.0 - nx
+        } else {
+            7.0 - ny
+        };
+        (ring, along)
+    };
+
+    let mut path: Vec<usize> = (0..outlines.len()).collect();
+    path.sort_by(|&a, &b| {
+        let ((ring_a, along_a), (ring_b, along_b)) = (place(centers[a]), place(centers[b]));
+        ring_a
+            .cmp(&ring_b)
+            .then(along_a.total_cmp(&along_b))
+            .then(compare_position(&outlines[a], &outlines[b]))
+    });
+    path.into_iter().map(|i| outlines[i].id).collect()
+}
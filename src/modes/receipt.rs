@@ -0,0 +1,160 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::core::{XYCutConfig, XYCutPlusPlus};
+use crate::element::Element;
+use crate::fallback::{FallbackSort, RowGrouping};
+use crate::traits::BoundingBox;
+
+/// Configuration for receipt / narrow-document mode
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ReceiptConfig {
+    /// Minimum vertical overlap (fraction of the shorter box) for an item and its
+    /// amount on one line
+    pub min_row_overlap: f32,
+}
+
+impl Default for ReceiptConfig {
+    fn default() -> Self {
+        Self {
+            min_row_overlap: 0.5,
+        }
+    }
+}
+
+impl XYCutConfig {
+    /// Preset for thermal receipts and other single narrow columns
+    ///
+    /// Never cuts vertically first and cuts rows at 2-pixel line gaps, one bin per
+    /// pixel, so the price column is not split from the items; regions without a
+    /// cut group their rows by vertical overlap, keeping a tall item level with its
+    /// amount.
+    pub fn receipt() -> Self {
+        Self {
+            vertical_first_threshold: f32::INFINITY,
+            min_cut_threshold: 2.0,
+            histogram_resolution_scale: 1.0,
+            fallback: FallbackSort {
+                rows: RowGrouping::Overlap,
+                ..FallbackSort::default()
+            },
+            ..Self::default()
+        }
+    }
+}
+
+/// Detect item → amount pairs: the rightmost box of a line with its nearest
+/// neighbor to the left
+///
+/// Boxes share a line when their vertical overlap reaches `min_row_overlap` of
+/// the shorter one, so an item wrapped over two lines still pairs with the amount
+/// printed level with it. Masked boxes (logos, codes) are never paired, and every
+/// box belongs to at most one pair. Returns `(item_id, amount_id)` tuples, top to
+/// bottom.
+pub fn detect_item_amount_pairs<T: BoundingBox>(
+    elements: &[T],
+    config: &ReceiptConfig,
+) -> Vec<(usize, usize)> {
+    let boxes: Vec<&T> = elements.iter().filter(|e| !e.should_mask()).collect();
+    let same_line = |a: &T, b: &T| {
+        let (_, ay1, _, ay2) = a.bounds();
+        let (_, by1, _, by2) = b.bounds();
+        let overlap = (ay2.min(by2) - ay1.max(by1)).max(0.0);
+        let shorter = (ay2 - ay1).min(by2 - by1);
+        shorter > 0.0 && overlap / shorter >= config.min_row_overlap
+    };
+
+    let mut amounts: Vec<&T> = boxes
+        .iter()
+        .copied()
+        .filter(|amount| {
+            !boxes.iter().any(|other| {
+                other.id() != amount.id()
+                    && other.center().0 > amount.center().0
+                    && same_line(other, amount)
+            })
+        })
+        .collect();
+    amounts.sort_by(|a, b| {
+        a.bounds()
+            .1
+            .total_cmp(&b.bounds().1)
+            .then(a.id().cmp(&b.id()))
+    });
+
+    let mut paired: HashSet<usize> = HashSet::new();
+    let mut pairs = Vec::new();
+    for amount in amounts {
+        if paired.contains(&amount.id()) {
+            continue;
+        }
+        let item = boxes
+            .iter()
+            .filter(|item| {
+                item.id() != amount.id()
+                    && !paired.contains(&item.id())
+                    && item.center().0 < amount.bounds().0
+                    && same_line(item, amount)
+            })
+            .max_by(|a, b| {
+                a.bounds()
+                    .2
+                    .total_cmp(&b.bounds().2)
+                    .then(b.id().cmp(&a.id()))
+            });
+        if let Some(item) = item {
+            paired.insert(item.id());
+            paired.insert(amount.id());
+            pairs.push((item.id(), amount.id()));
+        }
+    }
+
+    pairs
+}
+
+impl XYCutPlusPlus {
+    /// Receipt mode: read lines strictly top to bottom, each item before its amount
+    ///
+    /// Item–amount pairs (see [`detect_item_amount_pairs`]) are merged into one box,
+    /// then the boxes are grouped into lines by vertical overlap and read line by
+    /// line, left to right, without any cut: a vertical cut between the items and
+    /// the price column would read every price after every item.
+    pub fn compute_receipt_order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        receipt: &ReceiptConfig,
+    ) -> Vec<usize> {
+        let pairs = detect_item_amount_pairs(elements, receipt);
+        let amount_of: HashMap<usize, usize> = pairs.iter().copied().collect();
+        let amounts: HashSet<usize> = pairs.iter().map(|&(_, amount)| amount).collect();
+        let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+
+        // One unit per pair (keyed by the item id) plus every unpaired element
+        let mut units: Vec<Element> = elements
+            .iter()
+            .filter(|e| !amounts.contains(&e.id()))
+            .map(|e| {
+                let mut unit = Element::from_box(e);
+                if let Some(amount) = amount_of.get(&e.id()) {
+                    unit.extend(by_id[amount]);
+                }
+                unit
+            })
+            .collect();
+
+        let lines = FallbackSort {
+            rows: RowGrouping::Overlap,
+            ..FallbackSort::default()
+        };
+        lines.sort(
+            &mut units,
+            self.config.same_row_tolerance,
+            &self.config.label_priorities,
+        );
+        units
+            .iter()
+            .flat_map(|unit| std::iter::once(unit.id).chain(amount_of.get(&unit.id).copied()))
+            .collect()
+    }
+}
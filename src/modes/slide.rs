@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use crate::core::XYCutPlusPlus;
+use crate::element::Element;
+use crate::traits::BoundingBox;
+use crate::utils::compare_position;
+
+/// Detect floating shapes: boxes drawn over an unmasked box with a lower z-index
+///
+/// Callouts, stickers and annotations are placed on top of the body text; a text
+/// box over a background picture is not floating, nor is a shape over another of
+/// its group. Elements without a [`z_index`](BoundingBox::z_index) never float and
+/// are never floated over.
+pub fn detect_floating_shapes<T: BoundingBox>(elements: &[T]) -> Vec<usize> {
+    elements
+        .iter()
+        .filter(|shape| {
+            let Some(z) = shape.z_index() else {
+                return false;
+            };
+            elements.iter().any(|below| {
+                below.id() != shape.id()
+                    && (shape.group().is_none() || below.group() != shape.group())
+                    && !below.should_mask()
+                    && below.z_index().is_some_and(|below_z| below_z < z)
+                    && overlap_area(*shape, below) > 0.0
+            })
+        })
+        .map(|shape| shape.id())
+        .collect()
+}
+
+impl XYCutPlusPlus {
+    /// Slide mode: order the title placeholder, then the body placeholders, then the
+    /// floating shapes
+    ///
+    /// Titles are read top to bottom and body placeholders are ordered by the
+    /// algorithm. Floating shapes (see [`detect_floating_shapes`]) come last, top to
+    /// bottom and lowest z-index first among equals, wherever they sit over the
+    /// body. Shapes sharing a [`group`](BoundingBox::group) are read together, in
+    /// their own order, at the place of the group's box; a group is a title if it
+    /// holds one and floats if any member does.
+    pub fn compute_slide_order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Vec<usize> {
+        // One unit per group plus every ungrouped shape, each going by its first member's id
+        let mut groups: BTreeMap<usize, Vec<Element>> = BTreeMap::new();
+        let mut units: Vec<Vec<Element>> = Vec::new();
+        for e in elements {
+            match e.group() {
+                Some(group) => groups.entry(group).or_default().push(Element::from_box(e)),
+                None => units.push(vec![Element::from_box(e)]),
+            }
+        }
+        units.extend(groups.into_values());
+
+        let floating = detect_floating_shapes(elements);
+        let mut titles: Vec<Element> = Vec::new();
+        let mut body: Vec<Element> = Vec::new();
+        let mut floats: Vec<Element> = Vec::new();
+        let mut members: BTreeMap<usize, Vec<Element>> = BTreeMap::new();
+        for unit in units {
+            let mut proxy = unit[0].clone();
+            for member in &unit[1..] {
+                proxy.extend(member);
+                proxy.z_index = proxy.z_index.max(member.z_index);
+            }
+            if let Some(title) = unit.iter().find(|e| e.label.is_title()) {
+                (proxy.label, proxy.mask) = (title.label, title.mask);
+                titles.push(proxy.clone());
+            } else if unit.iter().any(|e| floating.contains(&e.id)) {
+                floats.push(proxy.clone());
+            } else {
+                body.push(proxy.clone());
+            }
+            members.insert(proxy.id, unit);
+        }
+
+        titles.sort_by(compare_position);
+        floats.sort_by(|a, b| {
+            a.y1.total_cmp(&b.y1)
+                .then(a.z_index.cmp(&b.z_index))
+                .then(compare_position(a, b))
+        });
+        let body = self.compute_order(&body, x_min, y_min, x_max, y_max);
+
+        titles
+            .iter()
+            .map(|e| e.id)
+            .chain(body)
+            .chain(floats.iter().map(|e| e.id))
+            .flat_map(|id| match members.get(&id) {
+                Some(unit) if unit.len() > 1 => {
+                    self.compute_order(unit, x_min, y_min, x_max, y_max)
+                }
+                _ => vec![id],
+            })
+            .collect()
+    }
+}
+
+/// Area shared by the boxes of `a` and `b`
+fn overlap_area<T: BoundingBox>(a: &T, b: &T) -> f32 {
+    let (ax1, ay1, ax2, ay2) = a.bounds();
+    let (bx1, by1, bx2, by2) = b.bounds();
+    (ax2.min(bx2) - ax1.max(bx1)).max(0.0) * (ay2.min(by2) - ay1.max(by1)).max(0.0)
+}
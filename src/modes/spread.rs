@@ -0,0 +1,158 @@
+use crate::core::XYCutPlusPlus;
+use crate::histogram::{bin_coordinate, build_vertical_histogram, find_largest_gap};
+use crate::traits::BoundingBox;
+
+/// Central fraction of the image width searched for the gutter of a spread
+const GUTTER_SEARCH_BAND: f32 = 0.3;
+
+/// Order of the two physical pages of a spread
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PageSequence {
+    /// Left page first (left-to-right scripts)
+    #[default]
+    LeftToRight,
+    /// Right page first (right-to-left scripts, manga)
+    RightToLeft,
+}
+
+/// Reading order of a scanned two-up spread
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpreadOrder {
+    /// X-coordinate of the detected gutter, `None` if the image holds a single page
+    pub gutter: Option<f32>,
+
+    /// Reading order of each logical page, in page sequence
+    pub pages: Vec<Vec<usize>>,
+}
+
+impl SpreadOrder {
+    /// All pages concatenated in sequence
+    pub fn order(&self) -> Vec<usize> {
+        self.pages.concat()
+    }
+}
+
+impl XYCutPlusPlus {
+    /// Detect the gutter of a two-up spread: the widest element-free vertical strip
+    /// within the central band of the image
+    pub fn detect_gutter<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        x_max: f32,
+    ) -> Option<f32> {
+        let resolution = self.histogram_resolution(x_max - x_min);
+        if resolution == 0 {
+            return None;
+        }
+        let histogram = build_vertical_histogram(elements, x_min, x_max, resolution);
+
+        let band = (resolution as f32 * GUTTER_SEARCH_BAND / 2.0) as usize;
+        let start = resolution / 2 - band;
+        let end = (resolution / 2 + band).min(resolution);
+
+        let min_gap_bins =
+            (self.config.min_cut_threshold * self.config.histogram_resolution_scale) as usize;
+        let bin = find_largest_gap(&histogram[start..end], min_gap_bins)?;
+
+        Some(bin_coordinate(x_min, x_max, start + bin, resolution))
+    }
+
+    /// Spread mode: split a two-up scan at its gutter and order both pages sequentially
+    ///
+    /// Elements are assigned to a page by their center; each page is ordered on its own
+    /// with the gutter as page boundary. Without a detectable gutter the image is
+    /// ordered as a single page.
+    pub fn compute_spread_order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        sequence: PageSequence,
+    ) -> SpreadOrder {
+        let Some(gutter) = self.detect_gutter(elements, x_min, x_max) else {
+            return SpreadOrder {
+                gutter: None,
+                pages: vec![self.compute_order(elements, x_min, y_min, x_max, y_max)],
+            };
+        };
+
+        let (left, right): (Vec<T>, Vec<T>) = elements
+            .iter()
+            .cloned()
+            .partition(|e| e.center().0 < gutter);
+
+        let left_order = self.compute_order(&left, x_min, y_min, gutter, y_max);
+        let right_order = self.compute_order(&right, gutter, y_min, x_max, y_max);
+
+        let pages = match sequence {
+            PageSequence::LeftToRight => vec![left_order, right_order],
+            PageSequence::RightToLeft => vec![right_order, left_order],
+        };
+
+        SpreadOrder {
+            gutter: Some(gutter),
+            pages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    /// Two facing pages of three stacked paragraphs each, level with each other
+    fn spread() -> Vec<Element> {
+        [(0, 50.0), (3, 650.0)]
+            .into_iter()
+            .flat_map(|(first, x)| {
+                (0..3).map(move |i| {
+                    let y = 100.0 + i as f32 * 200.0;
+                    Element::new(
+                        first + i,
+                        x,
+                        y,
+                        x + 500.0,
+                        y + 180.0,
+                        SemanticLabel::Regular,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn order(sequence: PageSequence) -> SpreadOrder {
+        XYCutPlusPlus::new(XYCutConfig::default()).compute_spread_order(
+            &spread(),
+            0.0,
+            0.0,
+            1200.0,
+            800.0,
+            sequence,
+        )
+    }
+
+    #[test]
+    fn left_page_finishes_before_the_right_page_starts() {
+        let spread = order(PageSequence::LeftToRight);
+        let gutter = spread.gutter.expect("gutter between the pages");
+        assert!((550.0..650.0).contains(&gutter), "gutter at {gutter}");
+        assert_eq!(spread.pages, vec![vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!(spread.order(), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn right_to_left_spreads_start_on_the_right_page() {
+        assert_eq!(
+            order(PageSequence::RightToLeft).order(),
+            vec![3, 4, 5, 0, 1, 2]
+        );
+    }
+}
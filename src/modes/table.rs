@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::core::XYCutPlusPlus;
+use crate::element::Element;
+use crate::traits::{BoundingBox, SemanticLabel};
+use crate::utils::compare_position;
+
+/// Configuration for table mode
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TableConfig {
+    /// Minimum fraction of a box's area inside a table region for it to be a cell
+    pub min_inside: f32,
+
+    /// Minimum number of cells for a `Vision` box to be a table region
+    pub min_cells: usize,
+
+    /// Minimum overlap (fraction of the shorter extent) for a cell to share a row or
+    /// column with the cells already in it
+    pub min_alignment: f32,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            min_inside: 0.5,
+            min_cells: 2,
+            min_alignment: 0.5,
+        }
+    }
+}
+
+/// A cell's place in its table, counted from the top-left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableCell {
+    pub id: usize,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// A table region with its cells in row-major order
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Table {
+    /// Id of the table region's box
+    pub id: usize,
+    pub cells: Vec<TableCell>,
+    pub rows: usize,
+    pub columns: usize,
+}
+
+/// Reading order of a page in table mode, with the cell grid of each table
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableOrder {
+    pub order: Vec<usize>,
+    pub tables: Vec<Table>,
+}
+
+impl TableOrder {
+    /// Row and column of the cell with id `id`, if it lies in a table
+    pub fn cell(&self, id: usize) -> Option<(usize, usize)> {
+        self.tables
+            .iter()
+            .flat_map(|table| &table.cells)
+            .find(|cell| cell.id == id)
+            .map(|cell| (cell.row, cell.col))
+    }
+}
+
+/// Cluster table cells into rows and columns by alignment
+///
+/// Cells are assigned smallest first, so ordinary cells define the rows and
+/// columns: a cell joins the first row (column) whose extent it overlaps by
+/// `min_alignment` of the shorter of the two, or opens a new one. Cells spanning
+/// several rows or columns take the first. Returns the cells in row-major order.
+pub fn layout_table_cells<T: BoundingBox>(cells: &[T], config: &TableConfig) -> Vec<TableCell> {
+    let rows = bands(
+        &cells
+            .iter()
+            .map(|e| {
+                let (_, y1, _, y2) = e.bounds();
+                (y1, y2)
+            })
+            .collect::<Vec<_>>(),
+        config.min_alignment,
+    );
+    let columns = bands(
+        &cells
+            .iter()
+            .map(|e| {
+                let (x1, _, x2, _) = e.bounds();
+                (x1, x2)
+            })
+            .collect::<Vec<_>>(),
+        config.min_alignment,
+    );
+
+    let mut laid_out: Vec<(TableCell, &T)> = cells
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let cell = TableCell {
+                id: e.id(),
+                row: rows[i],
+                col: columns[i],
+            };
+            (cell, e)
+        })
+        .collect();
+    laid_out.sort_by(|(a, ea), (b, eb)| {
+        (a.row, a.col)
+            .cmp(&(b.row, b.col))
+            .then(compare_position(*ea, *eb))
+    });
+    laid_out.into_iter().map(|(cell, _)| cell).collect()
+}
+
+/// Band index of each extent, bands numbered from the lowest coordinate
+fn bands(extents: &[(f32, f32)], min_alignment: f32) -> Vec<usize> {
+    let mut by_size: Vec<usize> = (0..extents.len()).collect();
+    by_size.sort_by(|&a, &b| {
+        let size = |i: usize| extents[i].1 - extents[i].0;
+        size(a)
+            .total_cmp(&size(b))
+            .then(extents[a].0.total_cmp(&extents[b].0))
+            .then(a.cmp(&b))
+    });
+
+    let mut bands: Vec<(f32, f32)> = Vec::new();
+    let mut band_of = vec![0; extents.len()];
+    for i in by_size {
+        let (start, end) = extents[i];
+        let aligned = |&(band_start, band_end): &(f32, f32)| {
+            let overlap = end.min(band_end) - start.max(band_start);
+            let shorter = (end - start).min(band_end - band_start);
+            overlap > 0.0 && overlap >= min_alignment * shorter
+        };
+        let first = bands
+            .iter()
+            .enumerate()
+            .filter(|(_, band)| aligned(band))
+            .min_by(|(_, a), (_, b)| a.0.total_cmp(&b.0))
+            .map(|(band, _)| band);
+        band_of[i] = match first {
+            Some(band) => band,
+            None => {
+                bands.push((start, end));
+                bands.len() - 1
+            }
+        };
+    }
+
+    // Renumber the bands by position
+    let mut by_start: Vec<usize> = (0..bands.len()).collect();
+    by_start.sort_by(|&a, &b| bands[a].0.total_cmp(&bands[b].0).then(a.cmp(&b)));
+    let mut rank = vec![0; bands.len()];
+    for (position, band) in by_start.into_iter().enumerate() {
+        rank[band] = position;
+    }
+    band_of.into_iter().map(|band| rank[band]).collect()
+}
+
+/// Detect table regions: `Vision` boxes holding at least `min_cells` unmasked boxes,
+/// each with their cells laid out by [`layout_table_cells`]
+///
+/// A box lying in several table regions belongs to the first by position.
+pub fn detect_tables<T: BoundingBox>(elements: &[T], config: &TableConfig) -> Vec<Table> {
+    let mut regions: Vec<&T> = elements
+        .iter()
+        .filter(|e| e.semantic_label() == SemanticLabel::Vision)
+        .collect();
+    regions.sort_by(|a, b| compare_position(*a, *b));
+
+    let mut taken: Vec<usize> = regions.iter().map(|r| r.id()).collect();
+    let mut tables = Vec::new();
+    for region in regions {
+        let (rx1, ry1, rx2, ry2) = region.bounds();
+        let cells: Vec<T> = elements
+            .iter()
+            .filter(|e| !e.should_mask() && !taken.contains(&e.id()))
+            .filter(|e| {
+                let (x1, y1, x2, y2) = e.bounds();
+                let inside =
+                    (x2.min(rx2) - x1.max(rx1)).max(0.0) * (y2.min(ry2) - y1.max(ry1)).max(0.0);
+                let area = (x2 - x1) * (y2 - y1);
+                area > 0.0 && inside >= config.min_inside * area
+            })
+            .cloned()
+            .collect();
+        if cells.len() < config.min_cells.max(1) {
+            continue;
+        }
+        taken.extend(cells.iter().map(|e| e.id()));
+        let cells = layout_table_cells(&cells, config);
+        tables.push(Table {
+            id: region.id(),
+            rows: cells.iter().map(|c| c.row + 1).max().unwrap_or(0),
+            columns: cells.iter().map(|c| c.col + 1).max().unwrap_or(0),
+            cells,
+        });
+    }
+    tables
+}
+
+impl XYCutPlusPlus {
+    /// Table mode: read each table region's cells row by row, right after the region
+    ///
+    /// Table regions (see [`detect_tables`]) are merged with their cells into one
+    /// unit before ordering, so no cut separates a table's cells, then expanded as
+    /// the region's box followed by its cells in row-major order. The (row, column)
+    /// of every cell is returned alongside, so invoice extraction gets the same cell
+    /// sequence whatever the detector's box order.
+    pub fn compute_table_order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        table: &TableConfig,
+    ) -> TableOrder {
+        let tables = detect_tables(elements, table);
+        let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+        let cells_of: HashMap<usize, &Table> = tables.iter().map(|t| (t.id, t)).collect();
+        let cells: HashSet<usize> = tables
+            .iter()
+            .flat_map(|t| t.cells.iter().map(|c| c.id))
+            .collect();
+
+        // One unit per table (keyed by the region id) plus every element outside tables
+        let units: Vec<Element> = elements
+            .iter()
+            .filter(|e| !cells.contains(&e.id()))
+            .map(|e| {
+                let mut unit = Element::from_box(e);
+                for cell in cells_of.get(&e.id()).into_iter().flat_map(|t| &t.cells) {
+                    unit.extend(by_id[&cell.id]);
+                }
+                unit
+            })
+            .collect();
+
+        let order = self
+            .compute_order(&units, x_min, y_min, x_max, y_max)
+            .into_iter()
+            .flat_map(|id| {
+                let cells = cells_of.get(&id).into_iter().flat_map(|t| &t.cells);
+                std::iter::once(id).chain(cells.map(|c| c.id))
+            })
+            .collect();
+        TableOrder { order, tables }
+    }
+}
@@ -0,0 +1,79 @@
+//! Callbacks into the ordering pipeline, for metrics and live visualization
+
+/// Axis a cut runs along
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Axis {
+    /// Vertical cut line at an x coordinate (columns)
+    X,
+    /// Horizontal cut line at a y coordinate (rows)
+    Y,
+}
+
+/// How the recursive cut divided a region
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cut {
+    /// Projection cut through an empty gap, or through lightly covered bins when
+    /// `tolerant` (see `cut_overlap_tolerance`)
+    Projection {
+        axis: Axis,
+        position: f32,
+        tolerant: bool,
+    },
+    /// Region cut after setting this element aside (see `region_subtraction`)
+    Obstacle(usize),
+}
+
+/// Where a masked element was reinserted into the order
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Insertion {
+    /// Id of the masked element
+    pub id: usize,
+
+    /// Index in the order at insertion time, `None` when appended without a match
+    pub position: Option<usize>,
+
+    /// Id of the element it was inserted before
+    pub before: Option<usize>,
+
+    /// Distance to that element (Equation 8); infinite without a valid anchor, zero
+    /// for a display formula placed next to its text
+    pub distance: f32,
+}
+
+/// Receives the decisions of [`compute_order_observed`](crate::XYCutPlusPlus::compute_order_observed)
+/// as they are taken
+///
+/// All methods default to doing nothing. Coordinates are in the frame the page is
+/// cut in, which differs from the input frame for rotated or deskewed pages.
+pub trait OrderingObserver {
+    /// The pre-mask partition split the page into regular and masked element ids
+    fn on_partition(&mut self, regular: &[usize], masked: &[usize]) {
+        let _ = (regular, masked);
+    }
+
+    /// The density of the region holding `ids` was estimated (τd of Equation 4 by
+    /// default, see `XYCutConfig::density`); above `vertical_first_threshold` the
+    /// region is cut vertically first
+    fn on_density(&mut self, density: f32, ids: &[usize]) {
+        let _ = (density, ids);
+    }
+
+    /// The region holding `ids` was divided by `cut`
+    fn on_cut(&mut self, cut: &Cut, ids: &[usize]) {
+        let _ = (cut, ids);
+    }
+
+    /// No cut was found; `order` is the region ordered by position
+    fn on_fallback_sort(&mut self, order: &[usize]) {
+        let _ = order;
+    }
+
+    /// A masked element was reinserted, or moved by the re-evaluation of
+    /// [`InsertionOrder::Dependency`](crate::InsertionOrder::Dependency)
+    fn on_insertion(&mut self, insertion: &Insertion) {
+        let _ = insertion;
+    }
+}
@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use crate::text::{ends_with_hyphenation, list_marker};
+use crate::traits::{BoundingBox, TextContent};
+use crate::utils::compute_median_height;
+
+/// Configuration for line-to-paragraph aggregation
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ParagraphConfig {
+    /// Maximum vertical gap between consecutive lines, as a multiple of the median line height
+    pub max_line_gap_ratio: f32,
+
+    /// Horizontal offset (pixels) of a line start that marks a first-line indent
+    pub indent_threshold: f32,
+
+    /// Minimum horizontal overlap (fraction of the narrower line) for two lines to share a column
+    pub min_column_overlap: f32,
+}
+
+impl Default for ParagraphConfig {
+    fn default() -> Self {
+        Self {
+            max_line_gap_ratio: 0.8,
+            indent_threshold: 8.0,
+            min_column_overlap: 0.5,
+        }
+    }
+}
+
+/// Reason a line starts a new paragraph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Boundary {
+    /// Line moved to another column
+    Column,
+    /// Line moved upwards or left a large vertical gap
+    Gap,
+    /// Line starts with a first-line indent
+    Indent,
+}
+
+/// Merge consecutive lines of a computed reading order into paragraphs
+///
+/// Walks `order` and starts a new paragraph whenever the next line:
+/// - changes column (insufficient horizontal overlap with the previous line),
+/// - moves upwards or leaves a vertical gap larger than `max_line_gap_ratio` line heights,
+/// - is indented relative to the paragraph's left edge (first-line indent).
+///
+/// Masked elements (titles, figures, tables) always form their own group.
+/// Ids in `order` that don't belong to `elements` are skipped.
+pub fn group_paragraphs<T: BoundingBox>(
+    elements: &[T],
+    order: &[usize],
+    config: &ParagraphConfig,
+) -> Vec<Vec<usize>> {
+    group_lines(elements, order, config, |_, _, _, boundary| {
+        boundary.is_none()
+    })
+}
+
+/// Content-aware variant of [`group_paragraphs`]
+///
+/// On top of the geometric rules:
+/// - a line ending in a hyphenated word always continues into the next line,
+///   even across a column change,
+/// - every list item ("1.", "•", "(a)") starts its own paragraph,
+/// - hanging-indented continuation lines stay with their list item.
+pub fn group_paragraphs_with_text<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+    config: &ParagraphConfig,
+) -> Vec<Vec<usize>> {
+    group_lines(elements, order, config, |first, prev, line, boundary| {
+        if list_marker(line.text()).is_some() {
+            return false;
+        }
+        if ends_with_hyphenation(prev.text()) {
+            return true;
+        }
+
+        match boundary {
+            None => true,
+            Some(Boundary::Indent) => list_marker(first.text()).is_some(),
+            Some(_) => false,
+        }
+    })
+}
+
+/// Shared grouping loop; `continues(first, prev, line, boundary)` decides whether
+/// `line` joins the current paragraph given the geometric boundary found, if any
+fn group_lines<T, F>(
+    elements: &[T],
+    order: &[usize],
+    config: &ParagraphConfig,
+    continues: F,
+) -> Vec<Vec<usize>>
+where
+    T: BoundingBox,
+    F: Fn(&T, &T, &T, Option<Boundary>) -> bool,
+{
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+    let line_height = compute_median_height(elements);
+
+    let mut paragraphs: Vec<Vec<usize>> = Vec::new();
+    let mut current: Vec<usize> = Vec::new();
+    let mut first: Option<&T> = None;
+    let mut previous: Option<&T> = None;
+    let mut paragraph_left = 0.0;
+
+    for id in order {
+        let Some(&line) = by_id.get(id) else {
+            continue;
+        };
+
+        let joins = match (first, previous) {
+            (Some(first), Some(prev)) if !prev.should_mask() && !line.should_mask() => {
+                let boundary = paragraph_boundary(prev, line, paragraph_left, line_height, config);
+                continues(first, prev, line, boundary)
+            }
+            _ => false,
+        };
+
+        if !joins && !current.is_empty() {
+            paragraphs.push(std::mem::take(&mut current));
+        }
+        if current.is_empty() {
+            first = Some(line);
+            paragraph_left = line.bounds().0;
+        } else {
+            // An indented first line doesn't define the paragraph's left edge
+            paragraph_left = paragraph_left.min(line.bounds().0);
+        }
+
+        current.push(line.id());
+        previous = Some(line);
+    }
+
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+
+    paragraphs
+}
+
+/// Find the geometric boundary, if any, between `prev` and the following `line`
+fn paragraph_boundary<T: BoundingBox>(
+    prev: &T,
+    line: &T,
+    paragraph_left: f32,
+    line_height: f32,
+    config: &ParagraphConfig,
+) -> Option<Boundary> {
+    let (px1, py1, px2, py2) = prev.bounds();
+    let (lx1, ly1, lx2, _) = line.bounds();
+
+    // Column assignment: lines must overlap horizontally
+    let overlap = (px2.min(lx2) - px1.max(lx1)).max(0.0);
+    let narrower = (px2 - px1).min(lx2 - lx1);
+    if narrower <= 0.0 || overlap / narrower < config.min_column_overlap {
+        return Some(Boundary::Column);
+    }
+
+    // Vertical spacing: next line must be below and close to the previous one
+    if ly1 < py1 {
+        return Some(Boundary::Gap);
+    }
+    let gap = ly1 - py2;
+    if gap > config.max_line_gap_ratio * line_height {
+        return Some(Boundary::Gap);
+    }
+
+    // Indentation: a line starting right of the paragraph edge opens a new paragraph
+    if lx1 - paragraph_left > config.indent_threshold {
+        return Some(Boundary::Indent);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::Element;
+    use crate::testing::line;
+    use crate::traits::SemanticLabel;
+
+    /// Regular 10-pixel line `id` on row `row` (14 pixels apart), from `x1` to `x2`
+    fn row(id: usize, row: usize, x1: f32, x2: f32) -> Element {
+        let y = row as f32 * 14.0;
+        Element::new(id, x1, y, x2, y + 10.0, SemanticLabel::Regular)
+    }
+
+    fn group(elements: &[Element], order: &[usize]) -> Vec<Vec<usize>> {
+        group_paragraphs(elements, order, &ParagraphConfig::default())
+    }
+
+    #[test]
+    fn close_lines_of_a_column_form_one_paragraph() {
+        let lines = [
+            row(0, 0, 0.0, 200.0),
+            row(1, 1, 0.0, 200.0),
+            row(2, 2, 0.0, 120.0),
+        ];
+        assert_eq!(group(&lines, &[0, 1, 2]), vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn large_gap_starts_a_paragraph() {
+        let lines = [
+            row(0, 0, 0.0, 200.0),
+            row(1, 1, 0.0, 200.0),
+            row(2, 3, 0.0, 200.0),
+        ];
+        assert_eq!(group(&lines, &[0, 1, 2]), vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn column_change_starts_a_paragraph() {
+        // The second column starts back at the top of the page
+        let lines = [
+            row(0, 0, 0.0, 200.0),
+            row(1, 1, 0.0, 200.0),
+            row(2, 0, 250.0, 450.0),
+            row(3, 1, 250.0, 450.0),
+        ];
+        assert_eq!(group(&lines, &[0, 1, 2, 3]), vec![vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn first_line_indent_starts_a_paragraph() {
+        let lines = [
+            row(0, 0, 20.0, 200.0),
+            row(1, 1, 0.0, 200.0),
+            row(2, 2, 20.0, 200.0),
+            row(3, 3, 0.0, 200.0),
+        ];
+        assert_eq!(group(&lines, &[0, 1, 2, 3]), vec![vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn masked_elements_stand_alone() {
+        let mut title = row(1, 1, 0.0, 200.0);
+        title.mask = true;
+        let lines = [row(0, 0, 0.0, 200.0), title, row(2, 2, 0.0, 200.0)];
+        assert_eq!(group(&lines, &[0, 1, 2]), vec![vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn unknown_ids_are_skipped() {
+        let lines = [row(0, 0, 0.0, 200.0), row(1, 1, 0.0, 200.0)];
+        assert_eq!(group(&lines, &[0, 7, 1]), vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn list_items_start_paragraphs_and_keep_hanging_lines() {
+        let lines = [
+            line(0, (0.0, 0.0, 200.0, 10.0), "1. First item that wraps"),
+            line(1, (20.0, 14.0, 200.0, 24.0), "onto a hanging line"),
+            line(2, (0.0, 28.0, 200.0, 38.0), "2. Second item"),
+        ];
+        let config = ParagraphConfig::default();
+        assert_eq!(
+            group_paragraphs_with_text(&lines, &[0, 1, 2], &config),
+            vec![vec![0, 1], vec![2]]
+        );
+        // Without text, the hanging line reads as a first-line indent
+        assert_eq!(
+            group_paragraphs(&lines, &[0, 1, 2], &config),
+            vec![vec![0], vec![1, 2]]
+        );
+    }
+
+    #[test]
+    fn hyphenated_line_continues_into_the_next_column() {
+        let lines = [
+            line(0, (0.0, 0.0, 200.0, 10.0), "a sentence broken by hyphen-"),
+            line(1, (250.0, 0.0, 450.0, 10.0), "ation at the column end"),
+        ];
+        let config = ParagraphConfig::default();
+        assert_eq!(
+            group_paragraphs_with_text(&lines, &[0, 1], &config),
+            vec![vec![0, 1]]
+        );
+        assert_eq!(
+            group_paragraphs(&lines, &[0, 1], &config),
+            vec![vec![0], vec![1]]
+        );
+    }
+}
@@ -0,0 +1,109 @@
+//! Ordering async streams of pages, for services fed from object stores or queues
+
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+
+use futures_util::{Stream, StreamExt};
+
+use crate::core::XYCutPlusPlus;
+use crate::traits::BoundingBox;
+
+/// A page of a stream: its elements and bounds `(x_min, y_min, x_max, y_max)`
+pub type OwnedPageInput<T> = (Vec<T>, (f32, f32, f32, f32));
+
+/// An ordered page, with its elements handed back
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageResult<T> {
+    pub elements: Vec<T>,
+    pub order: Vec<usize>,
+}
+
+impl XYCutPlusPlus {
+    /// Order a stream of pages with at most `concurrency` pages in flight; results
+    /// come out in input order
+    ///
+    /// Pages are ordered on the rayon thread pool and the stream only awaits them, so
+    /// executor threads are never blocked by the CPU work.
+    pub fn order_stream<T, S>(
+        &self,
+        pages: S,
+        concurrency: usize,
+    ) -> impl Stream<Item = PageResult<T>>
+    where
+        T: BoundingBox + Send + 'static,
+        S: Stream<Item = OwnedPageInput<T>>,
+    {
+        let xycut = Arc::new(XYCutPlusPlus::new(self.config.clone()));
+        pages
+            .map(move |(elements, bounds)| order_page(Arc::clone(&xycut), elements, bounds))
+            .buffered(concurrency.max(1))
+    }
+}
+
+/// Order one page on the rayon thread pool
+async fn order_page<T: BoundingBox + Send + 'static>(
+    xycut: Arc<XYCutPlusPlus>,
+    elements: Vec<T>,
+    bounds: (f32, f32, f32, f32),
+) -> PageResult<T> {
+    let (sender, receiver) = futures_channel::oneshot::channel();
+    rayon::spawn(move || {
+        // A panic is handed to the awaiting task instead of aborting the pool
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let (x_min, y_min, x_max, y_max) = bounds;
+            let order = xycut.compute_order(&elements, x_min, y_min, x_max, y_max);
+            PageResult { elements, order }
+        }));
+        let _ = sender.send(result);
+    });
+    match receiver.await {
+        Ok(Ok(result)) => result,
+        Ok(Err(panic)) => resume_unwind(panic),
+        Err(_) => unreachable!("ordering task dropped its result"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    /// A page of `lines` stacked lines, ids starting at `first`
+    fn page(first: usize, lines: usize) -> OwnedPageInput<Element> {
+        let elements = (0..lines)
+            .map(|i| {
+                let y = 10.0 + i as f32 * 30.0;
+                Element::new(first + i, 50.0, y, 550.0, y + 20.0, SemanticLabel::Regular)
+            })
+            .collect();
+        (elements, (0.0, 0.0, 600.0, 10.0 + lines as f32 * 30.0))
+    }
+
+    #[test]
+    fn results_keep_page_order_with_pages_in_flight() {
+        // The large first page finishes last
+        let pages: Vec<_> = [2000, 3, 50, 1, 400, 7]
+            .iter()
+            .enumerate()
+            .map(|(i, &lines)| page(i * 10_000, lines))
+            .collect();
+        let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+
+        let results: Vec<PageResult<Element>> = futures_executor::block_on(
+            xycut
+                .order_stream(futures_util::stream::iter(pages.clone()), 4)
+                .collect(),
+        );
+
+        assert_eq!(results.len(), pages.len());
+        for (result, (elements, (x1, y1, x2, y2))) in results.iter().zip(&pages) {
+            assert_eq!(result.elements, *elements);
+            assert_eq!(
+                result.order,
+                xycut.compute_order(elements, *x1, *y1, *x2, *y2)
+            );
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Partial reading order: only the precedences the algorithm is confident about
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::XYCutPlusPlus;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+/// Directed acyclic graph of precedence relations between elements
+///
+/// Elements separated by a cut are ordered; elements that were only ordered by the
+/// fallback position sort (no cut separated them) are left unordered. The order is
+/// split into blocks of mutually unordered elements, and every member of a block
+/// has an edge to every member of the next block, so two adjacent blocks of `k` and
+/// `m` elements contribute `k * m` edges. Precedence between blocks further apart is
+/// implied: `a` precedes `b` when `b` is reachable from `a`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrecedenceGraph {
+    /// Element ids in the order produced by [`XYCutPlusPlus::compute_order`]
+    pub nodes: Vec<usize>,
+
+    /// `(before, after)` pairs
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl PrecedenceGraph {
+    /// Build the graph from a total order and the groups within it that are unordered
+    ///
+    /// Each group is widened to the contiguous run of the order between its first and
+    /// last member, so elements inserted into it (masked elements, drop caps) join it.
+    pub fn from_order(order: &[usize], unordered: &[Vec<usize>]) -> Self {
+        let position: HashMap<usize, usize> =
+            order.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+        // Runs of positions [start, end] that are mutually unordered, merged when overlapping
+        let mut runs: Vec<(usize, usize)> = unordered
+            .iter()
+            .filter_map(|group| {
+                let positions = group.iter().filter_map(|id| position.get(id).copied());
+                let start = positions.clone().min()?;
+                let end = positions.max()?;
+                (end > start).then_some((start, end))
+            })
+            .collect();
+        runs.sort_unstable();
+        let mut merged: Vec<(usize, usize)> = Vec::new();
+        for (start, end) in runs {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        // Consecutive blocks of the order; every member of a block precedes the next block
+        let mut blocks: Vec<&[usize]> = Vec::new();
+        let mut i = 0;
+        let mut runs = merged.into_iter().peekable();
+        while i < order.len() {
+            let end = match runs.peek() {
+                Some(&(start, end)) if start == i => {
+                    runs.next();
+                    end + 1
+                }
+                _ => i + 1,
+            };
+            blocks.push(&order[i..end]);
+            i = end;
+        }
+
+        let edges = blocks
+            .windows(2)
+            .flat_map(|pair| {
+                pair[0]
+                    .iter()
+                    .flat_map(move |&a| pair[1].iter().map(move |&b| (a, b)))
+            })
+            .collect();
+
+        Self {
+            nodes: order.to_vec(),
+            edges,
+        }
+    }
+
+    /// Whether `a` is known to precede `b`
+    pub fn precedes(&self, a: usize, b: usize) -> bool {
+        let mut successors: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &(from, to) in &self.edges {
+            successors.entry(from).or_default().push(to);
+        }
+
+        let mut stack = vec![a];
+        let mut seen = HashSet::new();
+        while let Some(node) = stack.pop() {
+            for &next in successors.get(&node).into_iter().flatten() {
+                if next == b {
+                    return true;
+                }
+                if seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        false
+    }
+
+    /// Topological linearization; unordered elements keep their relative order in `nodes`
+    pub fn linearize(&self) -> Vec<usize> {
+        let rank: HashMap<usize, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i))
+            .collect();
+        let mut indegree: HashMap<usize, usize> = self.nodes.iter().map(|&id| (id, 0)).collect();
+        let mut successors: HashMap<usize, Vec<usize>> = HashMap::new();
+        for &(a, b) in &self.edges {
+            *indegree.entry(b).or_default() += 1;
+            successors.entry(a).or_default().push(b);
+        }
+
+        // Kahn's algorithm, always taking the ready node earliest in `nodes`
+        let mut ready: std::collections::BTreeSet<(usize, usize)> = indegree
+            .iter()
+            .filter(|&(_, &d)| d == 0)
+            .map(|(&id, _)| (rank.get(&id).copied().unwrap_or(usize::MAX), id))
+            .collect();
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(next) = ready.pop_first() {
+            let id = next.1;
+            order.push(id);
+            for &succ in successors.get(&id).into_iter().flatten() {
+                let degree = indegree.entry(succ).or_default();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.insert((rank.get(&succ).copied().unwrap_or(usize::MAX), succ));
+                }
+            }
+        }
+        order
+    }
+}
+
+impl XYCutPlusPlus {
+    /// Compute the partial reading order the cuts establish
+    ///
+    /// See [`PrecedenceGraph`]; [`PrecedenceGraph::linearize`] returns the same
+    /// order as [`compute_order`](Self::compute_order).
+    pub fn compute_precedence<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> PrecedenceGraph {
+        let mut trace = Trace::default();
+        let order = self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace);
+        PrecedenceGraph::from_order(&order, &trace.fallback_groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    #[test]
+    fn unordered_groups_become_blocks() {
+        let graph = PrecedenceGraph::from_order(&[0, 1, 2, 3, 4], &[vec![1, 2]]);
+        assert_eq!(graph.edges, vec![(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)]);
+
+        assert!(graph.precedes(0, 4));
+        assert!(graph.precedes(1, 3));
+        assert!(!graph.precedes(1, 2));
+        assert!(!graph.precedes(2, 1));
+        assert!(!graph.precedes(4, 0));
+        assert!(!graph.precedes(3, 3));
+        assert_eq!(graph.linearize(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn groups_widen_to_their_run_of_the_order() {
+        // 2 was inserted between the members of the group, so it joins it
+        let graph = PrecedenceGraph::from_order(&[0, 1, 2, 3, 4], &[vec![3, 1]]);
+        assert!(graph.precedes(0, 2));
+        assert!(!graph.precedes(1, 2));
+        assert!(!graph.precedes(2, 3));
+        assert!(graph.precedes(3, 4));
+    }
+
+    #[test]
+    fn linearization_matches_compute_order() {
+        let page = [
+            Element::new(0, 100.0, 50.0, 500.0, 90.0, SemanticLabel::Regular),
+            // Two overlapping boxes no cut can separate
+            Element::new(1, 100.0, 150.0, 300.0, 250.0, SemanticLabel::Regular),
+            Element::new(2, 200.0, 200.0, 400.0, 300.0, SemanticLabel::Regular),
+            Element::new(3, 100.0, 400.0, 500.0, 500.0, SemanticLabel::Regular),
+        ];
+        let engine = XYCutPlusPlus::new(XYCutConfig::default());
+        let graph = engine.compute_precedence(&page, 0.0, 0.0, 600.0, 600.0);
+
+        assert_eq!(
+            graph.linearize(),
+            engine.compute_order(&page, 0.0, 0.0, 600.0, 600.0)
+        );
+        assert!(graph.precedes(0, 3));
+        assert!(!graph.precedes(1, 2) && !graph.precedes(2, 1));
+    }
+}
@@ -0,0 +1,60 @@
+//! Caller-steered axis preference per region
+
+use crate::core::XYCutPlusPlus;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+/// Which axis a region is cut along first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CutPreference {
+    /// Decide by the density rule (Equation 5)
+    #[default]
+    Auto,
+    /// Try a vertical cut first (columns before rows)
+    VerticalFirst,
+    /// Try a horizontal cut first (rows before columns)
+    HorizontalFirst,
+}
+
+/// A region about to be cut, as seen by a cut-preference callback
+#[derive(Debug, Clone, Copy)]
+pub struct RegionStats<'a> {
+    /// Region bounds, in the frame the page is cut in
+    pub bounds: (f32, f32, f32, f32),
+
+    /// Ids of the region's elements
+    pub ids: &'a [usize],
+
+    /// Nesting depth of the recursive cut, 0 for the page
+    pub depth: usize,
+
+    /// Density the rule would compare against `vertical_first_threshold`
+    pub density: f32,
+}
+
+impl XYCutPlusPlus {
+    /// [`compute_order`](Self::compute_order), asking `preference` which axis to cut
+    /// each region along first
+    ///
+    /// The callback is consulted before the density rule of Equation 5, so callers
+    /// with page-class knowledge ("this is a table page", "this is a newspaper") can
+    /// force the order of rows and columns where it matters and return
+    /// [`CutPreference::Auto`] elsewhere. Ignored by Classic XY-Cut, which always
+    /// cuts horizontally first.
+    pub fn compute_order_with_cut_preference<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        preference: &dyn Fn(&RegionStats) -> CutPreference,
+    ) -> Vec<usize> {
+        let mut trace = Trace {
+            cut_preference: Some(preference),
+            ..Trace::default()
+        };
+        self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace)
+    }
+}
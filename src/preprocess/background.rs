@@ -0,0 +1,29 @@
+use crate::traits::BoundingBox;
+
+/// Ids of page-sized "background" or "page" boxes
+///
+/// A box is background when it covers more than `coverage` of the
+/// `(x_min, y_min, x_max, y_max)` page area. Such boxes fill every histogram bin
+/// and would prevent all cuts.
+pub fn detect_background<T: BoundingBox>(
+    elements: &[T],
+    page: (f32, f32, f32, f32),
+    coverage: f32,
+) -> Vec<usize> {
+    let (px1, py1, px2, py2) = page;
+    let page_area = (px2 - px1) * (py2 - py1);
+    if page_area <= 0.0 {
+        return Vec::new();
+    }
+
+    elements
+        .iter()
+        .filter(|e| {
+            let (x1, y1, x2, y2) = e.bounds();
+            let width = (x2.min(px2) - x1.max(px1)).max(0.0);
+            let height = (y2.min(py2) - y1.max(py1)).max(0.0);
+            width * height / page_area > coverage
+        })
+        .map(|e| e.id())
+        .collect()
+}
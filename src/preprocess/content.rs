@@ -0,0 +1,69 @@
+use crate::traits::BoundingBox;
+
+/// Estimate the content area of a page, ignoring outlying boxes near its edges
+///
+/// Each edge is the `trim` quantile of the corresponding box edges (the `1 - trim`
+/// quantile for right and bottom), so up to a `trim` fraction of the elements may
+/// stick out on each side: noise specks, page numbers, scanner borders.
+/// Returns `None` for an empty input.
+pub fn estimate_content_area<T: BoundingBox>(
+    elements: &[T],
+    trim: f32,
+) -> Option<(f32, f32, f32, f32)> {
+    if elements.is_empty() {
+        return None;
+    }
+
+    let quantile = |edge: fn(&T) -> f32, q: f32| {
+        let mut values: Vec<f32> = elements.iter().map(edge).collect();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let index = (q.clamp(0.0, 1.0) * (values.len() - 1) as f32).round() as usize;
+        values[index]
+    };
+    let trim = trim.clamp(0.0, 0.5);
+
+    let x1 = quantile(|e| e.bounds().0, trim);
+    let y1 = quantile(|e| e.bounds().1, trim);
+    let x2 = quantile(|e| e.bounds().2, 1.0 - trim);
+    let y2 = quantile(|e| e.bounds().3, 1.0 - trim);
+
+    (x1 < x2 && y1 < y2).then_some((x1, y1, x2, y2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    #[test]
+    fn header_footer_and_scanner_border_are_trimmed() {
+        let mut page: Vec<Element> = (0..16)
+            .map(|i| {
+                let y = 100.0 + i as f32 * 50.0;
+                Element::new(i, 100.0, y, 500.0, y + 30.0, SemanticLabel::Regular)
+            })
+            .collect();
+        page.extend([
+            // Running header, page number and the dark strip at the scan's edge
+            Element::new(16, 100.0, 10.0, 500.0, 30.0, SemanticLabel::Regular),
+            Element::new(17, 280.0, 970.0, 320.0, 990.0, SemanticLabel::Regular),
+            Element::new(18, 0.0, 0.0, 5.0, 1000.0, SemanticLabel::Regular),
+        ]);
+
+        assert_eq!(
+            estimate_content_area(&page, 0.1),
+            Some((100.0, 100.0, 500.0, 880.0))
+        );
+        // Without trimming the outliers define the area
+        assert_eq!(
+            estimate_content_area(&page, 0.0),
+            Some((0.0, 0.0, 500.0, 1000.0))
+        );
+    }
+
+    #[test]
+    fn empty_page_has_no_content_area() {
+        assert_eq!(estimate_content_area::<Element>(&[], 0.1), None);
+    }
+}
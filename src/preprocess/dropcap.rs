@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+use crate::element::Element;
+use crate::traits::BoundingBox;
+
+/// Maximum area of a drop cap or run-in heading relative to the text it overlaps
+const MAX_AREA_RATIO: f32 = 0.25;
+
+/// Minimum fraction of the drop cap's area overlapping text
+const MIN_OVERLAP_RATIO: f32 = 0.3;
+
+/// Detect drop caps and run-in headings: small unmasked boxes largely overlapping
+/// unmasked text, positioned at the start (top-left corner) of the first text box they overlap
+///
+/// Works with both paragraph-level boxes and line-level boxes, where a drop cap
+/// spans several lines and is attached to the first one.
+/// Returns `(drop_cap_id, block_id)` pairs.
+pub fn detect_drop_caps<T: BoundingBox>(elements: &[T]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+
+    for candidate in elements.iter().filter(|e| !e.should_mask()) {
+        let (cx1, cy1, cx2, cy2) = candidate.bounds();
+        let area = (cx2 - cx1) * (cy2 - cy1);
+        if area <= 0.0 {
+            continue;
+        }
+
+        let mut overlap_area = 0.0;
+        let mut hosts: Vec<&T> = Vec::new();
+        for block in elements {
+            if block.id() == candidate.id() || block.should_mask() {
+                continue;
+            }
+            let (bx1, by1, bx2, by2) = block.bounds();
+            let x_overlap = (cx2.min(bx2) - cx1.max(bx1)).max(0.0);
+            let y_overlap = (cy2.min(by2) - cy1.max(by1)).max(0.0);
+            if x_overlap * y_overlap > 0.0 {
+                overlap_area += x_overlap * y_overlap;
+                hosts.push(block);
+            }
+        }
+        if hosts.is_empty() || overlap_area / area < MIN_OVERLAP_RATIO {
+            continue;
+        }
+
+        // The text the drop cap belongs to starts at the topmost host
+        let Some(block) = hosts.iter().copied().min_by(|a, b| {
+            a.bounds()
+                .1
+                .total_cmp(&b.bounds().1)
+                .then(a.id().cmp(&b.id()))
+        }) else {
+            continue;
+        };
+        let (hx1, hy1, hx2, hy2) = hosts.iter().map(|h| h.bounds()).fold(
+            (
+                f32::INFINITY,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                f32::NEG_INFINITY,
+            ),
+            |(a, b, c, d), (x1, y1, x2, y2)| (a.min(x1), b.min(y1), c.max(x2), d.max(y2)),
+        );
+
+        let (bx1, by1, _, _) = block.bounds();
+        let is_small = area <= MAX_AREA_RATIO * (hx2 - hx1) * (hy2 - hy1);
+        let at_block_start =
+            (cx1 - bx1).abs() <= cx2 - cx1 && (cy1 - by1).abs() <= (cy2 - cy1) / 2.0;
+
+        if is_small && at_block_start {
+            pairs.push((candidate.id(), block.id()));
+        }
+    }
+
+    pairs
+}
+
+/// Merge every detected drop cap into its text block
+///
+/// Returns the merged units and a map from block id to the drop cap id it absorbed,
+/// or `None` when nothing was detected.
+pub(crate) fn merge_drop_caps<T: BoundingBox>(
+    elements: &[T],
+) -> Option<(Vec<Element>, HashMap<usize, usize>)> {
+    let pairs = detect_drop_caps(elements);
+    if pairs.is_empty() {
+        return None;
+    }
+
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+    let mut drop_cap_of: HashMap<usize, usize> = HashMap::new();
+    for (drop_cap, block) in pairs {
+        // A block absorbs at most one drop cap and merged elements don't chain
+        let involved = drop_cap_of.contains_key(&block)
+            || drop_cap_of.contains_key(&drop_cap)
+            || drop_cap_of.values().any(|&d| d == drop_cap || d == block);
+        if !involved {
+            drop_cap_of.insert(block, drop_cap);
+        }
+    }
+    let absorbed: Vec<usize> = drop_cap_of.values().copied().collect();
+
+    let units = elements
+        .iter()
+        .filter(|e| !absorbed.contains(&e.id()))
+        .map(|e| {
+            let mut unit = Element::from_box(e);
+            if let Some(drop_cap) = drop_cap_of.get(&e.id()) {
+                unit.extend(by_id[drop_cap]);
+            }
+            unit
+        })
+        .collect();
+
+    Some((units, drop_cap_of))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{XYCutConfig, XYCutPlusPlus};
+    use crate::traits::SemanticLabel;
+
+    fn paragraph() -> Element {
+        Element::new(0, 100.0, 100.0, 500.0, 300.0, SemanticLabel::Regular)
+    }
+
+    #[test]
+    fn initial_at_the_paragraph_start_is_a_drop_cap() {
+        let page = [
+            paragraph(),
+            Element::new(1, 100.0, 100.0, 160.0, 170.0, SemanticLabel::Regular),
+        ];
+        assert_eq!(detect_drop_caps(&page), vec![(1, 0)]);
+    }
+
+    #[test]
+    fn masked_boxes_and_boxes_inside_the_text_are_not_drop_caps() {
+        let page = [
+            paragraph(),
+            // An inline figure in the corner of the paragraph
+            Element::new(1, 100.0, 100.0, 160.0, 170.0, SemanticLabel::Vision),
+            // A small box in the middle of the paragraph
+            Element::new(2, 280.0, 180.0, 340.0, 250.0, SemanticLabel::Regular),
+        ];
+        assert!(detect_drop_caps(&page).is_empty());
+    }
+
+    #[test]
+    fn merged_drop_cap_is_read_right_before_its_paragraph() {
+        let page = [
+            Element::new(0, 100.0, 180.0, 500.0, 400.0, SemanticLabel::Regular),
+            Element::new(1, 100.0, 180.0, 160.0, 250.0, SemanticLabel::Regular),
+            Element::new(2, 100.0, 50.0, 500.0, 150.0, SemanticLabel::Regular),
+        ];
+        let config = XYCutConfig {
+            merge_drop_caps: true,
+            ..XYCutConfig::default()
+        };
+        let order = XYCutPlusPlus::new(config).compute_order(&page, 0.0, 0.0, 600.0, 500.0);
+        assert_eq!(order, vec![2, 1, 0]);
+    }
+}
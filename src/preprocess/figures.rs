@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use crate::element::Element;
+use crate::traits::{BoundingBox, SemanticLabel};
+use crate::utils::compare_position;
+
+/// Maximum gap between a figure (or its last attachment) and a caption, relative to
+/// the figure's height
+const CAPTION_GAP_RATIO: f32 = 0.1;
+
+/// Maximum height of a caption or footnote relative to the figure's height
+const CAPTION_HEIGHT_RATIO: f32 = 0.5;
+
+/// Minimum fraction of a caption's width within the figure's horizontal extent
+const CAPTION_ALIGNMENT_RATIO: f32 = 0.8;
+
+/// Attachments taken above and below a figure: a caption and a footnote
+const MAX_ATTACHED_PER_SIDE: usize = 2;
+
+/// Minimum fraction of a label's area inside the figure
+const LABEL_INSIDE_RATIO: f32 = 0.5;
+
+/// Detect figure groups: each `Vision` element with the unmasked text attached to
+/// it, in the group's internal reading order
+///
+/// Attached are labels lying mostly inside the figure (axis labels, callouts) and
+/// captions and footnotes stacked directly above or below it: short boxes within
+/// its horizontal extent, each separated from the group by less than a tenth of
+/// the figure's height. Only groups with attachments are returned, as
+/// `(figure_id, member_ids)`: the text above, the figure, its labels, then the text
+/// below.
+pub fn detect_figure_groups<T: BoundingBox>(elements: &[T]) -> Vec<(usize, Vec<usize>)> {
+    let mut figures: Vec<&T> = elements
+        .iter()
+        .filter(|e| e.semantic_label() == SemanticLabel::Vision)
+        .collect();
+    figures.sort_by(|a, b| compare_position(*a, *b));
+
+    let mut taken: Vec<usize> = figures.iter().map(|f| f.id()).collect();
+    let mut groups = Vec::new();
+    for figure in figures {
+        let (fx1, fy1, fx2, fy2) = figure.bounds();
+        let height = fy2 - fy1;
+        let free = |e: &&T, taken: &[usize]| !e.should_mask() && !taken.contains(&e.id());
+
+        let mut labels: Vec<&T> = elements
+            .iter()
+            .filter(|e| free(e, &taken))
+            .filter(|e| {
+                let (x1, y1, x2, y2) = e.bounds();
+                let inside =
+                    (x2.min(fx2) - x1.max(fx1)).max(0.0) * (y2.min(fy2) - y1.max(fy1)).max(0.0);
+                let area = (x2 - x1) * (y2 - y1);
+                area > 0.0 && inside >= LABEL_INSIDE_RATIO * area
+            })
+            .collect();
+        labels.sort_by(|a, b| compare_position(*a, *b));
+        taken.extend(labels.iter().map(|e| e.id()));
+
+        // Grow the group downwards, then upwards, one adjacent box at a time
+        let (mut above, mut beneath): (Vec<&T>, Vec<&T>) = (Vec::new(), Vec::new());
+        for below in [true, false] {
+            let mut edge = if below { fy2 } else { fy1 };
+            for _ in 0..MAX_ATTACHED_PER_SIDE {
+                let next = elements
+                    .iter()
+                    .filter(|e| free(e, &taken))
+                    .filter_map(|e| {
+                        let (x1, y1, x2, y2) = e.bounds();
+                        let gap = if below { y1 - edge } else { edge - y2 };
+                        let aligned = (x2.min(fx2) - x1.max(fx1)).max(0.0)
+                            >= CAPTION_ALIGNMENT_RATIO * (x2 - x1);
+                        let short = y2 - y1 <= CAPTION_HEIGHT_RATIO * height;
+                        (gap >= 0.0 && gap <= CAPTION_GAP_RATIO * height && aligned && short)
+                            .then_some((gap, e))
+                    })
+                    .min_by(|a, b| a.0.total_cmp(&b.0).then(compare_position(a.1, b.1)));
+                let Some((_, e)) = next else {
+                    break;
+                };
+                let (_, y1, _, y2) = e.bounds();
+                edge = if below { y2 } else { y1 };
+                if below { &mut beneath } else { &mut above }.push(e);
+                taken.push(e.id());
+            }
+        }
+
+        // Text above, the figure with the labels on it, then text below
+        let members: Vec<usize> = above
+            .iter()
+            .rev()
+            .chain([&figure])
+            .chain(&labels)
+            .chain(&beneath)
+            .map(|e| e.id())
+            .collect();
+        if members.len() > 1 {
+            groups.push((figure.id(), members));
+        }
+    }
+    groups
+}
+
+/// Members of each figure group in reading order, by figure id
+type FigureGroups = HashMap<usize, Vec<usize>>;
+
+/// Merge every detected figure group into one unit with the figure's id and label
+///
+/// Returns the units and a map from figure id to the group's members in reading
+/// order, or `None` when no figure has attachments.
+pub(crate) fn group_figures<T: BoundingBox>(
+    elements: &[T],
+) -> Option<(Vec<Element>, FigureGroups)> {
+    let groups: FigureGroups = detect_figure_groups(elements).into_iter().collect();
+    if groups.is_empty() {
+        return None;
+    }
+
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+    let attached: Vec<usize> = groups
+        .iter()
+        .flat_map(|(figure, members)| members.iter().filter(move |id| *id != figure))
+        .copied()
+        .collect();
+    let units = elements
+        .iter()
+        .filter(|e| !attached.contains(&e.id()))
+        .map(|e| {
+            let mut unit = Element::from_box(e);
+            for member in groups.get(&e.id()).into_iter().flatten() {
+                unit.extend(by_id[member]);
+            }
+            unit
+        })
+        .collect();
+
+    Some((units, groups))
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use crate::element::Element;
+use crate::traits::{BoundingBox, SemanticLabel};
+use crate::utils::compare_position;
+
+/// Maximum difference of left edges between aligned items, relative to the shorter
+/// item's height
+const ALIGN_RATIO: f32 = 0.5;
+
+/// Maximum vertical gap between consecutive items, relative to the shorter item's
+/// height; slight overlaps (a quarter of it) are allowed
+const GAP_RATIO: f32 = 0.5;
+
+/// Minimum horizontal overlap of consecutive items, relative to the narrower one
+const OVERLAP_RATIO: f32 = 0.5;
+
+/// Minimum number of items in a list
+const MIN_ITEMS: usize = 3;
+
+/// Items of one list, by the id of its first item, top to bottom
+type ListItems = HashMap<usize, Vec<usize>>;
+
+/// Detect lists: runs of unmasked text boxes stacked with small vertical gaps and
+/// a consistent left edge
+///
+/// Each item starts within half a line height of the first item's left edge, or
+/// to its right (a nested item), and overlaps the item above it horizontally, so
+/// slightly misaligned bullets and hanging indents stay in the list. Returns the
+/// item ids of every list of at least three items, top to bottom.
+pub fn detect_lists<T: BoundingBox>(elements: &[T]) -> Vec<Vec<usize>> {
+    let mut text: Vec<&T> = elements
+        .iter()
+        .filter(|e| e.semantic_label() == SemanticLabel::Regular && !e.should_mask())
+        .collect();
+    text.sort_by(|a, b| {
+        a.bounds()
+            .1
+            .total_cmp(&b.bounds().1)
+            .then(compare_position(*a, *b))
+    });
+
+    let mut taken = vec![false; text.len()];
+    let mut lists = Vec::new();
+    for start in 0..text.len() {
+        if taken[start] {
+            continue;
+        }
+        let (anchor, ..) = text[start].bounds();
+        let mut run = vec![start];
+        loop {
+            // The topmost box continuing the list below its last item
+            let (x1, y1, x2, y2) = text[run[run.len() - 1]].bounds();
+            let height = y2 - y1;
+            let next = (0..text.len())
+                .filter(|&i| !taken[i] && !run.contains(&i))
+                .find(|&i| {
+                    let (ix1, iy1, ix2, iy2) = text[i].bounds();
+                    let shorter = height.min(iy2 - iy1);
+                    let gap = iy1 - y2;
+                    let aligned = ix1 - anchor >= -ALIGN_RATIO * shorter && ix1 < x2;
+                    let overlap = ix2.min(x2) - ix1.max(x1);
+                    gap >= -GAP_RATIO / 2.0 * shorter
+                        && gap <= GAP_RATIO * shorter
+                        && aligned
+                        && overlap >= OVERLAP_RATIO * (ix2 - ix1).min(x2 - x1)
+                });
+            match next {
+                Some(i) => run.push(i),
+                None => break,
+            }
+        }
+        if run.len() >= MIN_ITEMS {
+            for &i in &run {
+                taken[i] = true;
+            }
+            lists.push(run.iter().map(|&i| text[i].id()).collect());
+        }
+    }
+    lists
+}
+
+/// Merge every detected list into one unit with its first item's id
+///
+/// Returns the units and a map from unit id to the items top to bottom, or `None`
+/// when no list was detected.
+pub(crate) fn group_lists<T: BoundingBox>(elements: &[T]) -> Option<(Vec<Element>, ListItems)> {
+    let lists: ListItems = detect_lists(elements)
+        .into_iter()
+        .map(|items| (items[0], items))
+        .collect();
+    if lists.is_empty() {
+        return None;
+    }
+
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+    let grouped: Vec<usize> = lists
+        .values()
+        .flat_map(|items| items.iter().skip(1))
+        .copied()
+        .collect();
+    let units = elements
+        .iter()
+        .filter(|e| !grouped.contains(&e.id()))
+        .map(|e| {
+            let mut unit = Element::from_box(e);
+            for item in lists.get(&e.id()).into_iter().flatten() {
+                unit.extend(by_id[item]);
+            }
+            unit
+        })
+        .collect();
+
+    Some((units, lists))
+}
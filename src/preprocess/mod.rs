@@ -0,0 +1,25 @@
+//! Optional passes reshaping the input before cutting
+
+pub mod background;
+pub mod content;
+pub mod dropcap;
+pub mod figures;
+pub mod lists;
+pub mod noise;
+pub mod orientation;
+pub mod skew;
+pub mod snap;
+pub mod tables;
+pub mod tiles;
+
+pub use background::detect_background;
+pub use content::estimate_content_area;
+pub use dropcap::detect_drop_caps;
+pub use figures::detect_figure_groups;
+pub use lists::detect_lists;
+pub use noise::{detect_noise, NoiseFilter};
+pub use orientation::{detect_orientation, PageOrientation};
+pub use skew::estimate_skew;
+pub use snap::snap_edges;
+pub use tables::detect_table_clusters;
+pub use tiles::bin_tiles;
@@ -0,0 +1,34 @@
+use crate::traits::BoundingBox;
+
+/// Minimum size of a box to take part in ordering
+///
+/// All thresholds default to zero, which keeps every box, inverted ones included.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NoiseFilter {
+    /// Minimum box area (square pixels)
+    pub min_area: f32,
+
+    /// Minimum box width (pixels)
+    pub min_width: f32,
+
+    /// Minimum box height (pixels)
+    pub min_height: f32,
+}
+
+/// Ids of speckle boxes below any of the `filter` thresholds
+pub fn detect_noise<T: BoundingBox>(elements: &[T], filter: &NoiseFilter) -> Vec<usize> {
+    elements
+        .iter()
+        .filter(|e| {
+            let (x1, y1, x2, y2) = e.bounds();
+            // Inverted boxes (x2 < x1) are measured by their extent, not dropped
+            let (width, height) = ((x2 - x1).abs(), (y2 - y1).abs());
+            width < filter.min_width
+                || height < filter.min_height
+                || width * height < filter.min_area
+        })
+        .map(|e| e.id())
+        .collect()
+}
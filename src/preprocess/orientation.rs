@@ -0,0 +1,301 @@
+use crate::element::Element;
+use crate::traits::{BoundingBox, SemanticLabel};
+
+/// Minimum share of the elongated text, by length, running vertically for a page
+/// to be considered sideways
+const SIDEWAYS_RATIO: f32 = 0.6;
+
+/// Minimum ratio of the long to the short side for a box to show the text direction;
+/// squarish boxes (one- or two-character words) show none
+const ELONGATION: f32 = 2.0;
+
+/// Minimum number of text lines for the edge alignment to tell a flipped page
+const MIN_ALIGNMENT_LINES: usize = 4;
+
+/// How many more lines must share their right edge than their left edge for an
+/// upside-down page: ragged-right text shows ragged left edges once flipped
+const ALIGNMENT_MARGIN: f32 = 0.3;
+
+/// Rotation of the page content within the image
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PageOrientation {
+    /// Text reads left-to-right along the image x axis
+    #[default]
+    Upright,
+    /// Content rotated 90° clockwise: the top of the page faces the right image edge
+    Rotated90,
+    /// Content upside down
+    Rotated180,
+    /// Content rotated 90° counter-clockwise: the top of the page faces the left image edge
+    Rotated270,
+    /// Detect the orientation with [`detect_orientation`]
+    Auto,
+}
+
+/// Guess the page orientation from the shape of the text boxes
+///
+/// Text lines and words are wider than tall, so a page whose elongated unmasked boxes
+/// mostly run vertically (weighted by length, so short tall tokens such as "I" or "1"
+/// don't count for much) is sideways. The direction, 90° or 270°, is taken from the
+/// side of the text the titles sit on, since headings precede their text.
+///
+/// Upside-down pages need two cues, since a heading low on an upright page is common:
+/// titles below the text, and text lines sharing their right edge clearly more often
+/// than their left edge (ragged-right text, flipped). Justified text gives no
+/// alignment cue, so such pages are left upright; pass `Rotated180` explicitly.
+pub fn detect_orientation<T: BoundingBox>(elements: &[T]) -> PageOrientation {
+    let text: Vec<&T> = elements.iter().filter(|e| !e.should_mask()).collect();
+    if text.is_empty() {
+        return PageOrientation::Upright;
+    }
+    let (vertical, horizontal) = text.iter().fold((0.0, 0.0), |(vertical, horizontal), e| {
+        let (x1, y1, x2, y2) = e.bounds();
+        let (width, height) = (x2 - x1, y2 - y1);
+        if height >= ELONGATION * width {
+            (vertical + height, horizontal)
+        } else if width >= ELONGATION * height {
+            (vertical, horizontal + width)
+        } else {
+            (vertical, horizontal)
+        }
+    });
+    let sideways = vertical > 0.0 && vertical / (vertical + horizontal) >= SIDEWAYS_RATIO;
+
+    let centroid = |boxes: &[&T]| {
+        let (sx, sy) = boxes
+            .iter()
+            .map(|e| e.center())
+            .fold((0.0, 0.0), |(a, b), (x, y)| (a + x, b + y));
+        (sx / boxes.len() as f32, sy / boxes.len() as f32)
+    };
+    let (tx, ty) = centroid(&text);
+    let titles: Vec<&T> = elements
+        .iter()
+        .filter(|e| e.semantic_label().is_title())
+        .collect();
+    let title_offset = (!titles.is_empty()).then(|| {
+        let (hx, hy) = centroid(&titles);
+        (hx - tx, hy - ty)
+    });
+
+    match (sideways, title_offset) {
+        (true, Some((dx, _))) if dx < 0.0 => PageOrientation::Rotated270,
+        (true, _) => PageOrientation::Rotated90,
+        (false, Some((_, dy))) if dy > 0.0 && flipped_alignment(&text) => {
+            PageOrientation::Rotated180
+        }
+        (false, _) => PageOrientation::Upright,
+    }
+}
+
+/// Whether the text lines share their right edges clearly more often than their left
+/// edges, as ragged-right text does upside down
+fn flipped_alignment<T: BoundingBox>(text: &[&T]) -> bool {
+    let lines: Vec<(f32, f32, f32)> = text
+        .iter()
+        .map(|e| e.bounds())
+        .filter(|(x1, y1, x2, y2)| x2 - x1 >= ELONGATION * (y2 - y1))
+        .map(|(x1, y1, x2, y2)| (x1, x2, y2 - y1))
+        .collect();
+    if lines.len() < MIN_ALIGNMENT_LINES {
+        return false;
+    }
+
+    let mut heights: Vec<f32> = lines.iter().map(|line| line.2).collect();
+    heights.sort_by(f32::total_cmp);
+    let tolerance = heights[heights.len() / 2] / 2.0;
+    let left = aligned_share(lines.iter().map(|line| line.0).collect(), tolerance);
+    let right = aligned_share(lines.iter().map(|line| line.1).collect(), tolerance);
+    right - left >= ALIGNMENT_MARGIN
+}
+
+/// Share of `edges` lying within `tolerance` of another edge
+fn aligned_share(mut edges: Vec<f32>, tolerance: f32) -> f32 {
+    edges.sort_by(f32::total_cmp);
+    let aligned = (0..edges.len())
+        .filter(|&i| {
+            let before = i > 0 && edges[i] - edges[i - 1] <= tolerance;
+            let after = i + 1 < edges.len() && edges[i + 1] - edges[i] <= tolerance;
+            before || after
+        })
+        .count();
+    aligned as f32 / edges.len() as f32
+}
+
+/// Map elements into the upright page frame
+///
+/// Returns the rotated elements and the upright page size; the upright page
+/// starts at the origin. Vertical and horizontal titles swap labels for sideways pages.
+pub(crate) fn rotate_upright<T: BoundingBox>(
+    elements: &[T],
+    orientation: PageOrientation,
+    x_min: f32,
+    y_min: f32,
+    x_max: f32,
+    y_max: f32,
+) -> (Vec<Element>, f32, f32) {
+    let (width, height) = (x_max - x_min, y_max - y_min);
+    let sideways = matches!(
+        orientation,
+        PageOrientation::Rotated90 | PageOrientation::Rotated270
+    );
+
+    // Image offset (u, v) → upright (a, b)
+    let map = |u: f32, v: f32| match orientation {
+        PageOrientation::Rotated90 => (v, width - u),
+        PageOrientation::Rotated180 => (width - u, height - v),
+        PageOrientation::Rotated270 => (height - v, u),
+        PageOrientation::Upright | PageOrientation::Auto => (u, v),
+    };
+
+    let upright = elements
+        .iter()
+        .map(|e| {
+            let mut proxy = Element::from_box(e);
+            let (a1, b1) = map(proxy.x1 - x_min, proxy.y1 - y_min);
+            let (a2, b2) = map(proxy.x2 - x_min, proxy.y2 - y_min);
+            (proxy.x1, proxy.x2) = (a1.min(a2), a1.max(a2));
+            (proxy.y1, proxy.y2) = (b1.min(b2), b1.max(b2));
+            if sideways {
+                proxy.label = match proxy.label {
+                    SemanticLabel::HorizontalTitle => SemanticLabel::VerticalTitle,
+                    SemanticLabel::VerticalTitle => SemanticLabel::HorizontalTitle,
+                    label => label,
+                };
+            }
+            proxy
+        })
+        .collect();
+
+    if sideways {
+        (upright, height, width)
+    } else {
+        (upright, width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ragged-right body lines with a heading `heading_y` down an 800×1000 page
+    fn upright_page(heading_y: f32) -> Vec<Element> {
+        let widths = [600.0, 540.0, 650.0, 420.0, 610.0, 580.0, 300.0, 630.0];
+        let mut page: Vec<Element> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let y = 100.0 + i as f32 * 20.0;
+                Element::new(i, 50.0, y, 50.0 + width, y + 12.0, SemanticLabel::Regular)
+            })
+            .collect();
+        page.push(Element::new(
+            widths.len(),
+            50.0,
+            heading_y,
+            350.0,
+            heading_y + 20.0,
+            SemanticLabel::HorizontalTitle,
+        ));
+        page
+    }
+
+    /// `page` as it appears in the image when turned by `orientation`
+    fn turned(page: &[Element], orientation: PageOrientation) -> Vec<Element> {
+        let (width, height) = (800.0, 1000.0);
+        // Upright (a, b) → image offset (u, v), the inverse of `rotate_upright`
+        let map = |a: f32, b: f32| match orientation {
+            PageOrientation::Rotated90 => (height - b, a),
+            PageOrientation::Rotated180 => (width - a, height - b),
+            PageOrientation::Rotated270 => (b, width - a),
+            _ => (a, b),
+        };
+        page.iter()
+            .map(|e| {
+                let (u1, v1) = map(e.x1, e.y1);
+                let (u2, v2) = map(e.x2, e.y2);
+                Element::new(
+                    e.id,
+                    u1.min(u2),
+                    v1.min(v2),
+                    u1.max(u2),
+                    v1.max(v2),
+                    e.label,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn heading_low_on_an_upright_page_keeps_it_upright() {
+        assert_eq!(
+            detect_orientation(&upright_page(800.0)),
+            PageOrientation::Upright
+        );
+        assert_eq!(
+            detect_orientation(&upright_page(40.0)),
+            PageOrientation::Upright
+        );
+    }
+
+    #[test]
+    fn sideways_pages_turn_toward_their_titles() {
+        let page = upright_page(40.0);
+        for orientation in [PageOrientation::Rotated90, PageOrientation::Rotated270] {
+            let image = turned(&page, orientation);
+            assert_eq!(detect_orientation(&image), orientation);
+
+            // Turning back restores the upright boxes
+            let (height, width) = (800.0, 1000.0);
+            let (upright, _, _) = rotate_upright(&image, orientation, 0.0, 0.0, width, height);
+            for (restored, original) in upright.iter().zip(&page) {
+                assert_eq!(restored.bounds(), original.bounds());
+            }
+        }
+    }
+
+    #[test]
+    fn upside_down_page_needs_ragged_left_edges() {
+        let image = turned(&upright_page(40.0), PageOrientation::Rotated180);
+        assert_eq!(detect_orientation(&image), PageOrientation::Rotated180);
+
+        // Justified lines share both edges: no evidence of a flip
+        let justified: Vec<Element> = upright_page(40.0)
+            .into_iter()
+            .map(|mut e| {
+                if !e.should_mask() {
+                    e.x2 = 650.0;
+                }
+                e
+            })
+            .collect();
+        let image = turned(&justified, PageOrientation::Rotated180);
+        assert_eq!(detect_orientation(&image), PageOrientation::Upright);
+    }
+
+    #[test]
+    fn short_word_boxes_do_not_make_a_page_sideways() {
+        // Rows alternating one-character words (taller than wide) and longer words
+        let mut words = Vec::new();
+        for row in 0..10 {
+            let y = 100.0 + row as f32 * 16.0;
+            let mut x = 50.0;
+            for width in [5.0, 40.0, 8.0, 6.0, 55.0, 5.0, 7.0] {
+                let id = words.len();
+                words.push(Element::new(
+                    id,
+                    x,
+                    y,
+                    x + width,
+                    y + 12.0,
+                    SemanticLabel::Regular,
+                ));
+                x += width + 6.0;
+            }
+        }
+        assert_eq!(detect_orientation(&words), PageOrientation::Upright);
+        let image = turned(&words, PageOrientation::Rotated90);
+        assert_eq!(detect_orientation(&image), PageOrientation::Rotated90);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::element::Element;
+use crate::traits::BoundingBox;
+
+/// Largest skew (degrees) considered a scanning artifact rather than intentional layout
+const MAX_SKEW_DEGREES: f32 = 10.0;
+
+/// Minimum vertical overlap (fraction of the shorter box) for two boxes on one text row
+const MIN_ROW_OVERLAP: f32 = 0.5;
+
+/// Estimate the page skew in degrees from the drift of box edges
+///
+/// Neighboring boxes on one text row drift vertically by `tan(angle)` per unit of
+/// horizontal distance; boxes stacked in one column drift horizontally the opposite
+/// way. The estimate is the median over all such neighbor pairs, so indents and
+/// ragged edges are outvoted. Positive angles are clockwise on screen (y pointing down).
+/// Returns `0.0` when there is not enough evidence.
+pub fn estimate_skew<T: BoundingBox>(elements: &[T]) -> f32 {
+    let max_slope = MAX_SKEW_DEGREES.to_radians().tan();
+    let mut slopes = Vec::new();
+
+    for a in elements.iter().filter(|e| !e.should_mask()) {
+        let (ax1, ay1, ax2, ay2) = a.bounds();
+        let (acx, acy) = a.center();
+
+        // Nearest right-hand neighbor on the same row
+        let right = elements
+            .iter()
+            .filter(|b| b.id() != a.id() && !b.should_mask())
+            .filter(|b| {
+                let (bx1, by1, _, by2) = b.bounds();
+                let overlap = ay2.min(by2) - ay1.max(by1);
+                let shorter = (ay2 - ay1).min(by2 - by1);
+                bx1 >= ax2 && shorter > 0.0 && overlap / shorter >= MIN_ROW_OVERLAP
+            })
+            .min_by(|b, c| {
+                b.bounds()
+                    .0
+                    .partial_cmp(&c.bounds().0)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        if let Some(b) = right {
+            let (bcx, bcy) = b.center();
+            slopes.push((bcy - acy) / (bcx - acx));
+        }
+
+        // Nearest box below whose left edge starts within this box's span
+        let below = elements
+            .iter()
+            .filter(|b| b.id() != a.id() && !b.should_mask())
+            .filter(|b| {
+                let (bx1, by1, _, _) = b.bounds();
+                by1 >= ay2 && (bx1 - ax1).abs() < (ax2 - ax1) / 2.0
+            })
+            .min_by(|b, c| {
+                b.bounds()
+                    .1
+                    .partial_cmp(&c.bounds().1)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        if let Some(b) = below {
+            let (bx1, by1, _, _) = b.bounds();
+            slopes.push(-(bx1 - ax1) / (by1 - ay1));
+        }
+    }
+
+    slopes.retain(|s| s.is_finite() && s.abs() <= max_slope);
+    if slopes.is_empty() {
+        return 0.0;
+    }
+    slopes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    slopes[slopes.len() / 2].atan().to_degrees()
+}
+
+/// Project every element onto axes rotated by `angle` degrees around `(cx, cy)`
+///
+/// Centers are rotated back by the skew and each box shrinks to the extent of the
+/// upright box whose rotation produced it, which reopens the gaps the skew closed.
+pub(crate) fn deskew<T: BoundingBox>(elements: &[T], angle: f32, cx: f32, cy: f32) -> Vec<Element> {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let cos_2 = cos * cos - sin * sin;
+
+    elements
+        .iter()
+        .map(|e| {
+            let mut proxy = Element::from_box(e);
+            let (x, y) = e.center();
+            let (dx, dy) = (x - cx, y - cy);
+            let x = cx + dx * cos + dy * sin;
+            let y = cy - dx * sin + dy * cos;
+
+            // Invert w = w0·cos + h0·|sin|, h = w0·|sin| + h0·cos; inverted boxes
+            // (x2 < x1) are measured by their extent
+            let (w, h) = (proxy.width().abs(), proxy.height().abs());
+            let w0 = ((w * cos - h * sin.abs()) / cos_2).clamp(w * 0.5, w);
+            let h0 = ((h * cos - w * sin.abs()) / cos_2).clamp(h * 0.1, h);
+            (proxy.x1, proxy.x2) = (x - w0 / 2.0, x + w0 / 2.0);
+            (proxy.y1, proxy.y2) = (y - h0 / 2.0, y + h0 / 2.0);
+            proxy
+        })
+        .collect()
+}
@@ -0,0 +1,47 @@
+use crate::element::Element;
+use crate::traits::BoundingBox;
+
+/// Snap box edges that nearly coincide to a common coordinate
+///
+/// Left, top, right and bottom edges are clustered separately: sorted, edges at most
+/// `epsilon` apart join one cluster, and each edge moves to the cluster's
+/// median. Columns whose boxes start a pixel apart then share one left
+/// edge, leaving clean gaps in the projection histograms. A box whose snapped edges
+/// would cross keeps its own coordinates on that axis.
+pub fn snap_edges<T: BoundingBox>(elements: &[T], epsilon: f32) -> Vec<Element> {
+    let mut snapped: Vec<Element> = elements.iter().map(Element::from_box).collect();
+    let lefts = snap_axis(&snapped.iter().map(|e| e.x1).collect::<Vec<_>>(), epsilon);
+    let tops = snap_axis(&snapped.iter().map(|e| e.y1).collect::<Vec<_>>(), epsilon);
+    let rights = snap_axis(&snapped.iter().map(|e| e.x2).collect::<Vec<_>>(), epsilon);
+    let bottoms = snap_axis(&snapped.iter().map(|e| e.y2).collect::<Vec<_>>(), epsilon);
+    for (i, e) in snapped.iter_mut().enumerate() {
+        if lefts[i] <= rights[i] {
+            (e.x1, e.x2) = (lefts[i], rights[i]);
+        }
+        if tops[i] <= bottoms[i] {
+            (e.y1, e.y2) = (tops[i], bottoms[i]);
+        }
+    }
+    snapped
+}
+
+/// `values` with each moved to the median of its cluster
+fn snap_axis(values: &[f32], epsilon: f32) -> Vec<f32> {
+    let mut sorted: Vec<usize> = (0..values.len()).collect();
+    sorted.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut snapped = values.to_vec();
+    let mut start = 0;
+    while start < sorted.len() {
+        let mut end = start + 1;
+        while end < sorted.len() && values[sorted[end]] - values[sorted[end - 1]] <= epsilon {
+            end += 1;
+        }
+        let median = values[sorted[(start + end - 1) / 2]];
+        for &i in &sorted[start..end] {
+            snapped[i] = median;
+        }
+        start = end;
+    }
+    snapped
+}
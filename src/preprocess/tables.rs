@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use crate::element::Element;
+use crate::modes::table::{layout_table_cells, TableConfig};
+use crate::preprocess::tiles::{find, union};
+use crate::traits::{BoundingBox, SemanticLabel};
+
+/// Maximum difference of the tops and bottoms of cells in one row, or of the left,
+/// right or center of cells in one column, relative to the shorter cell's height
+const ALIGN_RATIO: f32 = 0.3;
+
+/// Maximum vertical gap between consecutive cells of a column, relative to the
+/// shorter cell's height
+const ROW_GAP_RATIO: f32 = 1.5;
+
+/// Minimum number of rows and of columns of a table cluster
+const MIN_GRID: usize = 2;
+
+/// Minimum share of the grid's rows × columns slots holding a cell
+const MIN_FILL: f32 = 0.75;
+
+/// Cells of one table cluster, by the id of its first cell, in row-major order
+type ClusterCells = HashMap<usize, Vec<usize>>;
+
+/// Detect table clusters: dense grids of unmasked text boxes aligned in rows and
+/// columns
+///
+/// Boxes are linked to row neighbors sharing their top and bottom, and to column
+/// neighbors directly above or below sharing their left, right or center edge (so
+/// right-aligned amounts link up). Boxes spanning several columns of their cluster,
+/// like a note aligned with the first column, are left out and the rest linked
+/// again. A linked cluster is a table when it spans at least two rows and two
+/// columns (see [`layout_table_cells`]) with three quarters of the slots filled.
+/// Returns the cell ids of each table, in row-major order.
+pub fn detect_table_clusters<T: BoundingBox>(elements: &[T]) -> Vec<Vec<usize>> {
+    let mut cells: Vec<&T> = elements
+        .iter()
+        .filter(|e| e.semantic_label() == SemanticLabel::Regular && !e.should_mask())
+        .collect();
+    cells.sort_by(|a, b| a.bounds().1.total_cmp(&b.bounds().1));
+
+    let mut tables = Vec::new();
+    collect_grids(&cells, &mut tables);
+    tables
+}
+
+/// Link `cells` (sorted by top) into clusters and push the grids among them
+fn collect_grids<T: BoundingBox>(cells: &[&T], tables: &mut Vec<Vec<usize>>) {
+    let mut parent: Vec<usize> = (0..cells.len()).collect();
+    for i in 0..cells.len() {
+        let (x1, y1, x2, y2) = cells[i].bounds();
+        for (j, cell) in cells.iter().enumerate().skip(i + 1) {
+            let (jx1, jy1, jx2, jy2) = cell.bounds();
+            let shorter = (y2 - y1).min(jy2 - jy1);
+            if jy1 > y2 + ROW_GAP_RATIO * (y2 - y1) {
+                break;
+            }
+            let tolerance = ALIGN_RATIO * shorter;
+            let same_row = (jy1 - y1).abs() <= tolerance && (jy2 - y2).abs() <= tolerance;
+            let gap = jy1 - y2;
+            let same_column = gap >= -tolerance
+                && gap <= ROW_GAP_RATIO * shorter
+                && ((jx1 - x1).abs() <= tolerance
+                    || (jx2 - x2).abs() <= tolerance
+                    || (jx1 + jx2 - x1 - x2).abs() / 2.0 <= tolerance);
+            if same_row || same_column {
+                union(&mut parent, i, j);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<&T>> = vec![Vec::new(); cells.len()];
+    for (i, cell) in cells.iter().enumerate() {
+        clusters[find(&mut parent, i)].push(*cell);
+    }
+    let config = TableConfig::default();
+    for cluster in clusters {
+        if cluster.len() < MIN_GRID * MIN_GRID {
+            continue;
+        }
+        let (spanning, narrow): (Vec<&T>, Vec<&T>) = cluster
+            .iter()
+            .partition(|cell| spans_columns(**cell, &cluster));
+        if !spanning.is_empty() {
+            collect_grids(&narrow, tables);
+            continue;
+        }
+
+        let cluster: Vec<T> = cluster.into_iter().cloned().collect();
+        let layout = layout_table_cells(&cluster, &config);
+        let rows = layout.iter().map(|c| c.row + 1).max().unwrap_or(0);
+        let columns = layout.iter().map(|c| c.col + 1).max().unwrap_or(0);
+        if rows >= MIN_GRID
+            && columns >= MIN_GRID
+            && layout.len() as f32 >= MIN_FILL * (rows * columns) as f32
+        {
+            tables.push(layout.iter().map(|c| c.id).collect());
+        }
+    }
+}
+
+/// Whether `cell` lies over two boxes of `cluster` side by side, i.e. over two columns
+fn spans_columns<T: BoundingBox>(cell: &T, cluster: &[&T]) -> bool {
+    let beside = |a: &T, b: &T| {
+        let (ax1, _, ax2, _) = a.bounds();
+        let (bx1, _, bx2, _) = b.bounds();
+        ax2.min(bx2) <= ax1.max(bx1)
+    };
+    let overlapped: Vec<&T> = cluster
+        .iter()
+        .copied()
+        .filter(|other| other.id() != cell.id() && !beside(cell, other))
+        .collect();
+    overlapped
+        .iter()
+        .any(|a| overlapped.iter().any(|b| beside(a, b)))
+}
+
+/// Merge every detected table cluster into one masked unit with its first cell's id
+///
+/// The units are labelled `Vision`, so each table is inserted as a whole after
+/// cutting, like a detected table. Returns the units and a map from unit id to the
+/// cells in row-major order, or `None` when no table cluster was detected.
+pub(crate) fn group_tables<T: BoundingBox>(elements: &[T]) -> Option<(Vec<Element>, ClusterCells)> {
+    let tables: ClusterCells = detect_table_clusters(elements)
+        .into_iter()
+        .map(|cells| (cells[0], cells))
+        .collect();
+    if tables.is_empty() {
+        return None;
+    }
+
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+    let grouped: Vec<usize> = tables
+        .values()
+        .flat_map(|cells| cells.iter().skip(1))
+        .copied()
+        .collect();
+    let units = elements
+        .iter()
+        .filter(|e| !grouped.contains(&e.id()))
+        .map(|e| {
+            let mut unit = Element::from_box(e);
+            if let Some(cells) = tables.get(&e.id()) {
+                for cell in cells {
+                    unit.extend(by_id[cell]);
+                }
+                (unit.label, unit.mask, unit.confidence) = (SemanticLabel::Vision, true, None);
+            }
+            unit
+        })
+        .collect();
+
+    Some((units, tables))
+}
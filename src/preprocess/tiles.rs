@@ -0,0 +1,106 @@
+//! Pre-binning of very large pages (word boxes of whole book spreads) into tiles
+
+use std::collections::HashMap;
+
+use crate::traits::BoundingBox;
+
+/// Boxes spanning more grid cells than this along either axis are left out of tiles
+const MAX_CELL_SPAN: i64 = 64;
+
+/// Group boxes into tiles: connected groups of boxes less than `gap` apart on both axes
+///
+/// No projection cut needing a gap of at least `gap` can separate two boxes of a tile,
+/// so each tile can be ordered on its own once its place among the other tiles is
+/// known. Only boxes accepted by `binnable` are grouped, and boxes far larger than
+/// the typical box are left out. Returns the tiles of two or
+/// more boxes as indices into `elements`, in order of their first box.
+///
+/// Neighbours are found through a uniform grid of cells about the size of a typical
+/// box, so the cost grows linearly with the number of boxes for ordinary pages.
+pub fn bin_tiles<T: BoundingBox>(
+    elements: &[T],
+    gap: f32,
+    binnable: impl Fn(&T) -> bool,
+) -> Vec<Vec<usize>> {
+    // Boxes grown by half the gap on every side overlap exactly when they are
+    // less than `gap` apart on both axes
+    let half = gap.max(0.0) / 2.0;
+    let grown: Vec<(usize, (f32, f32, f32, f32))> = elements
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| binnable(e))
+        .map(|(i, e)| {
+            let (x1, y1, x2, y2) = e.bounds();
+            (i, (x1 - half, y1 - half, x2 + half, y2 + half))
+        })
+        .filter(|(_, b)| [b.0, b.1, b.2, b.3].iter().all(|v| v.is_finite()))
+        .collect();
+    if grown.len() < 2 {
+        return Vec::new();
+    }
+
+    let mut sizes: Vec<f32> = grown
+        .iter()
+        .map(|(_, (x1, y1, x2, y2))| (x2 - x1).max(y2 - y1))
+        .collect();
+    let middle = sizes.len() / 2;
+    let cell = *sizes.select_nth_unstable_by(middle, f32::total_cmp).1;
+    let cell = if cell > 0.0 { cell } else { 1.0 };
+
+    let to_cell = |v: f32| (v / cell).floor() as i64;
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (slot, &(_, (x1, y1, x2, y2))) in grown.iter().enumerate() {
+        let (columns, rows) = (to_cell(x1)..=to_cell(x2), to_cell(y1)..=to_cell(y2));
+        // Huge coordinates saturate to the ends of the i64 range
+        if columns.end().saturating_sub(*columns.start()) > MAX_CELL_SPAN
+            || rows.end().saturating_sub(*rows.start()) > MAX_CELL_SPAN
+        {
+            continue;
+        }
+        for cx in columns {
+            for cy in rows.clone() {
+                grid.entry((cx, cy)).or_default().push(slot);
+            }
+        }
+    }
+
+    let mut parent: Vec<usize> = (0..grown.len()).collect();
+    for members in grid.values() {
+        for (k, &a) in members.iter().enumerate() {
+            for &b in &members[k + 1..] {
+                let (a1, b1) = (grown[a].1, grown[b].1);
+                if a1.0 < b1.2 && b1.0 < a1.2 && a1.1 < b1.3 && b1.1 < a1.3 {
+                    union(&mut parent, a, b);
+                }
+            }
+        }
+    }
+
+    let mut tiles: Vec<Vec<usize>> = Vec::new();
+    let mut tile_of_root: HashMap<usize, usize> = HashMap::new();
+    for (slot, &(index, _)) in grown.iter().enumerate() {
+        let root = find(&mut parent, slot);
+        let tile = *tile_of_root.entry(root).or_insert_with(|| {
+            tiles.push(Vec::new());
+            tiles.len() - 1
+        });
+        tiles[tile].push(index);
+    }
+    tiles.retain(|tile| tile.len() > 1);
+    tiles
+}
+
+/// Root of `node`'s set, compressing the path to it
+pub(crate) fn find(parent: &mut [usize], mut node: usize) -> usize {
+    while parent[node] != node {
+        parent[node] = parent[parent[node]];
+        node = parent[node];
+    }
+    node
+}
+
+/// Join two sets under the smaller root, keeping roots at the first box of a tile
+pub(crate) fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (a, b) = (find(parent, a), find(parent, b));
+    parent[a.max(b)] = a.min(b);
+}
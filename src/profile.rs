@@ -0,0 +1,106 @@
+//! Per-stage timing and allocation counts for diagnosing slow pages
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::core::XYCutPlusPlus;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static COUNTING: AtomicBool = AtomicBool::new(false);
+
+/// Time spent in each stage of one [`compute_order_profiled`](XYCutPlusPlus::compute_order_profiled) call
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProfileReport {
+    /// Pre-mask partition into regular and masked elements
+    pub pre_mask: Duration,
+
+    /// Recursive cutting of the regular elements
+    pub cutting: Duration,
+
+    /// Reinsertion of the masked elements
+    pub insertion: Duration,
+
+    /// Whole call, including preprocessing (noise, orientation, skew, marginalia)
+    pub total: Duration,
+
+    /// Heap allocations made during the call, when [`CountingAllocator`] is the
+    /// global allocator; includes allocations by other threads running meanwhile
+    pub allocations: Option<usize>,
+}
+
+/// System allocator counting allocations for [`ProfileReport::allocations`]
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: xycut_plus_plus::profile::CountingAllocator =
+///     xycut_plus_plus::profile::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        COUNTING.store(true, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+impl XYCutPlusPlus {
+    /// [`compute_order`](Self::compute_order) returning how long each stage took
+    pub fn compute_order_profiled<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> (Vec<usize>, ProfileReport) {
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+
+        let mut trace = Trace::default();
+        let order = self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace);
+
+        let profile = ProfileReport {
+            total: start.elapsed(),
+            allocations: COUNTING
+                .load(Ordering::Relaxed)
+                .then(|| ALLOCATIONS.load(Ordering::Relaxed) - allocations),
+            ..trace.profile
+        };
+        (order, profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    #[test]
+    fn allocations_are_unknown_without_the_counting_allocator() {
+        let page = [
+            Element::new(0, 100.0, 100.0, 500.0, 200.0, SemanticLabel::Regular),
+            Element::new(1, 100.0, 300.0, 500.0, 400.0, SemanticLabel::Regular),
+        ];
+        let (order, profile) = XYCutPlusPlus::new(XYCutConfig::default())
+            .compute_order_profiled(&page, 0.0, 0.0, 600.0, 500.0);
+        assert_eq!(order, vec![0, 1]);
+        assert_eq!(profile.allocations, None);
+    }
+}
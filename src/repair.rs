@@ -0,0 +1,169 @@
+//! Corrections applied to a computed reading order
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::XYCutPlusPlus;
+use crate::traits::BoundingBox;
+
+/// Re-solve `order` around human-fixed `(id, rank)` anchors
+///
+/// Anchored ids are placed at their rank (clamped to the order length; an anchor
+/// whose rank is already taken moves to the next free slot). All other ids keep
+/// their relative algorithmic order and fill the remaining slots, which is the
+/// arrangement with the fewest pairwise deviations from `order`. Anchors for ids not in
+/// `order` are ignored.
+pub fn apply_anchors(order: &[usize], anchors: &[(usize, usize)]) -> Vec<usize> {
+    let known: HashSet<usize> = order.iter().copied().collect();
+    let mut anchors: Vec<(usize, usize)> = anchors
+        .iter()
+        .copied()
+        .filter(|(id, _)| known.contains(id))
+        .collect();
+    anchors.sort_by_key(|&(id, rank)| (rank, id));
+
+    let mut slots: Vec<Option<usize>> = vec![None; order.len()];
+    let mut anchored: HashSet<usize> = HashSet::new();
+    for (id, rank) in anchors {
+        if !anchored.insert(id) {
+            continue;
+        }
+        let start = rank.min(order.len() - 1);
+        let slot = (start..order.len())
+            .chain((0..start).rev())
+            .find(|&i| slots[i].is_none());
+        if let Some(slot) = slot {
+            slots[slot] = Some(id);
+        }
+    }
+
+    let mut rest = order.iter().copied().filter(|id| !anchored.contains(id));
+    slots
+        .into_iter()
+        .map(|slot| slot.or_else(|| rest.next()))
+        .collect::<Option<Vec<usize>>>()
+        .unwrap_or_default()
+}
+
+/// Minimum horizontal overlap (fraction of the narrower box) for two boxes in one column
+const SAME_COLUMN_OVERLAP: f32 = 0.5;
+
+/// Swap obvious geometric inversions: an element ordered after its nearest earlier
+/// neighbour in the same column although it lies strictly above it
+///
+/// Each element is moved back past such neighbours, as in an insertion sort, so a
+/// column in reverse costs one swap per inverted pair. Boxes starting at the same
+/// height (zero-height spans on one line) are never inverted. Sweeps repeat until
+/// nothing moves (at most `order.len()` of them). Returns the repaired order and the
+/// number of swaps made.
+pub fn repair_inversions<T: BoundingBox>(elements: &[T], order: &[usize]) -> (Vec<usize>, usize) {
+    let by_id: HashMap<usize, (f32, f32, f32, f32)> =
+        elements.iter().map(|e| (e.id(), e.bounds())).collect();
+    let mut order = order.to_vec();
+    // Bounds of `order[i]`, moved along with it; unknown ids are in no column
+    let mut bounds: Vec<Option<(f32, f32, f32, f32)>> =
+        order.iter().map(|id| by_id.get(id).copied()).collect();
+
+    let same_column = |a: (f32, f32, f32, f32), b: (f32, f32, f32, f32)| {
+        let overlap = a.2.min(b.2) - a.0.max(b.0);
+        let narrower = (a.2 - a.0).min(b.2 - b.0);
+        narrower > 0.0 && overlap / narrower >= SAME_COLUMN_OVERLAP
+    };
+    // Strict: `upper` ends above `lower` and starts higher
+    let above = |upper: (f32, f32, f32, f32), lower: (f32, f32, f32, f32)| {
+        upper.3 <= lower.1 && upper.1 < lower.1
+    };
+
+    let mut repairs = 0;
+    for _ in 0..order.len() {
+        let mut swapped = false;
+        for j in 1..order.len() {
+            let mut at = j;
+            while let Some(current) = bounds[at] {
+                let neighbour = (0..at).rev().find_map(|i| {
+                    bounds[i]
+                        .filter(|&b| same_column(b, current))
+                        .map(|b| (i, b))
+                });
+                let Some((before, earlier)) = neighbour else {
+                    break;
+                };
+                if !above(current, earlier) {
+                    break;
+                }
+                order.swap(before, at);
+                bounds.swap(before, at);
+                repairs += 1;
+                swapped = true;
+                at = before;
+            }
+        }
+        if !swapped {
+            break;
+        }
+    }
+    (order, repairs)
+}
+
+impl XYCutPlusPlus {
+    /// [`compute_order`](Self::compute_order) respecting `(id, rank)` anchors
+    /// fixed by a human, see [`apply_anchors`]
+    pub fn compute_order_with_anchors<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+        anchors: &[(usize, usize)],
+    ) -> Vec<usize> {
+        let order = self.compute_order(elements, x_min, y_min, x_max, y_max);
+        apply_anchors(&order, anchors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    #[test]
+    fn anchored_element_moves_and_the_rest_keep_their_order() {
+        // A human moved the caption 4 up to rank 1
+        assert_eq!(
+            apply_anchors(&[0, 1, 2, 3, 4], &[(4, 1)]),
+            vec![0, 4, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn conflicting_and_unknown_anchors() {
+        // Both want rank 0: the lower id keeps it, the other takes the next free slot
+        assert_eq!(
+            apply_anchors(&[0, 1, 2, 3], &[(3, 0), (2, 0), (9, 1)]),
+            vec![2, 3, 0, 1]
+        );
+        // Ranks past the end are clamped
+        assert_eq!(apply_anchors(&[0, 1, 2], &[(0, 10)]), vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn misordered_element_is_re_anchored() {
+        // A sidebar box the algorithm reads first, which belongs after the body
+        let page = [
+            Element::new(0, 50.0, 50.0, 250.0, 300.0, SemanticLabel::Regular),
+            Element::new(1, 300.0, 50.0, 750.0, 150.0, SemanticLabel::Regular),
+            Element::new(2, 300.0, 200.0, 750.0, 300.0, SemanticLabel::Regular),
+        ];
+        let engine = XYCutPlusPlus::new(XYCutConfig::default());
+        assert_eq!(
+            engine.compute_order(&page, 0.0, 0.0, 800.0, 400.0),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            engine.compute_order_with_anchors(&page, 0.0, 0.0, 800.0, 400.0, &[(0, 2)]),
+            vec![1, 2, 0]
+        );
+    }
+}
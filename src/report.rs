@@ -0,0 +1,86 @@
+use crate::core::XYCutPlusPlus;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+use crate::unanchored::Unanchored;
+
+/// Version of the ordering behavior, bumped whenever the same input and config
+/// may produce a different order
+pub const ALGORITHM_VERSION: u32 = 11;
+
+/// Initial state of [`fnv1a`]
+pub(crate) const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a hash of `bytes`, continuing from `hash`
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Reading order together with metadata about how it was produced
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderingReport {
+    /// Element ids in reading order
+    pub order: Vec<usize>,
+
+    /// [`XYCutPlusPlus::fingerprint`] of the instance that produced the order
+    pub fingerprint: u64,
+
+    /// Inversions swapped by the `repair_inversions` smoothing pass
+    pub repairs: usize,
+
+    /// Elements ordered by the fallback position sort, in regions without a cut
+    pub fallback_sorted: usize,
+
+    /// Masked elements placed by the insertion stage
+    pub inserted: usize,
+
+    /// Inserted elements with no valid anchor, with the reason, placed according to
+    /// the `unanchored` policy
+    pub unanchored: Vec<Unanchored>,
+
+    /// Deepest nesting of the recursive cut
+    pub depth: usize,
+
+    /// Ids left out of the order: speckles below the `noise` thresholds, marginalia under
+    /// [`MarginaliaPolicy::Exclude`](crate::MarginaliaPolicy::Exclude) and, unless
+    /// `keep_background` is set, page background boxes
+    pub dropped: Vec<usize>,
+}
+
+impl XYCutPlusPlus {
+    /// Hash of the effective configuration and [`ALGORITHM_VERSION`]
+    ///
+    /// FNV-1a over the config's `Debug` rendering: stable across runs and platforms for
+    /// one build, so cached orderings can be invalidated when it changes. A new compiler
+    /// or crate version may render the config differently and change the fingerprint
+    /// of an unchanged config, which only costs a recomputation.
+    pub fn fingerprint(&self) -> u64 {
+        let canonical = format!("xycut-plus-plus/{ALGORITHM_VERSION}/{:?}", self.config);
+        fnv1a(FNV_OFFSET_BASIS, canonical.as_bytes())
+    }
+
+    /// [`compute_order`](Self::compute_order) returning the order with its metadata
+    pub fn compute_order_with_report<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> OrderingReport {
+        let mut trace = Trace::default();
+        let order = self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace);
+        OrderingReport {
+            order,
+            fingerprint: self.fingerprint(),
+            repairs: trace.repairs,
+            fallback_sorted: trace.fallback_groups.iter().map(Vec::len).sum(),
+            inserted: trace.inserted,
+            unanchored: trace.unanchored,
+            depth: trace.max_depth,
+            dropped: trace.dropped,
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Fuse the element lists of several detectors into one page
+//!
+//! Models run on the same page number their boxes independently, so ids collide
+//! and the same block is often detected twice. [`merge_sources`] resolves both
+//! before ordering and keeps the way back to each source's ids.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::element::Element;
+use crate::traits::BoundingBox;
+
+/// How an id already taken by an earlier element is resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DuplicateIds {
+    /// The later element gets a fresh id, above every input id
+    #[default]
+    Reassign,
+    /// Merging fails with a [`DuplicateIdError`]
+    Error,
+}
+
+/// An element's index of source and id within it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceId {
+    pub source: usize,
+    pub id: usize,
+}
+
+/// Elements fused from several sources, with their origins
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MergedSources {
+    /// Elements kept, with unique ids, by source then in source order
+    pub elements: Vec<Element>,
+
+    /// Origin of each element of `elements`
+    pub origins: Vec<SourceId>,
+
+    /// Elements dropped as duplicate detections, with the id of the element kept
+    /// in their place
+    pub fused: Vec<(SourceId, usize)>,
+}
+
+impl MergedSources {
+    /// Origin of the merged element with id `id`
+    pub fn origin(&self, id: usize) -> Option<SourceId> {
+        let index = self.elements.iter().position(|e| e.id == id)?;
+        Some(self.origins[index])
+    }
+}
+
+/// An id is used twice under [`DuplicateIds::Error`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateIdError {
+    pub id: usize,
+
+    /// Sources of the first and second element using it, possibly the same
+    pub sources: (usize, usize),
+}
+
+impl fmt::Display for DuplicateIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (first, second) = self.sources;
+        if first == second {
+            write!(f, "id {} is used twice in source {first}", self.id)
+        } else {
+            write!(f, "id {} is used by sources {first} and {second}", self.id)
+        }
+    }
+}
+
+impl std::error::Error for DuplicateIdError {}
+
+/// Fuse the element lists of several sources into one page with unique ids
+///
+/// Boxes from different sources with an IoU of at least `overlap` are the same
+/// detection: the one with the higher confidence is kept (unknown counts as 0),
+/// the earlier source on ties. An `overlap` above 1 keeps every box. Ids taken by
+/// an earlier kept element are then resolved according to `duplicates`, so ids
+/// are reassigned the same way for the same sources.
+pub fn merge_sources<T: BoundingBox>(
+    sources: &[&[T]],
+    overlap: f32,
+    duplicates: DuplicateIds,
+) -> Result<MergedSources, DuplicateIdError> {
+    let all: Vec<(usize, &T)> = sources
+        .iter()
+        .enumerate()
+        .flat_map(|(source, elements)| elements.iter().map(move |e| (source, e)))
+        .collect();
+
+    // Greedy suppression across sources, most confident first
+    let mut by_confidence: Vec<usize> = (0..all.len()).collect();
+    let confidence = |i: usize| all[i].1.confidence().unwrap_or(0.0);
+    by_confidence.sort_by(|&a, &b| confidence(b).total_cmp(&confidence(a)));
+    let mut kept_by: Vec<Option<usize>> = vec![None; all.len()];
+    let mut kept: Vec<usize> = Vec::new();
+    for i in by_confidence {
+        let (source, e) = all[i];
+        let duplicate = kept.iter().copied().find(|&k| {
+            let (other_source, other) = all[k];
+            other_source != source && e.iou(other) >= overlap
+        });
+        match duplicate {
+            Some(k) => kept_by[i] = Some(k),
+            None => kept.push(i),
+        }
+    }
+    kept.sort_unstable();
+
+    let next_id = next_free_id(&all);
+    let fused = (0..all.len())
+        .filter_map(|i| {
+            let k = kept_by[i]?;
+            Some((i, kept.binary_search(&k).ok()?))
+        })
+        .map(|(i, k)| {
+            let (source, e) = all[i];
+            (SourceId { source, id: e.id() }, k)
+        })
+        .collect();
+    let kept = kept
+        .into_iter()
+        .map(|i| {
+            let (source, e) = all[i];
+            (SourceId { source, id: e.id() }, Element::from_box(e))
+        })
+        .collect();
+    unique_ids(kept, fused, next_id, duplicates)
+}
+
+/// Smallest id above every id of `all`
+pub(crate) fn next_free_id<T: BoundingBox>(all: &[(usize, &T)]) -> usize {
+    all.iter().map(|(_, e)| e.id() + 1).max().unwrap_or(0)
+}
+
+/// Give the `kept` elements unique ids, in order, from `next_id` on
+///
+/// `fused` holds dropped elements with the index in `kept` of the element kept in
+/// their place.
+pub(crate) fn unique_ids(
+    kept: Vec<(SourceId, Element)>,
+    fused: Vec<(SourceId, usize)>,
+    mut next_id: usize,
+    duplicates: DuplicateIds,
+) -> Result<MergedSources, DuplicateIdError> {
+    let mut users: HashMap<usize, usize> = HashMap::new();
+    let mut merged = MergedSources {
+        elements: Vec::with_capacity(kept.len()),
+        origins: Vec::with_capacity(kept.len()),
+        fused: Vec::with_capacity(fused.len()),
+    };
+    for (origin, mut element) in kept {
+        if let Some(&first) = users.get(&element.id) {
+            match duplicates {
+                DuplicateIds::Error => {
+                    return Err(DuplicateIdError {
+                        id: element.id,
+                        sources: (first, origin.source),
+                    })
+                }
+                DuplicateIds::Reassign => {
+                    element.id = next_id;
+                    next_id += 1;
+                }
+            }
+        } else {
+            users.insert(element.id, origin.source);
+        }
+        merged.elements.push(element);
+        merged.origins.push(origin);
+    }
+    merged.fused = fused
+        .into_iter()
+        .map(|(origin, k)| (origin, merged.elements[k].id))
+        .collect();
+    Ok(merged)
+}
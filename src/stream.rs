@@ -0,0 +1,124 @@
+//! Reading order as an iterator, yielding ids as soon as their place is final
+
+use crate::core::{Algorithm, Region, XYCutPlusPlus};
+use crate::element::Element;
+use crate::marginalia::MarginaliaPolicy;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+/// Ids of a page in reading order, cutting one region per step
+struct OrderIter<'a> {
+    xycut: &'a XYCutPlusPlus,
+
+    /// Ids whose place is final; `ready[next..]` are not yielded yet
+    ready: Vec<usize>,
+    next: usize,
+
+    /// Working copy of the elements being cut, and the regions left to cut
+    elements: Vec<Element>,
+    regions: Vec<Region>,
+    trace: Trace<'static>,
+}
+
+impl Iterator for OrderIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.next == self.ready.len() {
+            let region = self.regions.pop()?;
+            self.ready.clear();
+            self.next = 0;
+            self.xycut.cut_step(
+                &mut self.elements,
+                region,
+                &mut self.regions,
+                &mut self.trace,
+                &mut self.ready,
+            );
+        }
+        self.next += 1;
+        Some(self.ready[self.next - 1])
+    }
+}
+
+impl XYCutPlusPlus {
+    /// [`compute_order`](Self::compute_order) as an iterator, yielding ids as the
+    /// regions of the recursive cut are finished, so text assembly can start before
+    /// a huge page is fully ordered
+    ///
+    /// Streaming needs the cut order to be final: it applies to Classic XY-Cut and to
+    /// pages without masked elements, unless `promote_straddlers`, `repair_inversions`,
+    /// `merge_drop_caps`, `group_figures`, `group_lists`, `group_tables`, a marginalia
+    /// placement policy, `pre_binning`, `fixed_point`, `snap_edges` or `quantization`
+    /// is set.
+    /// Other pages are ordered in full before the first id.
+    pub fn compute_order_iter<'a, T: BoundingBox>(
+        &'a self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let mut iter = OrderIter {
+            xycut: self,
+            ready: Vec::new(),
+            next: 0,
+            elements: Vec::new(),
+            regions: Vec::new(),
+            trace: Trace::default(),
+        };
+        let config = &self.config;
+        let streams = config.fixed_point.is_none()
+            && config.snap_edges.is_none()
+            && config.quantization.is_none()
+            && !config.repair_inversions
+            && !config.merge_drop_caps
+            && !config.group_figures
+            && !config.group_lists
+            && !config.group_tables
+            && config.marginalia == MarginaliaPolicy::Inline
+            && config.pre_binning.is_none_or(|min| elements.len() < min)
+            && (config.algorithm == Algorithm::ClassicXYCut || !config.promote_straddlers);
+        if !streams {
+            iter.ready = self.compute_order(elements, x_min, y_min, x_max, y_max);
+            return iter;
+        }
+
+        // The preprocessing of `compute_order`, up to the cut
+        let (width, height) = (x_max - x_min, y_max - y_min);
+        if elements.is_empty()
+            || !width.is_finite()
+            || !height.is_finite()
+            || width <= 0.0
+            || height <= 0.0
+        {
+            return iter;
+        }
+        let page = self.prepare_page(elements, (x_min, y_min, x_max, y_max));
+        if config.keep_background {
+            // Background boxes contain the whole page, so they come first
+            iter.ready = page.background;
+        }
+        let (mut upright, width, height) = (page.elements, page.width, page.height);
+        let bounds = (0.0, 0.0, width, height);
+
+        if config.algorithm != Algorithm::ClassicXYCut {
+            let partition = self.partition_page(&upright, bounds, &mut iter.trace);
+            if !partition.masked_elements.is_empty() {
+                // Masked elements are inserted anywhere once all regions are cut
+                let order = self.order_partition(&partition, bounds, &mut iter.trace);
+                iter.ready.extend(order);
+                return iter;
+            }
+            upright = partition.regular_elements;
+        }
+        iter.regions.push(Region {
+            range: 0..upright.len(),
+            bounds,
+            depth: 0,
+        });
+        iter.elements = upright;
+        iter
+    }
+}
@@ -0,0 +1,51 @@
+//! Elements with text for the unit tests
+
+use crate::element::Element;
+use crate::traits::{BoundingBox, SemanticLabel, TextContent};
+
+/// An [`Element`] carrying recognized text
+#[derive(Debug, Clone)]
+pub(crate) struct TextLine {
+    pub element: Element,
+    pub text: String,
+}
+
+/// Regular line `id` over `(x1, y1, x2, y2)` reading `text`
+pub(crate) fn line(id: usize, (x1, y1, x2, y2): (f32, f32, f32, f32), text: &str) -> TextLine {
+    TextLine {
+        element: Element::new(id, x1, y1, x2, y2, SemanticLabel::Regular),
+        text: text.to_string(),
+    }
+}
+
+impl BoundingBox for TextLine {
+    fn id(&self) -> usize {
+        self.element.id()
+    }
+
+    fn center(&self) -> (f32, f32) {
+        self.element.center()
+    }
+
+    fn bounds(&self) -> (f32, f32, f32, f32) {
+        self.element.bounds()
+    }
+
+    fn iou(&self, other: &Self) -> f32 {
+        self.element.iou(&other.element)
+    }
+
+    fn should_mask(&self) -> bool {
+        self.element.should_mask()
+    }
+
+    fn semantic_label(&self) -> SemanticLabel {
+        self.element.semantic_label()
+    }
+}
+
+impl TextContent for TextLine {
+    fn text(&self) -> &str {
+        &self.text
+    }
+}
@@ -0,0 +1,603 @@
+use std::collections::HashMap;
+
+use crate::traits::{BoundingBox, TextContent};
+use crate::utils::compute_median_height;
+
+/// Bullet characters recognized as list markers
+const BULLETS: [char; 7] = ['•', '◦', '▪', '‣', '-', '–', '*'];
+
+/// Symbols used as footnote markers, in their traditional sequence
+const FOOTNOTE_SYMBOLS: [char; 5] = ['*', '†', '‡', '§', '¶'];
+
+/// Maximum height of a box holding only a footnote marker, relative to the median
+/// element height, for it to count as a superscript
+const SUPERSCRIPT_HEIGHT_RATIO: f32 = 0.75;
+
+/// Maximum height of a line, relative to the median element height, for it to count
+/// as set in the smaller font of footnotes
+const FOOTNOTE_HEIGHT_RATIO: f32 = 0.9;
+
+/// List item marker found at the start of a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListMarker {
+    /// Unnumbered bullet such as "•" or "-"
+    Bullet,
+    /// Arabic number such as "3.", "3)" or "(3)"
+    Number(u32),
+    /// Single letter such as "b." or "(b)", stored lowercase
+    Letter(char),
+}
+
+/// Reference to where an article continues, parsed from text like "continued on page 3"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Continuation {
+    /// The text continues on the given page
+    OnPage(u32),
+    /// The text continues from the given page
+    FromPage(u32),
+}
+
+/// Footnote marker, found as a reference in running text or opening a footnote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FootnoteMarker {
+    /// Number such as "¹" or "12"
+    Number(u32),
+    /// Symbol such as "*" or "†"
+    Symbol(char),
+}
+
+/// Link from an element referencing a footnote to the footnote itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FootnoteLink {
+    /// Id of the element holding the reference marker
+    pub reference: usize,
+    /// Id of the footnote element
+    pub footnote: usize,
+    /// Marker shared by both
+    pub marker: FootnoteMarker,
+}
+
+/// A break in a numbered list sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListSequenceBreak {
+    /// Id of the list item that broke the sequence
+    pub id: usize,
+    /// Ordinal expected after the previous item
+    pub expected: u32,
+    /// Ordinal actually found
+    pub found: u32,
+}
+
+/// Detect a list marker at the start of `text`
+pub fn list_marker(text: &str) -> Option<ListMarker> {
+    split_list_marker(text).map(|(marker, _)| marker)
+}
+
+/// Split a leading list marker from `text`, returning the marker and the item text
+pub fn split_list_marker(text: &str) -> Option<(ListMarker, &str)> {
+    let text = text.trim_start();
+    let mut chars = text.chars();
+    let first = chars.next()?;
+
+    if BULLETS.contains(&first) {
+        return match chars.next() {
+            Some(c) if c.is_whitespace() => Some((ListMarker::Bullet, chars.as_str().trim_start())),
+            _ => None,
+        };
+    }
+
+    // Strip an opening parenthesis: "(3)" and "(b)"
+    let (body, parenthesized) = match text.strip_prefix('(') {
+        Some(rest) => (rest, true),
+        None => (text, false),
+    };
+
+    let token_end = body.find(['.', ')']).filter(|&end| end > 0)?;
+    let (token, rest) = body.split_at(token_end);
+    if parenthesized && !rest.starts_with(')') {
+        return None;
+    }
+
+    // Marker must be followed by whitespace or end the line
+    let item = &rest[1..];
+    if !item.chars().next().is_none_or(char::is_whitespace) {
+        return None;
+    }
+    let item = item.trim_start();
+
+    if token.len() <= 3 && token.chars().all(|c| c.is_ascii_digit()) {
+        return token.parse().ok().map(|n| (ListMarker::Number(n), item));
+    }
+
+    let mut letters = token.chars();
+    match (letters.next(), letters.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => {
+            Some((ListMarker::Letter(c.to_ascii_lowercase()), item))
+        }
+        _ => None,
+    }
+}
+
+/// Check numbered and lettered list items along `order` for skipped or repeated ordinals
+///
+/// Lines without a marker (wrapped item text) don't interrupt a list; masked
+/// elements (titles, figures) end it. A list restarting at 1 (or "a") is accepted.
+pub fn validate_list_sequences<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+) -> Vec<ListSequenceBreak> {
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+
+    let mut breaks = Vec::new();
+    let mut last_number: Option<u32> = None;
+    let mut last_letter: Option<u32> = None;
+
+    for id in order {
+        let Some(element) = by_id.get(id) else {
+            continue;
+        };
+
+        if element.should_mask() {
+            last_number = None;
+            last_letter = None;
+            continue;
+        }
+
+        let (last, found) = match list_marker(element.text()) {
+            Some(ListMarker::Number(n)) => (&mut last_number, n),
+            Some(ListMarker::Letter(c)) => (&mut last_letter, c as u32 - 'a' as u32 + 1),
+            _ => continue,
+        };
+
+        if let Some(previous) = *last {
+            if found != previous + 1 && found != 1 {
+                breaks.push(ListSequenceBreak {
+                    id: *id,
+                    expected: previous + 1,
+                    found,
+                });
+            }
+        }
+        *last = Some(found);
+    }
+
+    breaks
+}
+
+/// Detect a jump reference such as "continued on page 3" or "cont'd from p. 2"
+pub fn detect_continuation(text: &str) -> Option<Continuation> {
+    const PATTERNS: [(&str, bool); 6] = [
+        ("continued on", true),
+        ("continued from", false),
+        ("cont. on", true),
+        ("cont. from", false),
+        ("cont'd on", true),
+        ("cont'd from", false),
+    ];
+
+    let lower = text.to_lowercase();
+    for (pattern, forward) in PATTERNS {
+        let Some(start) = lower.find(pattern) else {
+            continue;
+        };
+
+        let rest = lower[start + pattern.len()..].trim_start();
+        let rest = rest
+            .strip_prefix("page")
+            .or_else(|| rest.strip_prefix("p."))
+            .unwrap_or(rest)
+            .trim_start();
+        let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+
+        if let Ok(page) = digits.parse() {
+            return Some(if forward {
+                Continuation::OnPage(page)
+            } else {
+                Continuation::FromPage(page)
+            });
+        }
+    }
+
+    None
+}
+
+/// Collect every element in `order` carrying a continuation reference
+pub fn find_continuations<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+) -> Vec<(usize, Continuation)> {
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+
+    order
+        .iter()
+        .filter_map(|id| by_id.get(id))
+        .filter_map(|e| detect_continuation(e.text()).map(|c| (e.id(), c)))
+        .collect()
+}
+
+/// Value of a superscript digit ("¹" is 1)
+fn superscript_digit(c: char) -> Option<u32> {
+    match c {
+        '⁰' => Some(0),
+        '¹' => Some(1),
+        '²' => Some(2),
+        '³' => Some(3),
+        '⁴'..='⁹' => Some(c as u32 - '⁴' as u32 + 4),
+        _ => None,
+    }
+}
+
+/// Detect the footnote references in running text: superscript numbers ("word²")
+/// and footnote symbols ("word†") right after a word
+pub fn footnote_references(text: &str) -> Vec<FootnoteMarker> {
+    let mut references = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut digits: Vec<u32> = Vec::new();
+    for c in text.chars() {
+        match superscript_digit(c) {
+            Some(digit) if !digits.is_empty() || previous.is_some_and(|p| !p.is_whitespace()) => {
+                digits.push(digit);
+            }
+            _ => {
+                if !digits.is_empty() {
+                    references.push(FootnoteMarker::Number(number(&digits)));
+                    digits.clear();
+                }
+                if FOOTNOTE_SYMBOLS.contains(&c) && previous.is_some_and(char::is_alphanumeric) {
+                    references.push(FootnoteMarker::Symbol(c));
+                }
+            }
+        }
+        previous = Some(c);
+    }
+    if !digits.is_empty() {
+        references.push(FootnoteMarker::Number(number(&digits)));
+    }
+    references
+}
+
+/// Detect the marker opening a footnote: "¹ Text", or "1 Text" and "† Text" in a small font
+///
+/// A line opening with a number or symbol is only a footnote with a cue that sets it
+/// apart from running text: superscript digits, or `small_font` (the line is set
+/// smaller than the body text). Plain digits need whitespace after them (at most
+/// three), so "1." and "1)" stay list markers.
+pub fn footnote_marker(text: &str, small_font: bool) -> Option<FootnoteMarker> {
+    let text = text.trim_start();
+    let first = text.chars().next()?;
+
+    let superscript: Vec<u32> = text.chars().map_while(superscript_digit).collect();
+    if !superscript.is_empty() {
+        return Some(FootnoteMarker::Number(number(&superscript)));
+    }
+    if !small_font {
+        return None;
+    }
+
+    if FOOTNOTE_SYMBOLS.contains(&first) {
+        return Some(FootnoteMarker::Symbol(first));
+    }
+    let digits: String = text.chars().take_while(char::is_ascii_digit).collect();
+    let rest = &text[digits.len()..];
+    if digits.is_empty() || digits.len() > 3 || !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    digits.parse().ok().map(FootnoteMarker::Number)
+}
+
+/// The marker making up all of `text` ("²", "12" or "*"), as in a box the detector
+/// split off its line
+fn lone_marker(text: &str) -> Option<FootnoteMarker> {
+    let text = text.trim();
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(symbol), None) if FOOTNOTE_SYMBOLS.contains(&symbol) => {
+            return Some(FootnoteMarker::Symbol(symbol));
+        }
+        (None, _) => return None,
+        _ => {}
+    }
+    if let Some(digits) = text
+        .chars()
+        .map(superscript_digit)
+        .collect::<Option<Vec<u32>>>()
+    {
+        return Some(FootnoteMarker::Number(number(&digits)));
+    }
+    if text.len() <= 3 && text.chars().all(|c| c.is_ascii_digit()) {
+        return text.parse().ok().map(FootnoteMarker::Number);
+    }
+    None
+}
+
+/// Value of a sequence of decimal digits, saturating
+fn number(digits: &[u32]) -> u32 {
+    digits
+        .iter()
+        .fold(0u32, |n, digit| n.saturating_mul(10).saturating_add(*digit))
+}
+
+/// Link footnote references to their footnotes along `order`
+///
+/// References are markers right after a word (see [`footnote_references`]) or
+/// boxes holding nothing but a marker, shorter than three quarters of the median
+/// element height. Footnotes are elements opening with a marker (see
+/// [`footnote_marker`]); plain numbers and symbols only count in lines under nine
+/// tenths of the median element height. Each reference links to the first footnote after it in
+/// `order` with the same marker, so numbering restarting on every page or chapter
+/// links up; references without a matching footnote are left out.
+pub fn link_footnotes<T: BoundingBox + TextContent>(
+    elements: &[T],
+    order: &[usize],
+) -> Vec<FootnoteLink> {
+    let by_id: HashMap<usize, &T> = elements.iter().map(|e| (e.id(), e)).collect();
+    let median_height = compute_median_height(elements);
+    let superscript_height = SUPERSCRIPT_HEIGHT_RATIO * median_height;
+    let footnote_height = FOOTNOTE_HEIGHT_RATIO * median_height;
+    let ordered: Vec<&T> = order
+        .iter()
+        .filter_map(|id| by_id.get(id).copied())
+        .collect();
+
+    let mut links = Vec::new();
+    for (position, element) in ordered.iter().enumerate() {
+        let (_, y1, _, y2) = element.bounds();
+        let references = match lone_marker(element.text()) {
+            Some(marker) if y2 - y1 <= superscript_height => vec![marker],
+            _ => footnote_references(element.text()),
+        };
+        for marker in references {
+            let footnote = ordered[position + 1..].iter().find(|e| {
+                let (_, y1, _, y2) = e.bounds();
+                footnote_marker(e.text(), y2 - y1 <= footnote_height) == Some(marker)
+            });
+            if let Some(footnote) = footnote {
+                links.push(FootnoteLink {
+                    reference: element.id(),
+                    footnote: footnote.id(),
+                    marker,
+                });
+            }
+        }
+    }
+
+    links
+}
+
+/// Whether `text` ends with a word broken by a line-end hyphen ("docu-")
+pub fn ends_with_hyphenation(text: &str) -> bool {
+    let mut chars = text.trim_end().chars().rev();
+    matches!(
+        (chars.next(), chars.next()),
+        (Some('-') | Some('\u{00AD}'), Some(c)) if c.is_alphabetic()
+    )
+}
+
+/// Join line texts into one paragraph string, undoing line-end hyphenation
+///
+/// A hyphen is only removed when the next line continues in lowercase,
+/// so compounds broken before a capitalized word ("Franco-" + "German") keep it.
+pub fn join_lines<'a>(lines: impl IntoIterator<Item = &'a str>) -> String {
+    let mut joined = String::new();
+    for line in lines {
+        append_line(&mut joined, line);
+    }
+    joined
+}
+
+/// Append one line to a paragraph string, with the same rules as [`join_lines`]
+pub(crate) fn append_line(joined: &mut String, line: &str) {
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
+
+    if joined.is_empty() {
+        joined.push_str(line);
+    } else if ends_with_hyphenation(joined) {
+        if line.chars().next().is_some_and(char::is_lowercase) {
+            joined.pop();
+        }
+        joined.push_str(line);
+    } else {
+        joined.push(' ');
+        joined.push_str(line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::line;
+
+    #[test]
+    fn list_markers() {
+        assert_eq!(list_marker("• item"), Some(ListMarker::Bullet));
+        assert_eq!(list_marker("  - item"), Some(ListMarker::Bullet));
+        assert_eq!(list_marker("3. item"), Some(ListMarker::Number(3)));
+        assert_eq!(list_marker("12) item"), Some(ListMarker::Number(12)));
+        assert_eq!(list_marker("(3) item"), Some(ListMarker::Number(3)));
+        assert_eq!(list_marker("B. item"), Some(ListMarker::Letter('b')));
+        assert_eq!(list_marker("(b) item"), Some(ListMarker::Letter('b')));
+        assert_eq!(list_marker("4."), Some(ListMarker::Number(4)));
+    }
+
+    #[test]
+    fn ordinary_text_has_no_list_marker() {
+        assert_eq!(list_marker("-5 degrees"), None);
+        assert_eq!(list_marker("3.5 million"), None);
+        assert_eq!(list_marker("1999. A year"), None);
+        assert_eq!(list_marker("(3 items)"), None);
+        assert_eq!(list_marker("e.g. this"), None);
+        assert_eq!(list_marker("Mr. Smith"), None);
+        assert_eq!(list_marker(""), None);
+    }
+
+    #[test]
+    fn split_list_marker_returns_the_item_text() {
+        assert_eq!(
+            split_list_marker("(a)   first"),
+            Some((ListMarker::Letter('a'), "first"))
+        );
+        assert_eq!(split_list_marker("• "), Some((ListMarker::Bullet, "")));
+    }
+
+    #[test]
+    fn list_sequence_breaks() {
+        let lines = [
+            line(0, (0.0, 0.0, 100.0, 10.0), "1. one"),
+            line(1, (0.0, 14.0, 100.0, 24.0), "wrapped text"),
+            line(2, (0.0, 28.0, 100.0, 38.0), "2. two"),
+            line(3, (0.0, 42.0, 100.0, 52.0), "4. four"),
+            line(4, (0.0, 56.0, 100.0, 66.0), "1. restart"),
+            line(5, (0.0, 70.0, 100.0, 80.0), "a. letter"),
+            line(6, (0.0, 84.0, 100.0, 94.0), "c. letter"),
+        ];
+        assert_eq!(
+            validate_list_sequences(&lines, &[0, 1, 2, 3, 4, 5, 6]),
+            vec![
+                ListSequenceBreak {
+                    id: 3,
+                    expected: 3,
+                    found: 4
+                },
+                ListSequenceBreak {
+                    id: 6,
+                    expected: 2,
+                    found: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn masked_elements_end_a_list() {
+        let mut lines = vec![
+            line(0, (0.0, 0.0, 100.0, 10.0), "1. one"),
+            line(1, (0.0, 14.0, 100.0, 24.0), "Heading"),
+            line(2, (0.0, 28.0, 100.0, 38.0), "5. five"),
+        ];
+        assert_eq!(validate_list_sequences(&lines, &[0, 1, 2]).len(), 1);
+        lines[1].element.mask = true;
+        assert!(validate_list_sequences(&lines, &[0, 1, 2]).is_empty());
+    }
+
+    #[test]
+    fn continuations() {
+        assert_eq!(
+            detect_continuation("Continued on page 7"),
+            Some(Continuation::OnPage(7))
+        );
+        assert_eq!(
+            detect_continuation("(cont'd from p. 12)"),
+            Some(Continuation::FromPage(12))
+        );
+        assert_eq!(
+            detect_continuation("See story, cont. on 3"),
+            Some(Continuation::OnPage(3))
+        );
+        assert_eq!(detect_continuation("continued on the next page"), None);
+        assert_eq!(detect_continuation("The talks continued."), None);
+
+        let lines = [
+            line(0, (0.0, 0.0, 100.0, 10.0), "Story text"),
+            line(1, (0.0, 14.0, 100.0, 24.0), "Continued on page 4"),
+        ];
+        assert_eq!(
+            find_continuations(&lines, &[1, 0]),
+            vec![(1, Continuation::OnPage(4))]
+        );
+    }
+
+    #[test]
+    fn footnote_markers_need_a_cue() {
+        assert_eq!(
+            footnote_marker("¹² Smith 2020.", false),
+            Some(FootnoteMarker::Number(12))
+        );
+        assert_eq!(footnote_marker("1 Introduction", false), None);
+        assert_eq!(footnote_marker("* Note", false), None);
+        assert_eq!(
+            footnote_marker("1 Smith 2020.", true),
+            Some(FootnoteMarker::Number(1))
+        );
+        assert_eq!(
+            footnote_marker("† Deceased.", true),
+            Some(FootnoteMarker::Symbol('†'))
+        );
+        assert_eq!(footnote_marker("1. Item", true), None);
+        assert_eq!(footnote_marker("2024 was a year", true), None);
+    }
+
+    #[test]
+    fn footnote_references_follow_words() {
+        assert_eq!(
+            footnote_references("results¹² and claims† hold"),
+            vec![FootnoteMarker::Number(12), FootnoteMarker::Symbol('†')]
+        );
+        assert!(footnote_references("x ² and * bullet").is_empty());
+    }
+
+    #[test]
+    fn footnotes_link_with_a_cue() {
+        let lines = [
+            line(0, (0.0, 0.0, 200.0, 10.0), "As shown before¹ and in†"),
+            line(1, (0.0, 14.0, 200.0, 24.0), "1 Introduction"),
+            line(2, (0.0, 28.0, 200.0, 38.0), "more text"),
+            line(3, (0.0, 42.0, 200.0, 52.0), "further text"),
+            line(4, (0.0, 60.0, 200.0, 68.0), "1 Smith 2020."),
+            line(5, (0.0, 70.0, 200.0, 80.0), "† Deceased."),
+        ];
+        // The body-sized "1 Introduction" and "† Deceased." lack a cue
+        assert_eq!(
+            link_footnotes(&lines, &[0, 1, 2, 3, 4, 5]),
+            vec![FootnoteLink {
+                reference: 0,
+                footnote: 4,
+                marker: FootnoteMarker::Number(1),
+            }]
+        );
+    }
+
+    #[test]
+    fn lone_marker_boxes_link_to_the_next_footnote() {
+        let lines = [
+            line(0, (0.0, 0.0, 200.0, 10.0), "A claim"),
+            line(1, (200.0, 0.0, 204.0, 5.0), "2"),
+            line(2, (0.0, 14.0, 200.0, 24.0), "² First page note."),
+            line(3, (0.0, 28.0, 200.0, 38.0), "² Second page note."),
+            line(4, (0.0, 42.0, 200.0, 52.0), "2"),
+        ];
+        // The body-sized lone "2" is not a superscript
+        assert_eq!(
+            link_footnotes(&lines, &[0, 1, 2, 3, 4]),
+            vec![FootnoteLink {
+                reference: 1,
+                footnote: 2,
+                marker: FootnoteMarker::Number(2),
+            }]
+        );
+    }
+
+    #[test]
+    fn hyphenation() {
+        assert!(ends_with_hyphenation("a docu-"));
+        assert!(ends_with_hyphenation("a docu\u{00AD}  "));
+        assert!(!ends_with_hyphenation("pages 3-"));
+        assert!(!ends_with_hyphenation("a dash -"));
+        assert!(!ends_with_hyphenation("-"));
+    }
+
+    #[test]
+    fn join_lines_undoes_hyphenation() {
+        assert_eq!(
+            join_lines(["The docu-", "ment was", "", "  signed.  "]),
+            "The document was signed."
+        );
+        assert_eq!(
+            join_lines(["Franco-", "German talks"]),
+            "Franco-German talks"
+        );
+        assert_eq!(join_lines(Vec::<&str>::new()), "");
+    }
+}
@@ -0,0 +1,222 @@
+//! Record of the decisions taken while ordering one page
+
+use crate::ambiguity::AmbiguityKind;
+use crate::columns::ColumnHints;
+use crate::core::XYCutPlusPlus;
+use crate::histogram::{build_horizontal_histogram, build_vertical_histogram};
+use crate::observer::{Cut, Insertion, OrderingObserver};
+use crate::preference::{CutPreference, RegionStats};
+use crate::profile::ProfileReport;
+use crate::traits::{BoundingBox, SemanticLabel};
+use crate::unanchored::Unanchored;
+use crate::workspace::Workspace;
+
+/// How a region was divided
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Decision {
+    Cut(Cut),
+    /// No cut: ordered by position
+    Fallback,
+}
+
+/// A region visited by the recursive cut
+///
+/// Recorded only for the state dump and the SVG plots.
+#[derive(Debug, Clone)]
+#[cfg_attr(not(any(feature = "viz-svg", feature = "dump")), allow(dead_code))]
+pub(crate) struct RegionState {
+    pub bounds: (f32, f32, f32, f32),
+    pub ids: Vec<usize>,
+    /// Boxes of `ids`, in the frame the page was cut in
+    pub boxes: Vec<(f32, f32, f32, f32)>,
+    /// Labels of `ids`
+    #[cfg_attr(not(feature = "dump"), allow(dead_code))]
+    pub labels: Vec<SemanticLabel>,
+    /// Projection histograms along x (columns) and y (rows)
+    pub histograms: (Vec<usize>, Vec<usize>),
+    pub decision: Option<Decision>,
+    /// Density the vertical-first rule compared (τd by default)
+    pub density: Option<f32>,
+}
+
+impl XYCutPlusPlus {
+    /// Snapshot a region with the projection histograms its cut was chosen from
+    pub(crate) fn region_state<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> RegionState {
+        let x_resolution = self.histogram_resolution(x_max - x_min);
+        let y_resolution = self.histogram_resolution(y_max - y_min);
+        RegionState {
+            bounds: (x_min, y_min, x_max, y_max),
+            ids: elements.iter().map(|e| e.id()).collect(),
+            boxes: elements.iter().map(|e| e.bounds()).collect(),
+            labels: elements.iter().map(|e| e.semantic_label()).collect(),
+            histograms: (
+                build_vertical_histogram(elements, x_min, x_max, x_resolution),
+                build_horizontal_histogram(elements, y_min, y_max, y_resolution),
+            ),
+            decision: None,
+            density: None,
+        }
+    }
+}
+
+/// A cut with the ids on either side and the whitespace between them
+#[derive(Debug, Clone)]
+pub(crate) struct CutGap {
+    /// Gap along the cut axis, negative when the sides overlap
+    pub gap: f32,
+    pub before: Vec<usize>,
+    pub after: Vec<usize>,
+}
+
+/// Decisions collected along the pipeline, for APIs reporting more than the order
+#[derive(Default)]
+pub(crate) struct Trace<'a> {
+    /// Groups of element ids ordered by the fallback position sort, i.e. without a cut
+    pub fallback_groups: Vec<Vec<usize>>,
+
+    /// Ids of elements straddling a cut, set aside for reinsertion
+    pub promoted: Vec<usize>,
+
+    /// Nesting depth of the recursive cut
+    pub depth: usize,
+
+    /// Deepest nesting reached
+    pub max_depth: usize,
+
+    /// Number of masked elements placed by the insertion stage
+    pub inserted: usize,
+
+    /// Inserted elements with no valid anchor, with the reason
+    pub unanchored: Vec<Unanchored>,
+
+    /// Number of inversions swapped by the smoothing pass
+    pub repairs: usize,
+
+    /// Ids left out of the order as speckle noise, page background or excluded marginalia
+    pub dropped: Vec<usize>,
+
+    /// Whether to record marginal decisions into `ambiguities`
+    pub track_ambiguity: bool,
+
+    /// Marginal cuts and near-tied insertions with the element ids involved
+    pub ambiguities: Vec<(AmbiguityKind, Vec<usize>)>,
+
+    /// Whether to record every cut's gap and insertion margin below
+    pub track_confidence: bool,
+
+    /// Gaps of the cuts taken, outermost first
+    pub cut_gaps: Vec<CutGap>,
+
+    /// Masked element ids with how clearly their insertion point beat the runner-up,
+    /// from 0 (tied, or appended without an anchor) to 1
+    pub insertion_margins: Vec<(usize, f32)>,
+
+    /// Gutters carried from earlier pages, tried where a region has no vertical cut
+    pub column_hints: Vec<f32>,
+
+    /// How `column_hints` are applied
+    pub hint_mode: ColumnHints,
+
+    /// Content area learned from other pages, judging centrality instead of the
+    /// page's own (see `content_trim`)
+    pub content_area: Option<(f32, f32, f32, f32)>,
+
+    /// Whether to record vertical cut positions into `columns`
+    pub record_columns: bool,
+
+    /// Positions of the vertical cuts taken
+    pub columns: Vec<f32>,
+
+    /// Whether to record the partition, regions and insertions below
+    pub record_state: bool,
+
+    /// Ids of the regular and masked elements of the pre-mask partition
+    pub partition: (Vec<usize>, Vec<usize>),
+
+    /// Regions in the order they were visited
+    pub regions: Vec<RegionState>,
+
+    /// Masked element insertions in the order they were made
+    pub insertions: Vec<Insertion>,
+
+    /// Time spent per stage; `total` and `allocations` are left to the caller
+    pub profile: ProfileReport,
+
+    /// Buffers reused across calls (empty unless the caller supplies a workspace)
+    pub workspace: Workspace,
+
+    /// User callbacks notified of each decision
+    pub observer: Option<&'a mut dyn OrderingObserver>,
+
+    /// User callback choosing the axis each region is cut along first
+    pub cut_preference: Option<&'a dyn Fn(&RegionStats) -> CutPreference>,
+}
+
+impl Trace<'_> {
+    /// Whether partition and insertion details are wanted by anyone
+    pub fn observing(&self) -> bool {
+        self.record_state || self.observer.is_some()
+    }
+
+    pub fn partition(&mut self, regular: Vec<usize>, masked: Vec<usize>) {
+        if let Some(observer) = self.observer.as_deref_mut() {
+            observer.on_partition(&regular, &masked);
+        }
+        if self.record_state {
+            self.partition.0.extend(regular);
+            self.partition.1.extend(masked);
+        }
+    }
+
+    /// Record how the region holding `ids` was cut
+    pub fn cut(&mut self, cut: Cut, ids: &[usize]) {
+        self.decide(Decision::Cut(cut));
+        if let Some(observer) = self.observer.as_deref_mut() {
+            observer.on_cut(&cut, ids);
+        }
+    }
+
+    /// Record a region ordered by the fallback position sort
+    pub fn fallback(&mut self, order: &[usize]) {
+        self.decide(Decision::Fallback);
+        if let Some(observer) = self.observer.as_deref_mut() {
+            observer.on_fallback_sort(order);
+        }
+        self.fallback_groups.push(order.to_vec());
+    }
+
+    pub fn insertion(&mut self, insertion: Insertion) {
+        if let Some(observer) = self.observer.as_deref_mut() {
+            observer.on_insertion(&insertion);
+        }
+        if self.record_state {
+            self.insertions.push(insertion);
+        }
+    }
+
+    /// Record the density of the region holding `ids`, computed before its cut
+    pub fn density(&mut self, density: f32, ids: &[usize]) {
+        if let Some(region) = self.regions.last_mut() {
+            region.density = Some(density);
+        }
+        if let Some(observer) = self.observer.as_deref_mut() {
+            observer.on_density(density, ids);
+        }
+    }
+
+    /// Attach a decision to the region being visited
+    ///
+    /// Decisions are taken before recursing, so the current region is the last one visited.
+    fn decide(&mut self, decision: Decision) {
+        if let Some(region) = self.regions.last_mut() {
+            region.decision = Some(decision);
+        }
+    }
+}
@@ -1,12 +1,24 @@
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum SemanticLabel {
     CrossLayout,
     HorizontalTitle,
     VerticalTitle,
     Vision,
+    /// Mathematical expression: display equations are masked and kept between their
+    /// paragraphs, inline ones are ordered as text (see `XYCutConfig::formulas`)
+    Formula,
     Regular,
 }
 
+impl SemanticLabel {
+    /// Whether this label is a horizontal or vertical title
+    pub fn is_title(self) -> bool {
+        matches!(self, Self::HorizontalTitle | Self::VerticalTitle)
+    }
+}
+
 /// Core trait that any bounding box must implement to use XY-Cut++
 ///
 /// # Paper Reference
@@ -36,4 +48,62 @@ pub trait BoundingBox: Clone {
 
     /// Returns the semantic label type for this element
     fn semantic_label(&self) -> SemanticLabel;
+
+    /// Returns the detector confidence in [0, 1] for this element's label, if known
+    ///
+    /// Elements whose confidence falls below `XYCutConfig::mask_confidence_threshold`
+    /// are not masked even if `should_mask` returns true.
+    fn confidence(&self) -> Option<f32> {
+        None
+    }
+
+    /// Returns the stacking position of this element, higher drawn on top, if known
+    ///
+    /// Slide decks keep the designer's z-order; see
+    /// [`compute_slide_order`](crate::XYCutPlusPlus::compute_slide_order).
+    fn z_index(&self) -> Option<i32> {
+        None
+    }
+
+    /// Returns the id of the shape group this element belongs to, if any
+    ///
+    /// Grouped shapes on a slide are read together as one unit.
+    fn group(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the language of this element's text, as an index, if known
+    ///
+    /// Bilingual documents set it to keep each translation's column apart; see
+    /// [`compute_parallel_order`](crate::XYCutPlusPlus::compute_parallel_order).
+    fn language(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Optional extension trait for elements that carry recognized text
+///
+/// Implementing it alongside [`BoundingBox`] enables content-aware
+/// post-processing: list-number sequence validation, continuation
+/// detection ("continued on page 3") and hyphenation-aware paragraph merging.
+pub trait TextContent {
+    /// Returns the text content of this element
+    fn text(&self) -> &str;
+}
+
+/// Anything that turns a page of elements into a reading order
+///
+/// Implemented by [`XYCutPlusPlus`](crate::XYCutPlusPlus) and the simple
+/// [`Baseline`](crate::baselines::Baseline) orderings, so they can be evaluated
+/// interchangeably.
+pub trait ReadingOrder {
+    /// Element ids in reading order for a page spanning `(x_min, y_min)`–`(x_max, y_max)`
+    fn order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Vec<usize>;
 }
@@ -0,0 +1,232 @@
+//! Search over `XYCutConfig` thresholds for the values scoring best on annotated pages
+
+use crate::core::{XYCutConfig, XYCutPlusPlus};
+use crate::eval::{average_relative_distance, kendall_tau, AnnotatedPage};
+
+/// A numeric `XYCutConfig` field the tuner can vary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Parameter {
+    MinCutThreshold,
+    HistogramResolutionScale,
+    SameRowTolerance,
+    MaskConfidenceThreshold,
+    BackgroundCoverage,
+    CutOverlapTolerance,
+    ContinuityPenalty,
+    VerticalFirstThreshold,
+}
+
+impl Parameter {
+    /// Current value of the field in `config`
+    pub fn get(self, config: &XYCutConfig) -> f32 {
+        match self {
+            Self::MinCutThreshold => config.min_cut_threshold,
+            Self::HistogramResolutionScale => config.histogram_resolution_scale,
+            Self::SameRowTolerance => config.same_row_tolerance,
+            Self::MaskConfidenceThreshold => config.mask_confidence_threshold,
+            Self::BackgroundCoverage => config.background_coverage,
+            Self::CutOverlapTolerance => config.cut_overlap_tolerance,
+            Self::ContinuityPenalty => config.distance.continuity_penalty,
+            Self::VerticalFirstThreshold => config.vertical_first_threshold,
+        }
+    }
+
+    /// Set the field in `config` to `value`
+    pub fn set(self, config: &mut XYCutConfig, value: f32) {
+        let field = match self {
+            Self::MinCutThreshold => &mut config.min_cut_threshold,
+            Self::HistogramResolutionScale => &mut config.histogram_resolution_scale,
+            Self::SameRowTolerance => &mut config.same_row_tolerance,
+            Self::MaskConfidenceThreshold => &mut config.mask_confidence_threshold,
+            Self::BackgroundCoverage => &mut config.background_coverage,
+            Self::CutOverlapTolerance => &mut config.cut_overlap_tolerance,
+            Self::ContinuityPenalty => &mut config.distance.continuity_penalty,
+            Self::VerticalFirstThreshold => &mut config.vertical_first_threshold,
+        };
+        *field = value;
+    }
+}
+
+/// Metric maximized by the tuner, averaged over the pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Metric {
+    /// Mean Kendall's τ (higher is better)
+    #[default]
+    KendallTau,
+    /// Mean average relative distance, negated so that higher is better
+    AverageRelativeDistance,
+}
+
+impl Metric {
+    /// Score of one page; higher is better
+    pub fn score(self, predicted: &[usize], truth: &[usize]) -> f32 {
+        match self {
+            Self::KendallTau => kendall_tau(predicted, truth),
+            Self::AverageRelativeDistance => -average_relative_distance(predicted, truth),
+        }
+    }
+}
+
+/// How candidate configs are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Search {
+    /// Every combination of `steps` evenly spaced values per parameter
+    /// (`steps ^ parameters` candidates)
+    Grid { steps: usize },
+    /// `samples` configs with values drawn uniformly from the ranges; the same seed
+    /// gives the same candidates
+    Random { samples: usize, seed: u64 },
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Self::Grid { steps: 5 }
+    }
+}
+
+/// What to search and how
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TuningConfig {
+    /// Starting point; fields not listed in `parameters` keep its values
+    pub base: XYCutConfig,
+
+    /// Parameters to vary with their inclusive `(min, max)` range
+    pub parameters: Vec<(Parameter, f32, f32)>,
+
+    pub metric: Metric,
+
+    pub search: Search,
+}
+
+impl Default for TuningConfig {
+    fn default() -> Self {
+        Self {
+            base: XYCutConfig::default(),
+            parameters: vec![
+                (Parameter::MinCutThreshold, 5.0, 40.0),
+                (Parameter::HistogramResolutionScale, 0.25, 2.0),
+                (Parameter::SameRowTolerance, 2.0, 30.0),
+            ],
+            metric: Metric::default(),
+            search: Search::default(),
+        }
+    }
+}
+
+/// Best config found by [`tune`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TuningResult {
+    pub config: XYCutConfig,
+
+    /// Mean metric of `config` over the pages
+    pub score: f32,
+
+    /// Mean metric of the base config, for comparison
+    pub base_score: f32,
+
+    /// Number of configs evaluated, the base included
+    pub evaluated: usize,
+}
+
+/// Search the parameter space for the config maximizing the mean metric over `pages`
+///
+/// The base config is evaluated first and kept unless a candidate scores strictly
+/// higher, so the result is never worse than the starting point on these pages.
+/// Ties go to the earlier candidate, which makes the result deterministic.
+pub fn tune(pages: &[AnnotatedPage], tuning: &TuningConfig) -> TuningResult {
+    let base_score = evaluate(pages, &tuning.base, tuning.metric);
+    let mut result = TuningResult {
+        config: tuning.base.clone(),
+        score: base_score,
+        base_score,
+        evaluated: 1,
+    };
+
+    for values in candidates(tuning) {
+        let mut config = tuning.base.clone();
+        for (&(parameter, _, _), value) in tuning.parameters.iter().zip(values) {
+            parameter.set(&mut config, value);
+        }
+        let score = evaluate(pages, &config, tuning.metric);
+        result.evaluated += 1;
+        if score > result.score {
+            result.config = config;
+            result.score = score;
+        }
+    }
+    result
+}
+
+/// Mean metric of `config` over `pages` (0 for no pages)
+pub fn evaluate(pages: &[AnnotatedPage], config: &XYCutConfig, metric: Metric) -> f32 {
+    if pages.is_empty() {
+        return 0.0;
+    }
+    let xycut = XYCutPlusPlus::new(config.clone());
+    let total: f32 = pages
+        .iter()
+        .map(|page| {
+            let (x_min, y_min, x_max, y_max) = page.page;
+            let predicted = xycut.compute_order(&page.elements, x_min, y_min, x_max, y_max);
+            metric.score(&predicted, &page.order)
+        })
+        .sum();
+    total / pages.len() as f32
+}
+
+/// Parameter values of every candidate, in `tuning.parameters` order
+fn candidates(tuning: &TuningConfig) -> Vec<Vec<f32>> {
+    let ranges = &tuning.parameters;
+    match tuning.search {
+        Search::Grid { steps } => {
+            let value = |(_, min, max): (Parameter, f32, f32), step: usize| {
+                if steps < 2 {
+                    (min + max) / 2.0
+                } else {
+                    min + (max - min) * step as f32 / (steps - 1) as f32
+                }
+            };
+            let mut grid = vec![Vec::new()];
+            for &range in ranges {
+                grid = grid
+                    .into_iter()
+                    .flat_map(|prefix| {
+                        (0..steps.max(1)).map(move |step| {
+                            let mut values = prefix.clone();
+                            values.push(value(range, step));
+                            values
+                        })
+                    })
+                    .collect();
+            }
+            grid
+        }
+        Search::Random { samples, seed } => {
+            let mut state = seed;
+            (0..samples)
+                .map(|_| {
+                    ranges
+                        .iter()
+                        .map(|&(_, min, max)| min + (max - min) * unit(&mut state))
+                        .collect()
+                })
+                .collect()
+        }
+    }
+}
+
+/// Uniform sample in `[0, 1)` from a SplitMix64 stream
+fn unit(state: &mut u64) -> f32 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 40) as f32 / (1u64 << 24) as f32
+}
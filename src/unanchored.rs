@@ -0,0 +1,105 @@
+//! Masked elements the insertion stage found no anchor for
+
+use std::fmt;
+
+use crate::core::XYCutPlusPlus;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+/// Why a masked element had no valid anchor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnanchoredReason {
+    /// Nothing was ordered before it (a page of masked elements only)
+    NoCandidates,
+    /// Every ordered element has a lower `label_priorities` rank, which Equation 7
+    /// rules out as an anchor (unless `relax_priorities` is set)
+    Outranked,
+    /// Every distance was NaN or infinite (non-finite coordinates or weights)
+    NonFiniteDistance,
+}
+
+impl fmt::Display for UnanchoredReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoCandidates => write!(f, "no element ordered before it"),
+            Self::Outranked => write!(f, "every ordered element has a lower label rank"),
+            Self::NonFiniteDistance => write!(f, "no finite distance to any element"),
+        }
+    }
+}
+
+/// A masked element placed without a valid anchor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Unanchored {
+    pub id: usize,
+    pub reason: UnanchoredReason,
+}
+
+/// Where masked elements without a valid anchor are placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnanchoredPolicy {
+    /// At the end of the order
+    #[default]
+    Append,
+    /// Before the first ordered element following it in position (top to bottom,
+    /// then left to right), ignoring label priorities
+    Geometric,
+    /// Appended, but [`XYCutPlusPlus::try_compute_order`] fails instead of
+    /// returning the order
+    Error,
+}
+
+/// Masked elements had no valid anchor under [`UnanchoredPolicy::Error`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnanchoredError {
+    /// The elements without an anchor, in insertion order
+    pub unanchored: Vec<Unanchored>,
+
+    /// The order with those elements appended, for callers accepting it anyway
+    pub order: Vec<usize>,
+}
+
+impl fmt::Display for UnanchoredError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [first, rest @ ..] = self.unanchored.as_slice() else {
+            return write!(f, "no masked element was left without an anchor");
+        };
+        write!(
+            f,
+            "element {} has no valid anchor: {}",
+            first.id, first.reason
+        )?;
+        if !rest.is_empty() {
+            write!(f, " (and {} more)", rest.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnanchoredError {}
+
+impl XYCutPlusPlus {
+    /// [`compute_order`](Self::compute_order), failing when a masked element had no
+    /// valid anchor and `unanchored` is [`UnanchoredPolicy::Error`]
+    pub fn try_compute_order<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Result<Vec<usize>, UnanchoredError> {
+        let mut trace = Trace::default();
+        let order = self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace);
+        if self.config.unanchored == UnanchoredPolicy::Error && !trace.unanchored.is_empty() {
+            return Err(UnanchoredError {
+                unanchored: trace.unanchored,
+                order,
+            });
+        }
+        Ok(order)
+    }
+}
@@ -20,12 +20,60 @@ pub fn count_overlap<T: BoundingBox>(element: &T, all_elements: &[T]) -> usize {
         .count()
 }
 
+/// How ϕ3 (vertical continuity) measures a candidate below the masked element
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Continuity {
+    /// Offset between the tops
+    #[default]
+    TopAlign,
+    /// Offset between the bottoms, where the last text lines sit
+    BaselineAlign,
+    /// Offset between the centers, for boxes of very different heights
+    CenterAlign,
+    /// Whitespace between the masked bottom and the candidate top, so only the
+    /// distance down the page counts, not the masked element's own height
+    StrictBelow,
+}
+
+/// Tunable parts of the insertion distance of masked elements (Equation 8)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DistanceWeights {
+    /// ϕ3 measure for candidates starting below the masked element
+    pub continuity: Continuity,
+
+    /// ϕ3 factor on the overlap with candidates that start above the masked
+    /// element's bottom, discouraging insertion before content read earlier
+    pub continuity_penalty: f32,
+}
+
+impl Default for DistanceWeights {
+    fn default() -> Self {
+        Self {
+            continuity: Continuity::TopAlign,
+            continuity_penalty: 10.0,
+        }
+    }
+}
+
 /// Optimized distance calculation with early termination (Algorithm 1)
 /// Returns early if partial distance exceeds current_best
 pub fn compute_distance_with_early_exit<T: BoundingBox>(
     masked: &T,
     regular: &T,
     current_best: f32,
+) -> f32 {
+    compute_weighted_distance(masked, regular, current_best, &DistanceWeights::default())
+}
+
+/// [`compute_distance_with_early_exit`] with the ϕ3 variant and penalty of `weights`
+pub fn compute_weighted_distance<T: BoundingBox>(
+    masked: &T,
+    regular: &T,
+    current_best: f32,
+    weights: &DistanceWeights,
 ) -> f32 {
     let (mx1, my1, mx2, my2) = masked.bounds();
     let (rx1, ry1, rx2, ry2) = regular.bounds();
@@ -125,9 +173,14 @@ pub fn compute_distance_with_early_exit<T: BoundingBox>(
     } else {
         // Single column: Prefer elements below (reading flow)
         if ry1 >= my2 {
-            ry1 - my1 // Regular below - baseline alignment (top-to-top)
+            match weights.continuity {
+                Continuity::TopAlign => ry1 - my1,
+                Continuity::BaselineAlign => ry2 - my2,
+                Continuity::CenterAlign => (ry1 + ry2 - my1 - my2) / 2.0,
+                Continuity::StrictBelow => ry1 - my2,
+            }
         } else {
-            (my2 - ry1) * 10.0 // Regular above - scaled penalty
+            (my2 - ry1) * weights.continuity_penalty // Regular above - scaled penalty
         }
     };
 
@@ -202,7 +255,7 @@ pub fn distance_to_nearest_text<T: BoundingBox>(element: &T, all_elements: &[T])
             0.0 // Boxes overlap vertically
         };
 
-        let euclidean_distance = (dx.powf(2.0) + dy.powf(2.0)).sqrt();
+        let euclidean_distance = (dx * dx + dy * dy).sqrt();
 
         if euclidean_distance < min_distance {
             min_distance = euclidean_distance
@@ -211,3 +264,70 @@ pub fn distance_to_nearest_text<T: BoundingBox>(element: &T, all_elements: &[T])
 
     min_distance
 }
+
+/// Calculate median height of elements
+pub fn compute_median_height<T: BoundingBox>(elements: &[T]) -> f32 {
+    if elements.is_empty() {
+        return 0.0;
+    }
+
+    let mut heights: Vec<f32> = elements
+        .iter()
+        .map(|e| {
+            let (_, y1, _, y2) = e.bounds();
+            y2 - y1
+        })
+        .collect();
+
+    heights.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let len = heights.len();
+    if len % 2 == 1 {
+        heights[len / 2]
+    } else {
+        (heights[len / 2 - 1] + heights[len / 2]) / 2.0
+    }
+}
+
+/// Total order on element positions: center y, then center x, then width, then id
+///
+/// Uses `f32::total_cmp`, so the result never depends on input order, even for
+/// identical boxes or NaN coordinates.
+pub fn compare_position<T: BoundingBox>(a: &T, b: &T) -> std::cmp::Ordering {
+    let (ax, ay) = a.center();
+    let (bx, by) = b.center();
+    let width = |e: &T| {
+        let (x1, _, x2, _) = e.bounds();
+        x2 - x1
+    };
+    ay.total_cmp(&by)
+        .then(ax.total_cmp(&bx))
+        .then(width(a).total_cmp(&width(b)))
+        .then(a.id().cmp(&b.id()))
+}
+
+/// Sort elements into rows, top to bottom, and each row left to right
+///
+/// Elements are first sorted by [`compare_position`]; a new row starts whenever an
+/// element's center lies more than `same_row_tolerance` below the first element of
+/// the current row. The result is deterministic for any input permutation.
+pub fn sort_by_rows<T: BoundingBox>(elements: &mut [T], same_row_tolerance: f32) {
+    elements.sort_by(compare_position);
+
+    let mut start = 0;
+    while start < elements.len() {
+        let row_y = elements[start].center().1;
+        // A row holds at least its first element, even at a tolerance of 0
+        let end = elements[start + 1..]
+            .iter()
+            .position(|e| e.center().1 - row_y >= same_row_tolerance)
+            .map_or(elements.len(), |offset| start + 1 + offset);
+        elements[start..end].sort_by(|a, b| {
+            a.center()
+                .0
+                .total_cmp(&b.center().0)
+                .then(compare_position(a, b))
+        });
+        start = end;
+    }
+}
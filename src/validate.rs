@@ -0,0 +1,58 @@
+//! Check that an order is a permutation of the input elements
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::traits::BoundingBox;
+
+/// Why an order is not a permutation of the input ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderValidationError {
+    /// An input id does not appear in the order
+    Missing(usize),
+    /// An id appears more often than in the input
+    Duplicate(usize),
+    /// An id appears that no input element has
+    Unknown(usize),
+}
+
+impl fmt::Display for OrderValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(id) => write!(f, "element {id} is missing from the order"),
+            Self::Duplicate(id) => write!(f, "element {id} appears more than once in the order"),
+            Self::Unknown(id) => write!(f, "order contains unknown element {id}"),
+        }
+    }
+}
+
+impl std::error::Error for OrderValidationError {}
+
+/// Check that `order` holds every id of `elements` exactly once and nothing else
+///
+/// Ids shared by several elements must appear once per element. Reports the first
+/// unknown or repeated id in order position, then the first missing id in input order.
+/// Note that `compute_order` drops noise boxes (and background boxes unless
+/// `keep_background` is set), so validate against the elements that were kept.
+pub fn validate_order<T: BoundingBox>(
+    elements: &[T],
+    order: &[usize],
+) -> Result<(), OrderValidationError> {
+    let mut remaining: HashMap<usize, usize> = HashMap::new();
+    for element in elements {
+        *remaining.entry(element.id()).or_default() += 1;
+    }
+
+    for &id in order {
+        match remaining.get_mut(&id) {
+            None => return Err(OrderValidationError::Unknown(id)),
+            Some(0) => return Err(OrderValidationError::Duplicate(id)),
+            Some(count) => *count -= 1,
+        }
+    }
+
+    match elements.iter().find(|e| remaining[&e.id()] > 0) {
+        Some(element) => Err(OrderValidationError::Missing(element.id())),
+        None => Ok(()),
+    }
+}
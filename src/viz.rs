@@ -0,0 +1,391 @@
+//! SVG plots for tuning: the projection histograms behind each cut, and diffs
+//! between two orderings
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::core::XYCutPlusPlus;
+use crate::observer::{Axis, Cut};
+use crate::trace::{Decision, RegionState, Trace};
+use crate::traits::BoundingBox;
+
+/// Height of the histogram strips drawn along the region edges, in page units
+const STRIP: f32 = 80.0;
+
+/// Space between the region and its strips
+const MARGIN: f32 = 8.0;
+
+impl XYCutPlusPlus {
+    /// Plot the region's boxes with the projection histograms the first cut is chosen from
+    ///
+    /// The x histogram (columns) runs below the region and the y histogram (rows) to its
+    /// right. Bins are shaded by occupancy, the gap the cut was taken in is highlighted and
+    /// the cut itself is drawn as a dashed line. Any sub-region can be plotted by passing
+    /// its elements and bounds.
+    pub fn histogram_svg<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> String {
+        self.region_svgs(elements, x_min, y_min, x_max, y_max)
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| {
+                let region = self.region_state(elements, x_min, y_min, x_max, y_max);
+                self.region_svg(&region)
+            })
+    }
+
+    /// Plot every region visited while ordering the page, in visiting order
+    ///
+    /// Coordinates are in the frame the page was cut in, which differs from the input
+    /// frame for rotated or deskewed pages.
+    pub fn region_svgs<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Vec<String> {
+        let mut trace = Trace {
+            record_state: true,
+            ..Trace::default()
+        };
+        self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace);
+        trace
+            .regions
+            .iter()
+            .map(|region| self.region_svg(region))
+            .collect()
+    }
+
+    fn region_svg(&self, region: &RegionState) -> String {
+        let (x_min, y_min, x_max, y_max) = region.bounds;
+        let (width, height) = (x_max - x_min, y_max - y_min);
+        let (columns, rows) = &region.histograms;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" font-family=\"sans-serif\" font-size=\"10\">\n",
+            width + MARGIN + STRIP,
+            height + MARGIN + STRIP
+        );
+        svg.push_str(&format!(
+            "<rect width=\"{width}\" height=\"{height}\" fill=\"none\" stroke=\"#999\"/>\n"
+        ));
+
+        for (&id, &(x1, y1, x2, y2)) in region.ids.iter().zip(&region.boxes) {
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#4a90d9\" fill-opacity=\"0.15\" stroke=\"#4a90d9\"/>\
+                 <text x=\"{}\" y=\"{}\">{id}</text>",
+                x1 - x_min,
+                y1 - y_min,
+                x2 - x1,
+                y2 - y1,
+                x1 - x_min + 2.0,
+                y1 - y_min + 10.0
+            );
+        }
+
+        let cut = match region.decision {
+            Some(Decision::Cut(Cut::Projection {
+                axis,
+                position,
+                tolerant,
+            })) => Some((axis, position, tolerant)),
+            _ => None,
+        };
+        let max_occupancy = |tolerant: bool| {
+            if tolerant {
+                (self.config.cut_overlap_tolerance * region.ids.len() as f32) as usize
+            } else {
+                0
+            }
+        };
+
+        // Columns: bins along x, bars growing down from the strip's top edge
+        let column_gap =
+            cut.filter(|(axis, ..)| *axis == Axis::X)
+                .map(|(_, position, tolerant)| {
+                    gap_run(columns, (position - x_min) / width, max_occupancy(tolerant))
+                });
+        strip(
+            &mut svg,
+            columns,
+            width,
+            column_gap,
+            |offset, extent, bar| (offset, height + MARGIN, extent, bar),
+        );
+
+        // Rows: bins along y, bars growing right from the strip's left edge
+        let row_gap = cut
+            .filter(|(axis, ..)| *axis == Axis::Y)
+            .map(|(_, position, tolerant)| {
+                gap_run(rows, (position - y_min) / height, max_occupancy(tolerant))
+            });
+        strip(&mut svg, rows, height, row_gap, |offset, extent, bar| {
+            (width + MARGIN, offset, bar, extent)
+        });
+
+        match cut {
+            Some((Axis::X, position, _)) => {
+                let x = position - x_min;
+                let _ = writeln!(
+                    svg,
+                    "<line x1=\"{x}\" y1=\"0\" x2=\"{x}\" y2=\"{}\" stroke=\"#d0021b\" stroke-dasharray=\"4 2\"/>",
+                    height + MARGIN + STRIP
+                );
+            }
+            Some((Axis::Y, position, _)) => {
+                let y = position - y_min;
+                let _ = writeln!(
+                    svg,
+                    "<line x1=\"0\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\" stroke=\"#d0021b\" stroke-dasharray=\"4 2\"/>",
+                    width + MARGIN + STRIP
+                );
+            }
+            None => {}
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Bins `(start, end)` of the run around `fraction` whose occupancy stays within `max_occupancy`
+fn gap_run(histogram: &[usize], fraction: f32, max_occupancy: usize) -> (usize, usize) {
+    if histogram.is_empty() {
+        return (0, 0);
+    }
+    let center = ((fraction * histogram.len() as f32) as usize).min(histogram.len() - 1);
+    let mut start = center;
+    while start > 0 && histogram[start - 1] <= max_occupancy {
+        start -= 1;
+    }
+    let mut end = center + 1;
+    while end < histogram.len() && histogram[end] <= max_occupancy {
+        end += 1;
+    }
+    (start, end)
+}
+
+/// Draw one histogram strip, merging runs of equal bins into one bar
+///
+/// `place(offset, extent, bar)` maps a run starting `offset` along the region with
+/// length `extent` and a bar `bar` units long to an `(x, y, width, height)` rectangle.
+fn strip(
+    svg: &mut String,
+    histogram: &[usize],
+    length: f32,
+    gap: Option<(usize, usize)>,
+    place: impl Fn(f32, f32, f32) -> (f32, f32, f32, f32),
+) {
+    if histogram.is_empty() {
+        return;
+    }
+    let bin = length / histogram.len() as f32;
+    let peak = histogram.iter().copied().max().unwrap_or(0).max(1) as f32;
+
+    if let Some((start, end)) = gap {
+        let (x, y, w, h) = place(start as f32 * bin, (end - start) as f32 * bin, STRIP);
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"#f5a623\" fill-opacity=\"0.35\"/>"
+        );
+    }
+
+    let mut start = 0;
+    for end in 1..=histogram.len() {
+        if end < histogram.len() && histogram[end] == histogram[start] {
+            continue;
+        }
+        let count = histogram[start];
+        if count > 0 {
+            let heat = count as f32 / peak;
+            let (x, y, w, h) = place(start as f32 * bin, (end - start) as f32 * bin, heat * STRIP);
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" fill=\"#d0021b\" fill-opacity=\"{:.2}\"><title>{count}</title></rect>",
+                0.2 + 0.8 * heat
+            );
+        }
+        start = end;
+    }
+}
+
+/// Overlay two orderings of the same elements, e.g. before and after a config
+/// change or predicted vs ground truth
+///
+/// Reading-order links of `after` are drawn as arrows between box centers: grey when
+/// `before` has the same link, red when it is new. Links only found in `before` are
+/// dashed blue. Boxes are shaded by how far their rank moved and labelled
+/// `before→after`; boxes missing from one ordering get a dashed outline.
+pub fn order_diff_svg<T: BoundingBox>(
+    elements: &[T],
+    before: &[usize],
+    after: &[usize],
+    x_min: f32,
+    y_min: f32,
+    x_max: f32,
+    y_max: f32,
+) -> String {
+    let rank = |order: &[usize]| -> HashMap<usize, usize> {
+        order.iter().enumerate().map(|(i, &id)| (id, i)).collect()
+    };
+    let (before_rank, after_rank) = (rank(before), rank(after));
+    let links = |order: &[usize]| -> HashSet<(usize, usize)> {
+        order.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    };
+    let (before_links, after_links) = (links(before), links(after));
+    let centers: HashMap<usize, (f32, f32)> = elements
+        .iter()
+        .map(|e| {
+            let (x, y) = e.center();
+            (e.id(), (x - x_min, y - y_min))
+        })
+        .collect();
+    let max_shift = elements.len().max(1) as f32;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" font-family=\"sans-serif\" font-size=\"10\">\n\
+         <defs>{}{}{}</defs>\n",
+        x_max - x_min,
+        y_max - y_min,
+        arrowhead("xycut-same", "#888"),
+        arrowhead("xycut-new", "#d0021b"),
+        arrowhead("xycut-old", "#4a90d9")
+    );
+
+    for e in elements {
+        let (x1, y1, x2, y2) = e.bounds();
+        let ranks = (before_rank.get(&e.id()), after_rank.get(&e.id()));
+        let (shift, dash) = match ranks {
+            (Some(&b), Some(&a)) => (b.abs_diff(a) as f32 / max_shift, ""),
+            _ => (0.0, " stroke-dasharray=\"4 2\""),
+        };
+        let rank_text = |r: Option<&usize>| r.map_or("-".to_string(), |r| r.to_string());
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#f5a623\" fill-opacity=\"{:.2}\" stroke=\"#555\"{dash}/>\
+             <text x=\"{}\" y=\"{}\">{}: {}→{}</text>",
+            x1 - x_min,
+            y1 - y_min,
+            x2 - x1,
+            y2 - y1,
+            0.05 + 0.75 * shift.sqrt(),
+            x1 - x_min + 2.0,
+            y1 - y_min + 10.0,
+            e.id(),
+            rank_text(ranks.0),
+            rank_text(ranks.1)
+        );
+    }
+
+    let mut arrow = |(from, to): (usize, usize), marker: &str, color: &str, dash: &str| {
+        if let (Some(&(x1, y1)), Some(&(x2, y2))) = (centers.get(&from), centers.get(&to)) {
+            let _ = writeln!(
+                svg,
+                "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\" stroke=\"{color}\" stroke-width=\"1.5\"{dash} marker-end=\"url(#{marker})\"/>"
+            );
+        }
+    };
+    for pair in before.windows(2).map(|pair| (pair[0], pair[1])) {
+        if !after_links.contains(&pair) {
+            arrow(pair, "xycut-old", "#4a90d9", " stroke-dasharray=\"4 2\"");
+        }
+    }
+    for pair in after.windows(2).map(|pair| (pair[0], pair[1])) {
+        if before_links.contains(&pair) {
+            arrow(pair, "xycut-same", "#888", "");
+        } else {
+            arrow(pair, "xycut-new", "#d0021b", "");
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn arrowhead(id: &str, color: &str) -> String {
+    format!(
+        "<marker id=\"{id}\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+         <path d=\"M0,0 L10,5 L0,10 z\" fill=\"{color}\"/></marker>"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::XYCutConfig;
+    use crate::element::Element;
+    use crate::traits::SemanticLabel;
+
+    /// Two columns, one paragraph each
+    fn page() -> Vec<Element> {
+        vec![
+            Element::new(0, 50.0, 50.0, 450.0, 950.0, SemanticLabel::Regular),
+            Element::new(1, 550.0, 50.0, 950.0, 950.0, SemanticLabel::Regular),
+        ]
+    }
+
+    #[test]
+    fn histogram_plot_marks_the_column_cut() {
+        let engine = XYCutPlusPlus::new(XYCutConfig::default());
+        // Bounds hugging the boxes, so the first cut is the gutter rather than a margin
+        let svg = engine.histogram_svg(&page(), 50.0, 50.0, 950.0, 950.0);
+
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">0</text>") && svg.contains(">1</text>"));
+        // Highlighted gap and the dashed cut line inside the gutter
+        assert!(svg.contains("fill=\"#f5a623\""));
+        let cut = svg
+            .lines()
+            .find(|line| line.starts_with("<line") && line.contains("stroke-dasharray"))
+            .expect("cut line");
+        let x: f32 = cut
+            .split("x1=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .and_then(|x| x.parse().ok())
+            .expect("cut position");
+        assert!((400.0..=500.0).contains(&x), "cut at {x}");
+
+        assert!(!engine
+            .region_svgs(&page(), 0.0, 0.0, 1000.0, 1000.0)
+            .is_empty());
+    }
+
+    #[test]
+    fn diff_draws_an_arrow_per_reading_order_link() {
+        let page: Vec<Element> = (0..4)
+            .map(|i| {
+                let y = 50.0 + i as f32 * 200.0;
+                Element::new(i, 50.0, y, 950.0, y + 150.0, SemanticLabel::Regular)
+            })
+            .collect();
+        let svg = order_diff_svg(
+            &page,
+            &[0, 1, 2, 3],
+            &[0, 1, 3, 2],
+            0.0,
+            0.0,
+            1000.0,
+            1000.0,
+        );
+
+        let arrows = |marker: &str| {
+            svg.matches(&format!("marker-end=\"url(#{marker})\""))
+                .count()
+        };
+        // 0→1 kept, 1→3 and 3→2 new, 1→2 and 2→3 only in `before`
+        assert_eq!(arrows("xycut-same"), 1);
+        assert_eq!(arrows("xycut-new"), 2);
+        assert_eq!(arrows("xycut-old"), 2);
+        // Arrows run between box centers
+        assert!(svg.contains("<line x1=\"500\" y1=\"125\" x2=\"500\" y2=\"325\" stroke=\"#888\""));
+        assert!(svg.contains(">2: 2→3</text>") && svg.contains(">3: 3→2</text>"));
+    }
+}
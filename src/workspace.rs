@@ -0,0 +1,53 @@
+//! Buffers reused across pages, for batch jobs ordering millions of pages
+
+use crate::core::XYCutPlusPlus;
+use crate::trace::Trace;
+use crate::traits::BoundingBox;
+
+/// Scratch space for [`XYCutPlusPlus::compute_order_with`]
+///
+/// Holds the projection histogram, split plan and result buffers. They grow to the
+/// largest page seen and are then reused, so a long-running worker stops
+/// allocating them per page. A workspace is not shared between threads; give
+/// each worker its own.
+#[derive(Debug, Default)]
+pub struct Workspace {
+    /// Projection histogram of the region being cut
+    pub(crate) histogram: Vec<usize>,
+
+    /// Target position of each element of the region being split
+    pub(crate) split: Vec<usize>,
+
+    /// Reading order of the last page
+    pub(crate) order: Vec<usize>,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl XYCutPlusPlus {
+    /// [`compute_order`](Self::compute_order) using the buffers of `workspace`
+    ///
+    /// The order is returned as a view into the workspace, valid until its next use.
+    pub fn compute_order_with<'w, T: BoundingBox>(
+        &self,
+        workspace: &'w mut Workspace,
+        elements: &[T],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> &'w [usize] {
+        let mut trace = Trace {
+            workspace: std::mem::take(workspace),
+            ..Trace::default()
+        };
+        let order = self.compute_order_traced(elements, x_min, y_min, x_max, y_max, &mut trace);
+        *workspace = trace.workspace;
+        workspace.order = order;
+        &workspace.order
+    }
+}
@@ -0,0 +1,103 @@
+//! Page zones ordered with their own configuration
+//!
+//! Headers, body text and footers often need different thresholds. A zone is an
+//! area of the page with the config its elements are cut and inserted with.
+
+use crate::core::{XYCutConfig, XYCutPlusPlus};
+use crate::traits::BoundingBox;
+
+/// An area of the page ordered with its own configuration
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Zone {
+    /// Area `(x1, y1, x2, y2)`; an element belongs to the first zone holding its center
+    pub area: (f32, f32, f32, f32),
+
+    /// Config for the zone's elements, usually the page config with a few fields
+    /// changed (`XYCutConfig { min_cut_threshold: 5.0, ..page.clone() }`)
+    pub config: XYCutConfig,
+}
+
+impl Zone {
+    /// A horizontal band of the page from `y1` to `y2`
+    pub fn band(y1: f32, y2: f32, config: XYCutConfig) -> Self {
+        Self {
+            area: (f32::NEG_INFINITY, y1, f32::INFINITY, y2),
+            config,
+        }
+    }
+
+    fn contains(&self, (x, y): (f32, f32)) -> bool {
+        let (x1, y1, x2, y2) = self.area;
+        x >= x1 && x < x2 && y >= y1 && y < y2
+    }
+}
+
+impl XYCutPlusPlus {
+    /// Order a page divided into zones
+    ///
+    /// Each zone's elements are ordered on their own with the zone's config, within
+    /// the zone's area clipped to the page; elements outside every zone are ordered
+    /// together with this detector's config. The groups are then read from the top
+    /// down by their topmost element, left to right on ties.
+    pub fn compute_order_zoned<T: BoundingBox>(
+        &self,
+        elements: &[T],
+        zones: &[Zone],
+        x_min: f32,
+        y_min: f32,
+        x_max: f32,
+        y_max: f32,
+    ) -> Vec<usize> {
+        // One group per zone, then the elements outside every zone
+        let mut groups: Vec<Vec<T>> = vec![Vec::new(); zones.len() + 1];
+        for e in elements {
+            let zone = zones
+                .iter()
+                .position(|zone| zone.contains(e.center()))
+                .unwrap_or(zones.len());
+            groups[zone].push(e.clone());
+        }
+
+        let mut ordered: Vec<((f32, f32), Vec<usize>)> = Vec::new();
+        for (index, group) in groups.iter().enumerate() {
+            if group.is_empty() {
+                continue;
+            }
+            let (top, left) =
+                group
+                    .iter()
+                    .fold((f32::INFINITY, f32::INFINITY), |(top, left), e| {
+                        let (x1, y1, _, _) = e.bounds();
+                        (top.min(y1), left.min(x1))
+                    });
+            let order = match zones.get(index) {
+                Some(zone) => {
+                    let (x1, y1, x2, y2) = zone_bounds(zone, group, (x_min, y_min, x_max, y_max));
+                    XYCutPlusPlus::new(zone.config.clone()).compute_order(group, x1, y1, x2, y2)
+                }
+                None => self.compute_order(group, x_min, y_min, x_max, y_max),
+            };
+            ordered.push(((top, left), order));
+        }
+        // Stable: equal positions keep zone order, the outside group last
+        ordered.sort_by(|a, b| a.0 .0.total_cmp(&b.0 .0).then(a.0 .1.total_cmp(&b.0 .1)));
+        ordered.into_iter().flat_map(|(_, order)| order).collect()
+    }
+}
+
+/// The zone's area clipped to the page, grown to hold every element of the zone
+fn zone_bounds<T: BoundingBox>(
+    zone: &Zone,
+    group: &[T],
+    (x_min, y_min, x_max, y_max): (f32, f32, f32, f32),
+) -> (f32, f32, f32, f32) {
+    let (x1, y1, x2, y2) = zone.area;
+    group.iter().fold(
+        (x1.max(x_min), y1.max(y_min), x2.min(x_max), y2.min(y_max)),
+        |(x1, y1, x2, y2), e| {
+            let (ex1, ey1, ex2, ey2) = e.bounds();
+            (x1.min(ex1), y1.min(ey1), x2.max(ex2), y2.max(ey2))
+        },
+    )
+}
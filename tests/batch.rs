@@ -0,0 +1,64 @@
+//! `compute_orders` orders each page of a batch as `compute_order` would
+
+use xycut_plus_plus::{Element, PageInput, Workspace, XYCutConfig, XYCutPlusPlus};
+
+mod common;
+
+fn fixture(name: &str) -> ((f32, f32, f32, f32), Vec<Element>) {
+    let path = format!("{}/tests/fixtures/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    common::load_page(&common::read_json(&path))
+}
+
+#[test]
+fn batch_orders_match_single_page_orders() {
+    let pages: Vec<_> = [
+        "two_column_paper",
+        "newspaper",
+        "magazine",
+        "form",
+        "negative_origin",
+    ]
+    .into_iter()
+    .map(fixture)
+    .collect();
+    let batch: Vec<PageInput<Element>> = pages
+        .iter()
+        .map(|(bounds, elements)| (elements.as_slice(), *bounds))
+        .collect();
+
+    let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+    let orders = xycut.compute_orders(&batch);
+
+    assert_eq!(orders.len(), pages.len());
+    for (order, ((x1, y1, x2, y2), elements)) in orders.iter().zip(&pages) {
+        assert_eq!(*order, xycut.compute_order(elements, *x1, *y1, *x2, *y2));
+    }
+}
+
+#[test]
+fn empty_batch_has_no_orders() {
+    let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+    assert!(xycut.compute_orders::<Element>(&[]).is_empty());
+}
+
+#[test]
+fn reused_workspace_orders_like_compute_order() {
+    let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+    let mut workspace = Workspace::new();
+    // Large pages before small ones, so stale buffer contents would show up
+    for name in [
+        "newspaper",
+        "magazine",
+        "form",
+        "two_column_paper",
+        "newspaper",
+    ] {
+        let ((x1, y1, x2, y2), elements) = fixture(name);
+        let order = xycut.compute_order_with(&mut workspace, &elements, x1, y1, x2, y2);
+        assert_eq!(
+            order,
+            xycut.compute_order(&elements, x1, y1, x2, y2).as_slice(),
+            "{name}"
+        );
+    }
+}
@@ -0,0 +1,45 @@
+//! Layout JSON shared by the fixture-based tests
+//!
+//! A page is `{"page": [x1, y1, x2, y2], "elements": [{"id", "bbox": [x1, y1, x2, y2], "label"}]}`
+//! with labels named after `SemanticLabel` variants.
+
+use serde_json::Value;
+use xycut_plus_plus::{Element, SemanticLabel};
+
+pub fn read_json(path: &str) -> Value {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+    serde_json::from_str(&text).unwrap_or_else(|e| panic!("{path}: {e}"))
+}
+
+/// Page bounds and elements of a layout document
+pub fn load_page(layout: &Value) -> ((f32, f32, f32, f32), Vec<Element>) {
+    let coords = |value: &Value| -> Vec<f32> {
+        value
+            .as_array()
+            .expect("coordinate array")
+            .iter()
+            .map(|v| v.as_f64().expect("number") as f32)
+            .collect()
+    };
+    let page = coords(&layout["page"]);
+    let elements = layout["elements"]
+        .as_array()
+        .expect("elements array")
+        .iter()
+        .map(|element| {
+            let b = coords(&element["bbox"]);
+            let label = match element["label"].as_str().expect("label") {
+                "CrossLayout" => SemanticLabel::CrossLayout,
+                "HorizontalTitle" => SemanticLabel::HorizontalTitle,
+                "VerticalTitle" => SemanticLabel::VerticalTitle,
+                "Vision" => SemanticLabel::Vision,
+                "Formula" => SemanticLabel::Formula,
+                "Regular" => SemanticLabel::Regular,
+                other => panic!("unknown label {other}"),
+            };
+            let id = element["id"].as_u64().expect("id") as usize;
+            Element::new(id, b[0], b[1], b[2], b[3], label)
+        })
+        .collect();
+    ((page[0], page[1], page[2], page[3]), elements)
+}
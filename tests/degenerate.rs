@@ -0,0 +1,170 @@
+//! Degenerate configurations and boxes that must still produce a full order
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use xycut_plus_plus::preprocess::NoiseFilter;
+use xycut_plus_plus::repair::repair_inversions;
+use xycut_plus_plus::{
+    validate_order, Element, FallbackSort, SemanticLabel, XYCutConfig, XYCutPlusPlus,
+};
+
+/// Order `elements` on a 1000×1000 page, failing instead of hanging
+fn order_within_timeout(elements: Vec<Element>, config: XYCutConfig) -> Vec<usize> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let order = XYCutPlusPlus::new(config).compute_order(&elements, 0.0, 0.0, 1000.0, 1000.0);
+        sender.send(order).unwrap();
+    });
+    receiver
+        .recv_timeout(Duration::from_secs(10))
+        .expect("compute_order did not finish")
+}
+
+#[test]
+fn zero_row_tolerance_terminates() {
+    // Overlapping boxes leave no cut, so the fallback sort orders them
+    let elements = vec![
+        Element::new(0, 100.0, 100.0, 300.0, 200.0, SemanticLabel::Regular),
+        Element::new(1, 200.0, 150.0, 400.0, 250.0, SemanticLabel::Regular),
+    ];
+    // The plain position sort and the configurable fallback sort
+    let tops = FallbackSort {
+        use_tops: true,
+        ..FallbackSort::default()
+    };
+    for fallback in [FallbackSort::default(), tops] {
+        let config = XYCutConfig {
+            same_row_tolerance: 0.0,
+            fallback,
+            ..XYCutConfig::default()
+        };
+        let order = order_within_timeout(elements.clone(), config);
+        assert_eq!(validate_order(&elements, &order), Ok(()));
+    }
+}
+
+#[test]
+fn zero_height_boxes_share_a_row_under_adaptive_tolerance() {
+    // Baselines without height, as hOCR or PDF text spans sometimes come
+    let elements = vec![
+        Element::new(0, 150.0, 29.0, 250.0, 29.0, SemanticLabel::Regular),
+        Element::new(1, 100.0, 31.0, 200.0, 31.0, SemanticLabel::Regular),
+        Element::new(2, 200.0, 30.0, 300.0, 30.0, SemanticLabel::Regular),
+    ];
+    let config = XYCutConfig {
+        adaptive_row_tolerance: Some(0.5),
+        ..XYCutConfig::default()
+    };
+    assert_eq!(order_within_timeout(elements, config), vec![1, 0, 2]);
+}
+
+#[test]
+fn fixed_point_orders_a_page_narrower_than_one_unit() {
+    let elements = vec![
+        Element::new(0, 0.0, 100.0, 0.3, 200.0, SemanticLabel::Regular),
+        Element::new(1, 0.0, 300.0, 0.3, 400.0, SemanticLabel::Regular),
+    ];
+    let config = XYCutConfig {
+        fixed_point: Some(1),
+        ..XYCutConfig::default()
+    };
+    let order = XYCutPlusPlus::new(config).compute_order(&elements, 0.0, 0.0, 0.4, 1000.0);
+    assert_eq!(order, vec![0, 1]);
+}
+
+#[test]
+fn default_noise_filter_keeps_inverted_boxes() {
+    let elements = vec![
+        Element::new(0, 100.0, 100.0, 400.0, 150.0, SemanticLabel::Regular),
+        // Corners swapped by a detector
+        Element::new(1, 400.0, 250.0, 100.0, 200.0, SemanticLabel::Regular),
+        Element::new(2, 100.0, 300.0, 400.0, 350.0, SemanticLabel::Regular),
+    ];
+    let report = XYCutPlusPlus::new(XYCutConfig::default())
+        .compute_order_with_report(&elements, 0.0, 0.0, 1000.0, 1000.0);
+    assert_eq!(validate_order(&elements, &report.order), Ok(()));
+    assert!(report.dropped.is_empty());
+}
+
+#[test]
+fn deskewed_page_keeps_inverted_boxes() {
+    // Rows drifting 10 px over 300 px, about 1.9° of skew
+    let elements = vec![
+        Element::new(0, 100.0, 100.0, 300.0, 130.0, SemanticLabel::Regular),
+        Element::new(1, 400.0, 110.0, 600.0, 140.0, SemanticLabel::Regular),
+        Element::new(2, 100.0, 200.0, 300.0, 230.0, SemanticLabel::Regular),
+        Element::new(3, 400.0, 210.0, 600.0, 240.0, SemanticLabel::Regular),
+        Element::new(4, 600.0, 400.0, 100.0, 300.0, SemanticLabel::Regular),
+    ];
+    let config = XYCutConfig {
+        deskew: true,
+        ..XYCutConfig::default()
+    };
+    let order = order_within_timeout(elements.clone(), config);
+    assert_eq!(validate_order(&elements, &order), Ok(()));
+}
+
+#[test]
+fn dropped_speckles_are_reported() {
+    let elements = vec![
+        Element::new(0, 100.0, 100.0, 400.0, 150.0, SemanticLabel::Regular),
+        Element::new(1, 500.0, 500.0, 502.0, 502.0, SemanticLabel::Regular),
+        Element::new(2, 100.0, 300.0, 400.0, 350.0, SemanticLabel::Regular),
+    ];
+    let config = XYCutConfig {
+        noise: NoiseFilter {
+            min_area: 10.0,
+            ..NoiseFilter::default()
+        },
+        ..XYCutConfig::default()
+    };
+    let report =
+        XYCutPlusPlus::new(config).compute_order_with_report(&elements, 0.0, 0.0, 1000.0, 1000.0);
+    assert_eq!(report.order, vec![0, 2]);
+    assert_eq!(report.dropped, vec![1]);
+}
+
+#[test]
+fn non_finite_boxes_are_dropped_not_placed_as_background() {
+    let elements = vec![
+        Element::new(0, 100.0, 100.0, 400.0, 150.0, SemanticLabel::Regular),
+        // max() skips NaN, so this box would seem to cover the whole page
+        Element::new(1, f32::NAN, -1e30, 1000.0, 1000.0, SemanticLabel::Regular),
+        Element::new(2, 100.0, 300.0, 400.0, 350.0, SemanticLabel::Regular),
+    ];
+    let report = XYCutPlusPlus::new(XYCutConfig::default())
+        .compute_order_with_report(&elements, 0.0, 0.0, 1000.0, 1000.0);
+    assert_eq!(report.order, vec![0, 2]);
+    assert_eq!(report.dropped, vec![1]);
+}
+
+#[test]
+fn pre_binning_skips_boxes_spanning_the_coordinate_range() {
+    let elements = vec![
+        Element::new(0, 100.0, 100.0, 400.0, 150.0, SemanticLabel::Regular),
+        Element::new(1, 100.0, 160.0, 400.0, 210.0, SemanticLabel::Regular),
+        // Its grid cells run from i64::MIN to i64::MAX
+        Element::new(2, 500.0, -1e30, 520.0, 1e30, SemanticLabel::Regular),
+    ];
+    let config = XYCutConfig {
+        pre_binning: Some(1),
+        ..XYCutConfig::default()
+    };
+    let order = order_within_timeout(elements.clone(), config);
+    assert_eq!(validate_order(&elements, &order), Ok(()));
+}
+
+#[test]
+fn zero_height_boxes_on_one_line_are_not_inverted() {
+    // Overlapping baselines at the same height: neither lies above the other
+    let elements = vec![
+        Element::new(0, 100.0, 300.0, 250.0, 300.0, SemanticLabel::Regular),
+        Element::new(1, 150.0, 300.0, 300.0, 300.0, SemanticLabel::Regular),
+        Element::new(2, 120.0, 100.0, 280.0, 100.0, SemanticLabel::Regular),
+    ];
+    assert_eq!(repair_inversions(&elements, &[0, 1, 2]), (vec![2, 0, 1], 2));
+    assert_eq!(repair_inversions(&elements, &[2, 0, 1]), (vec![2, 0, 1], 0));
+    assert_eq!(repair_inversions(&elements, &[2, 1, 0]), (vec![2, 1, 0], 0));
+}
@@ -0,0 +1,62 @@
+//! `dump_state` documents replay the run they were dumped from
+#![cfg(feature = "dump")]
+
+use serde_json::Value;
+use xycut_plus_plus::{Element, XYCutConfig, XYCutPlusPlus};
+
+mod common;
+
+#[test]
+fn dump_replays_its_run() {
+    let path = format!(
+        "{}/tests/fixtures/newspaper.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let (page, elements) = common::load_page(&common::read_json(&path));
+    let config = XYCutConfig {
+        min_cut_threshold: 12.5,
+        deskew: true,
+        group_figures: true,
+        ..XYCutConfig::default()
+    };
+    let xycut = XYCutPlusPlus::new(config);
+    let dump = xycut.dump_state(&elements, page.0, page.1, page.2, page.3);
+
+    // Rebuild the run from the dump alone
+    let state: Value = serde_json::from_str(&dump).unwrap();
+    let config: XYCutConfig = serde_json::from_value(state["config"].clone()).unwrap();
+    let elements: Vec<Element> = serde_json::from_value(state["elements"].clone()).unwrap();
+    let page: [f32; 4] = serde_json::from_value(state["page"].clone()).unwrap();
+    let order: Vec<usize> = serde_json::from_value(state["order"].clone()).unwrap();
+
+    let replay = XYCutPlusPlus::new(config);
+    assert_eq!(replay.fingerprint(), xycut.fingerprint());
+    assert_eq!(
+        replay.compute_order(&elements, page[0], page[1], page[2], page[3]),
+        order
+    );
+    assert_eq!(
+        replay.dump_state(&elements, page[0], page[1], page[2], page[3]),
+        dump
+    );
+}
+
+#[test]
+fn regions_carry_their_boxes_and_labels() {
+    let path = format!(
+        "{}/tests/fixtures/two_column_paper.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let (page, elements) = common::load_page(&common::read_json(&path));
+    let dump = XYCutPlusPlus::new(XYCutConfig::default())
+        .dump_state(&elements, page.0, page.1, page.2, page.3);
+
+    let state: Value = serde_json::from_str(&dump).unwrap();
+    let regions = state["regions"].as_array().unwrap();
+    assert!(!regions.is_empty());
+    for region in regions {
+        let ids = region["ids"].as_array().unwrap().len();
+        assert_eq!(region["boxes"].as_array().unwrap().len(), ids);
+        assert_eq!(region["labels"].as_array().unwrap().len(), ids);
+    }
+}
@@ -0,0 +1,61 @@
+//! Golden orders for the fixture pages in `tests/fixtures`
+//!
+//! Any change to cuts, weights or insertion shows up as a snapshot diff; review
+//! it with `cargo insta review` and commit the accepted snapshots.
+
+use std::fmt::Write;
+
+use xycut_plus_plus::{XYCutConfig, XYCutPlusPlus};
+
+mod common;
+
+/// One line per element in reading order: rank, id, label and box
+fn render(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    let (page, elements) = common::load_page(&common::read_json(&path));
+    let order = XYCutPlusPlus::new(XYCutConfig::default())
+        .compute_order(&elements, page.0, page.1, page.2, page.3);
+
+    let mut rendered = String::new();
+    for (rank, id) in order.iter().enumerate() {
+        let e = elements.iter().find(|e| e.id == *id).expect("ordered id");
+        let label = format!("{:?}", e.label);
+        writeln!(
+            rendered,
+            "{rank:>2}  #{id:<3} {label:<15} [{}, {}, {}, {}]",
+            e.x1, e.y1, e.x2, e.y2
+        )
+        .unwrap();
+    }
+    rendered
+}
+
+#[test]
+fn two_column_paper() {
+    insta::assert_snapshot!(render("two_column_paper"));
+}
+
+#[test]
+fn newspaper() {
+    insta::assert_snapshot!(render("newspaper"));
+}
+
+#[test]
+fn form() {
+    insta::assert_snapshot!(render("form"));
+}
+
+#[test]
+fn magazine() {
+    insta::assert_snapshot!(render("magazine"));
+}
+
+#[test]
+fn equations() {
+    insta::assert_snapshot!(render("equations"));
+}
+
+#[test]
+fn negative_origin() {
+    insta::assert_snapshot!(render("negative_origin"));
+}
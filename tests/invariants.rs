@@ -0,0 +1,770 @@
+//! Property-based invariants of `compute_order`
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+use xycut_plus_plus::modes::poster::detect_poster_clusters;
+use xycut_plus_plus::modes::receipt::detect_item_amount_pairs;
+use xycut_plus_plus::modes::{
+    CheckboxConfig, PosterConfig, PosterPath, ReceiptConfig, TableConfig,
+};
+use xycut_plus_plus::preprocess::{
+    bin_tiles, detect_figure_groups, detect_lists, detect_table_clusters, snap_edges,
+};
+use xycut_plus_plus::{
+    fuse, merge_sources, validate_order, Algorithm, CutPreference, DensityEstimator, Detections,
+    DuplicateIds, Element, FusionConfig, InsertionOrder, LabelPriorities, OrderCache,
+    OrderingObserver, SemanticLabel, UnanchoredError, UnanchoredPolicy, UnanchoredReason,
+    XYCutConfig, XYCutPlusPlus, Zone,
+};
+
+const PAGE_WIDTH: f32 = 1000.0;
+const PAGE_HEIGHT: f32 = 1400.0;
+
+fn label() -> impl Strategy<Value = SemanticLabel> {
+    prop_oneof![
+        Just(SemanticLabel::CrossLayout),
+        Just(SemanticLabel::HorizontalTitle),
+        Just(SemanticLabel::VerticalTitle),
+        Just(SemanticLabel::Vision),
+        Just(SemanticLabel::Formula),
+        Just(SemanticLabel::Regular),
+    ]
+}
+
+/// Pages of up to 24 boxes with integer coordinates inside the page
+fn page() -> impl Strategy<Value = Vec<Element>> {
+    let boxes = (0u16..900, 0u16..1300, 5u16..300, 5u16..200, label());
+    prop::collection::vec(boxes, 0..24).prop_map(|boxes| {
+        boxes
+            .into_iter()
+            .enumerate()
+            .map(|(id, (x, y, w, h, label))| {
+                let x2 = (x + w).min(PAGE_WIDTH as u16);
+                let y2 = (y + h).min(PAGE_HEIGHT as u16);
+                Element::new(id, x as f32, y as f32, x2 as f32, y2 as f32, label)
+            })
+            .collect()
+    })
+}
+
+const ESTIMATORS: [DensityEstimator; 3] = [
+    DensityEstimator::AspectRatio,
+    DensityEstimator::AreaCoverage,
+    DensityEstimator::ColumnCount,
+];
+
+/// Records the region densities reported to the observer
+#[derive(Default)]
+struct Densities(Vec<(f32, Vec<usize>)>);
+
+impl OrderingObserver for Densities {
+    fn on_density(&mut self, density: f32, ids: &[usize]) {
+        self.0.push((density, ids.to_vec()));
+    }
+}
+
+/// Records the pre-mask partition reported to the observer
+#[derive(Default)]
+struct Partition {
+    regular: Vec<usize>,
+    masked: Vec<usize>,
+}
+
+impl OrderingObserver for Partition {
+    fn on_partition(&mut self, regular: &[usize], masked: &[usize]) {
+        self.regular = regular.to_vec();
+        self.masked = masked.to_vec();
+    }
+}
+
+/// Position of each id in `order`
+fn positions(order: &[usize]) -> HashMap<usize, usize> {
+    order.iter().enumerate().map(|(i, &id)| (id, i)).collect()
+}
+
+fn order(elements: &[Element], config: XYCutConfig, page: (f32, f32, f32, f32)) -> Vec<usize> {
+    XYCutPlusPlus::new(config).compute_order(elements, page.0, page.1, page.2, page.3)
+}
+
+proptest! {
+    #[test]
+    fn order_is_a_permutation(elements in page()) {
+        let order = order(&elements, XYCutConfig::default(), (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        prop_assert_eq!(validate_order(&elements, &order), Ok(()));
+    }
+
+    #[test]
+    fn order_is_translation_invariant(elements in page(), dx in 0u16..2000, dy in 0u16..2000) {
+        let (dx, dy) = (dx as f32, dy as f32);
+        let moved: Vec<Element> = elements
+            .iter()
+            .map(|e| Element { x1: e.x1 + dx, y1: e.y1 + dy, x2: e.x2 + dx, y2: e.y2 + dy, ..e.clone() })
+            .collect();
+        prop_assert_eq!(
+            order(&elements, XYCutConfig::default(), (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT)),
+            order(&moved, XYCutConfig::default(), (dx, dy, PAGE_WIDTH + dx, PAGE_HEIGHT + dy))
+        );
+    }
+
+    #[test]
+    fn cuts_are_scale_invariant(elements in page(), exponent in 1u32..3) {
+        // Scaling by a power of two keeps every coordinate exact, and pixel-valued
+        // thresholds scale with the page. Only the cutting stage is compared: the
+        // paper's insertion distance mixes quadratic and linear terms, so masked
+        // element placement is not scale-invariant by design.
+        let k = 2u32.pow(exponent) as f32;
+        let scaled: Vec<Element> = elements
+            .iter()
+            .map(|e| Element { x1: e.x1 * k, y1: e.y1 * k, x2: e.x2 * k, y2: e.y2 * k, ..e.clone() })
+            .collect();
+        let classic = XYCutConfig {
+            algorithm: Algorithm::ClassicXYCut,
+            ..XYCutConfig::default()
+        };
+        let config = XYCutConfig {
+            min_cut_threshold: classic.min_cut_threshold * k,
+            histogram_resolution_scale: classic.histogram_resolution_scale / k,
+            same_row_tolerance: classic.same_row_tolerance * k,
+            ..classic.clone()
+        };
+        prop_assert_eq!(
+            order(&elements, classic, (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT)),
+            order(&scaled, config, (0.0, 0.0, PAGE_WIDTH * k, PAGE_HEIGHT * k))
+        );
+    }
+
+    #[test]
+    fn adaptive_row_tolerance_needs_no_retuning(elements in page(), exponent in 1u32..3) {
+        let k = 2u32.pow(exponent) as f32;
+        let scaled: Vec<Element> = elements
+            .iter()
+            .map(|e| Element { x1: e.x1 * k, y1: e.y1 * k, x2: e.x2 * k, y2: e.y2 * k, ..e.clone() })
+            .collect();
+        let adaptive = XYCutConfig {
+            algorithm: Algorithm::ClassicXYCut,
+            adaptive_row_tolerance: Some(0.5),
+            ..XYCutConfig::default()
+        };
+        let config = XYCutConfig {
+            min_cut_threshold: adaptive.min_cut_threshold * k,
+            histogram_resolution_scale: adaptive.histogram_resolution_scale / k,
+            ..adaptive.clone()
+        };
+        prop_assert_eq!(
+            order(&elements, adaptive, (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT)),
+            order(&scaled, config, (0.0, 0.0, PAGE_WIDTH * k, PAGE_HEIGHT * k))
+        );
+    }
+
+    #[test]
+    fn order_ignores_input_order(
+        (elements, shuffled) in page().prop_flat_map(|elements| {
+            (Just(elements.clone()), Just(elements).prop_shuffle())
+        })
+    ) {
+        prop_assert_eq!(
+            order(&elements, XYCutConfig::default(), (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT)),
+            order(&shuffled, XYCutConfig::default(), (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+        );
+    }
+
+    #[test]
+    fn fixed_point_ignores_subgrid_jitter(
+        (elements, jitter) in page().prop_flat_map(|elements| {
+            let n = elements.len();
+            (Just(elements), prop::collection::vec(-0.1f32..0.1, n * 4))
+        })
+    ) {
+        // A grid of 1/4 unit absorbs differences below half a step, such as the same
+        // coordinates computed by two platforms' float math
+        let jittered: Vec<Element> = elements
+            .iter()
+            .zip(jitter.chunks(4))
+            .map(|(e, d)| Element { x1: e.x1 + d[0], y1: e.y1 + d[1], x2: e.x2 + d[2], y2: e.y2 + d[3], ..e.clone() })
+            .collect();
+        let fixed = XYCutConfig { fixed_point: Some(4), ..XYCutConfig::default() };
+        prop_assert_eq!(
+            order(&elements, fixed.clone(), (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT)),
+            order(&jittered, fixed, (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+        );
+    }
+
+    #[test]
+    fn density_threshold_decides_the_first_cut_axis(
+        elements in page(),
+        estimator in 0usize..3,
+        vertical in any::<bool>(),
+    ) {
+        // Past either end of the density range, every region takes the same axis first
+        let density = ESTIMATORS[estimator];
+        let (vertical_first_threshold, preference) = if vertical {
+            (f32::NEG_INFINITY, CutPreference::VerticalFirst)
+        } else {
+            (f32::INFINITY, CutPreference::HorizontalFirst)
+        };
+        let config = XYCutConfig { density, vertical_first_threshold, ..XYCutConfig::default() };
+        let forced = XYCutPlusPlus::new(XYCutConfig { density, ..XYCutConfig::default() })
+            .compute_order_with_cut_preference(
+                &elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT, &|_| preference,
+            );
+        prop_assert_eq!(order(&elements, config, (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT)), forced);
+    }
+
+    #[test]
+    fn reported_densities_are_the_ones_decided_on(elements in page(), estimator in 0usize..3) {
+        let density = ESTIMATORS[estimator];
+        let xycut = XYCutPlusPlus::new(XYCutConfig { density, ..XYCutConfig::default() });
+        let mut reported = Densities::default();
+        xycut.compute_order_observed(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT, &mut reported);
+        let decided = RefCell::new(Vec::new());
+        xycut.compute_order_with_cut_preference(
+            &elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT,
+            &|stats| {
+                decided.borrow_mut().push((stats.density, stats.ids.to_vec()));
+                CutPreference::Auto
+            },
+        );
+        prop_assert_eq!(&reported.0, &decided.into_inner());
+        for (value, _) in &reported.0 {
+            prop_assert!(value.is_finite() && *value >= 0.0);
+            if density == DensityEstimator::ColumnCount {
+                prop_assert_eq!(value.fract(), 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn cut_preference_overrides_only_the_axis(elements in page(), choice in 0usize..3) {
+        let preference =
+            [CutPreference::Auto, CutPreference::VerticalFirst, CutPreference::HorizontalFirst][choice];
+        let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+        let order = xycut.compute_order_with_cut_preference(
+            &elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT, &|_| preference,
+        );
+        prop_assert_eq!(validate_order(&elements, &order), Ok(()));
+        if preference == CutPreference::Auto {
+            prop_assert_eq!(order, xycut.compute_order(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        }
+    }
+
+    #[test]
+    fn masked_separators_keep_bands_apart(elements in page(), ratio in 0.05f32..0.3) {
+        // Regular elements centred above a separator are read before those centred below it
+        let xycut = XYCutPlusPlus::new(XYCutConfig { masked_separators: Some(ratio), ..XYCutConfig::default() });
+        let mut partition = Partition::default();
+        let order =
+            xycut.compute_order_observed(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT, &mut partition);
+        prop_assert_eq!(validate_order(&elements, &order), Ok(()));
+
+        let position = positions(&order);
+        let center = |id: usize| (elements[id].y1 + elements[id].y2) / 2.0;
+        let separators = partition
+            .masked
+            .iter()
+            .filter(|&&id| elements[id].x2 - elements[id].x1 >= ratio * PAGE_WIDTH);
+        for &separator in separators {
+            let y = center(separator);
+            for &above in partition.regular.iter().filter(|&&id| center(id) < y) {
+                for &below in partition.regular.iter().filter(|&&id| center(id) > y) {
+                    prop_assert!(position[&above] < position[&below]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unanchored_elements_are_reported_and_kept(elements in page(), geometric in any::<bool>()) {
+        // Text ranked first rules it out as an anchor for every masked element
+        let label_priorities = LabelPriorities { regular: 0, cross_layout: 3, ..LabelPriorities::default() };
+        let unanchored = if geometric { UnanchoredPolicy::Geometric } else { UnanchoredPolicy::Error };
+        let xycut = XYCutPlusPlus::new(XYCutConfig { label_priorities, unanchored, ..XYCutConfig::default() });
+        let report = xycut.compute_order_with_report(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+        prop_assert_eq!(validate_order(&elements, &report.order), Ok(()));
+        let expected = if geometric || report.unanchored.is_empty() {
+            Ok(report.order)
+        } else {
+            Err(UnanchoredError { unanchored: report.unanchored, order: report.order })
+        };
+        prop_assert_eq!(xycut.try_compute_order(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT), expected);
+    }
+
+    #[test]
+    fn relaxed_priorities_leave_no_element_outranked(elements in page()) {
+        let label_priorities = LabelPriorities { regular: 0, cross_layout: 3, ..LabelPriorities::default() };
+        let xycut = XYCutPlusPlus::new(XYCutConfig { label_priorities, relax_priorities: true, ..XYCutConfig::default() });
+        let report = xycut.compute_order_with_report(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+        prop_assert_eq!(validate_order(&elements, &report.order), Ok(()));
+        prop_assert!(report.unanchored.iter().all(|u| u.reason != UnanchoredReason::Outranked));
+    }
+
+    #[test]
+    fn dependency_insertion_only_moves_masked_elements(elements in page()) {
+        let bounds = (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+        let dependency = XYCutConfig { insertion_order: InsertionOrder::Dependency, ..XYCutConfig::default() };
+        let mut partition = Partition::default();
+        let reordered = XYCutPlusPlus::new(dependency)
+            .compute_order_observed(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT, &mut partition);
+        let rows = order(&elements, XYCutConfig::default(), bounds);
+        prop_assert_eq!(validate_order(&elements, &reordered), Ok(()));
+
+        // The cut order of the regular elements is left as it was
+        let regular = |order: &[usize]| -> Vec<usize> {
+            order.iter().copied().filter(|id| partition.regular.contains(id)).collect()
+        };
+        prop_assert_eq!(regular(&reordered), regular(&rows));
+        // A single masked element has nothing to be re-evaluated against
+        if partition.masked.len() <= 1 {
+            prop_assert_eq!(reordered, rows);
+        }
+    }
+
+    #[test]
+    fn unbounded_anchor_radius_changes_nothing(elements in page()) {
+        let bounds = (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+        let local = XYCutConfig { anchor_radius: Some(f32::MAX), ..XYCutConfig::default() };
+        prop_assert_eq!(order(&elements, local, bounds), order(&elements, XYCutConfig::default(), bounds));
+    }
+
+    #[test]
+    fn merged_sources_have_unique_ids(first in page(), second in page(), overlap in 0.0f32..1.5) {
+        let merged = merge_sources(&[&first[..], &second[..]], overlap, DuplicateIds::Reassign).unwrap();
+        prop_assert_eq!(merged.elements.len() + merged.fused.len(), first.len() + second.len());
+        let mut ids: Vec<usize> = merged.elements.iter().map(|e| e.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        prop_assert_eq!(ids.len(), merged.elements.len());
+    }
+
+    #[test]
+    fn fusion_keeps_or_maps_every_box(layout in page(), lines in page(), trust in 0.0f32..1.0) {
+        let detections = [
+            Detections { elements: &layout, trust, labels: true },
+            Detections { elements: &lines, trust: 0.5, labels: false },
+        ];
+        let merged = fuse(&detections, &FusionConfig::default()).unwrap();
+        prop_assert_eq!(merged.elements.len() + merged.fused.len(), layout.len() + lines.len());
+        let ids: Vec<usize> = merged.elements.iter().map(|e| e.id).collect();
+        for (_, id) in &merged.fused {
+            prop_assert!(ids.contains(id));
+        }
+    }
+
+    #[test]
+    fn cached_orders_match_computed_orders(elements in page(), max_bytes in 0usize..4096) {
+        let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+        let mut cache = OrderCache::new(max_bytes);
+        let expected = xycut.compute_order(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+        for _ in 0..2 {
+            let order = cache.compute_order(&xycut, &elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+            prop_assert_eq!(&order, &expected);
+        }
+        prop_assert!(cache.bytes() <= max_bytes);
+        prop_assert_eq!(cache.hits(), cache.len());
+    }
+
+    #[test]
+    fn quantization_ignores_jitter_below_half_a_step(
+        (elements, jitter) in page().prop_flat_map(|elements| {
+            let n = elements.len();
+            (Just(elements), prop::collection::vec(-0.45f32..0.45, n * 4))
+        }),
+        step in (0u32..5).prop_map(|k| (2 * k + 1) as f32),
+    ) {
+        // Integer coordinates lie at least half a unit from the rounding boundaries of
+        // an odd grid step, so re-detections moved by less than that round alike
+        let jittered: Vec<Element> = elements
+            .iter()
+            .zip(jitter.chunks(4))
+            .map(|(e, d)| Element { x1: e.x1 + d[0], y1: e.y1 + d[1], x2: e.x2 + d[2], y2: e.y2 + d[3], ..e.clone() })
+            .collect();
+        let quantized = XYCutConfig { quantization: Some(step), ..XYCutConfig::default() };
+        let order_of = |elements: &[Element]| order(elements, quantized.clone(), (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        let expected = order_of(&elements);
+        prop_assert_eq!(validate_order(&elements, &expected), Ok(()));
+        prop_assert_eq!(order_of(&jittered), expected);
+    }
+
+    #[test]
+    fn snapped_edges_align_nearly_coincident_edges(
+        (elements, jitter) in page().prop_flat_map(|elements| {
+            let n = elements.len();
+            (Just(elements), prop::collection::vec(0.0f32..0.5, n * 4))
+        })
+    ) {
+        // Edges 4 units apart or more, each moved by less than the snapping distance
+        let edges = |e: &Element| [e.x1, e.y1, e.x2, e.y2];
+        let scaled: Vec<Element> = elements
+            .iter()
+            .map(|e| Element { x1: e.x1 * 4.0, y1: e.y1 * 4.0, x2: e.x2 * 4.0, y2: e.y2 * 4.0, ..e.clone() })
+            .collect();
+        let jittered: Vec<Element> = scaled
+            .iter()
+            .zip(jitter.chunks(4))
+            .map(|(e, d)| Element { x1: e.x1 + d[0], y1: e.y1 + d[1], x2: e.x2 + d[2], y2: e.y2 + d[3], ..e.clone() })
+            .collect();
+        let snapped = snap_edges(&jittered, 1.0);
+
+        // Edges that coincided before the jitter coincide again, the others stay apart
+        for (a, a_snapped) in scaled.iter().zip(&snapped) {
+            for (b, b_snapped) in scaled.iter().zip(&snapped) {
+                for edge in 0..4 {
+                    prop_assert_eq!(
+                        edges(a)[edge] == edges(b)[edge],
+                        edges(a_snapped)[edge] == edges(b_snapped)[edge]
+                    );
+                }
+            }
+            for (original, moved) in edges(a).iter().zip(edges(a_snapped)) {
+                prop_assert!((*original..=*original + 0.5).contains(&moved));
+            }
+        }
+
+        // Snapping a snapped page changes nothing
+        let config = XYCutConfig { snap_edges: Some(1.0), ..XYCutConfig::default() };
+        let bounds = (0.0, 0.0, PAGE_WIDTH * 4.0 + 0.5, PAGE_HEIGHT * 4.0 + 0.5);
+        prop_assert_eq!(
+            order(&jittered, config.clone(), bounds),
+            order(&snapped, config, bounds)
+        );
+    }
+
+    #[test]
+    fn pre_binned_tiles_are_read_as_runs(
+        boxes in prop::collection::vec((0u16..900, 0u16..1300, 5u16..30), 0..48),
+    ) {
+        // Equally wide text boxes, none wide enough to be left out of the tiles
+        let elements: Vec<Element> = boxes
+            .into_iter()
+            .enumerate()
+            .map(|(id, (x, y, h))| {
+                Element::new(id, x as f32, y as f32, x as f32 + 40.0, (y + h) as f32, SemanticLabel::Regular)
+            })
+            .collect();
+        let config = XYCutConfig { pre_binning: Some(0), ..XYCutConfig::default() };
+        let min_gap_bins = (config.min_cut_threshold * config.histogram_resolution_scale) as usize;
+        let gap = min_gap_bins as f32 / config.histogram_resolution_scale;
+        let order = order(&elements, config, (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        prop_assert_eq!(validate_order(&elements, &order), Ok(()));
+
+        let position = positions(&order);
+        for tile in bin_tiles(&elements, gap, |_| true) {
+            let mut run: Vec<usize> = tile.iter().map(|i| position[&elements[*i].id]).collect();
+            run.sort_unstable();
+            prop_assert_eq!(run[run.len() - 1] - run[0], run.len() - 1);
+        }
+    }
+
+    #[test]
+    fn streamed_order_matches_compute_order(elements in page(), classic in any::<bool>()) {
+        let algorithm = if classic { Algorithm::ClassicXYCut } else { Algorithm::XYCutPlusPlus };
+        let xycut = XYCutPlusPlus::new(XYCutConfig { algorithm, ..XYCutConfig::default() });
+        let streamed: Vec<usize> =
+            xycut.compute_order_iter(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT).collect();
+        prop_assert_eq!(streamed, xycut.compute_order(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+    }
+
+    #[test]
+    fn streamed_order_matches_compute_order_on_inverted_boxes(
+        mut elements in page(),
+        inverted in prop::collection::vec(any::<bool>(), 24),
+    ) {
+        // Corners swapped along x, as some detectors emit them
+        for (e, _) in elements.iter_mut().zip(&inverted).filter(|(_, &inverted)| inverted) {
+            (e.x1, e.x2) = (e.x2, e.x1);
+        }
+        let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+        let streamed: Vec<usize> =
+            xycut.compute_order_iter(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT).collect();
+        prop_assert_eq!(streamed, xycut.compute_order(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+    }
+
+    #[test]
+    fn confidence_rates_each_step_of_the_order(elements in page()) {
+        let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+        let (order, transitions) =
+            xycut.compute_order_with_confidence(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+        prop_assert_eq!(&order, &xycut.compute_order(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        prop_assert_eq!(transitions.len(), order.len().saturating_sub(1));
+        for (t, pair) in transitions.iter().zip(order.windows(2)) {
+            prop_assert_eq!((t.from, t.to), (pair[0], pair[1]));
+            prop_assert!((0.0..=1.0).contains(&t.confidence));
+        }
+    }
+
+    #[test]
+    fn document_orders_are_permutations(pages in prop::collection::vec(page(), 1..5)) {
+        let bounds = (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+        let inputs: Vec<_> = pages.iter().map(|page| (page.as_slice(), bounds)).collect();
+        let orders = XYCutPlusPlus::new(XYCutConfig::default()).compute_document_orders(&inputs);
+        for (page, order) in pages.iter().zip(&orders) {
+            prop_assert_eq!(validate_order(page, order), Ok(()));
+        }
+    }
+
+    #[test]
+    fn zoned_order_is_a_permutation(elements in page(), split in 100u16..1300) {
+        let split = split as f32;
+        let zones = [Zone::band(0.0, split, XYCutConfig {
+            min_cut_threshold: 5.0,
+            ..XYCutConfig::default()
+        })];
+        let order = XYCutPlusPlus::new(XYCutConfig::default())
+            .compute_order_zoned(&elements, &zones, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+        prop_assert_eq!(validate_order(&elements, &order), Ok(()));
+    }
+
+    #[test]
+    fn figure_groups_are_read_as_units(elements in page()) {
+        let grouped = XYCutConfig { group_figures: true, ..XYCutConfig::default() };
+        let order = order(&elements, grouped, (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        prop_assert_eq!(validate_order(&elements, &order), Ok(()));
+        for (_, members) in detect_figure_groups(&elements) {
+            let start = order.iter().position(|id| *id == members[0]).unwrap();
+            prop_assert_eq!(&order[start..start + members.len()], members.as_slice());
+        }
+    }
+
+    #[test]
+    fn slide_titles_lead_and_groups_stay_together(elements in page(), groups in 1usize..4) {
+        let shapes: Vec<Element> = elements
+            .iter()
+            .map(|e| Element {
+                z_index: Some((e.id % 4) as i32),
+                group: (e.id % 3 == 0).then_some(e.id % groups),
+                ..e.clone()
+            })
+            .collect();
+        let order = XYCutPlusPlus::new(XYCutConfig::default())
+            .compute_slide_order(&shapes, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+        prop_assert_eq!(validate_order(&shapes, &order), Ok(()));
+        let rank = |id: usize| order.iter().position(|o| *o == id).unwrap();
+        for group in 0..groups {
+            let ranks: Vec<usize> =
+                shapes.iter().filter(|e| e.group == Some(group)).map(|e| rank(e.id)).collect();
+            if let (Some(first), Some(last)) = (ranks.iter().min(), ranks.iter().max()) {
+                prop_assert_eq!(last - first + 1, ranks.len());
+            }
+        }
+        let ungrouped = || shapes.iter().filter(|e| e.group.is_none());
+        for title in ungrouped().filter(|e| e.label.is_title()) {
+            for other in ungrouped().filter(|e| !e.label.is_title()) {
+                prop_assert!(rank(title.id) < rank(other.id));
+            }
+        }
+    }
+
+    #[test]
+    fn receipt_items_precede_their_amounts(elements in page()) {
+        let receipt = ReceiptConfig::default();
+        let order = XYCutPlusPlus::new(XYCutConfig::receipt()).compute_receipt_order(&elements, &receipt);
+        prop_assert_eq!(validate_order(&elements, &order), Ok(()));
+        for (item, amount) in detect_item_amount_pairs(&elements, &receipt) {
+            let start = order.iter().position(|id| *id == item).unwrap();
+            prop_assert_eq!(order[start + 1], amount);
+        }
+    }
+
+    #[test]
+    fn posters_read_the_biggest_title_then_whole_clusters(elements in page(), spiral in any::<bool>()) {
+        let path = if spiral { PosterPath::Spiral } else { PosterPath::Columnar };
+        let poster = PosterConfig { path, ..PosterConfig::default() };
+        let order = XYCutPlusPlus::new(XYCutConfig::poster()).compute_poster_order(
+            &elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT, &poster,
+        );
+        prop_assert_eq!(validate_order(&elements, &order), Ok(()));
+        let area = |e: &Element| (e.x2 - e.x1) * (e.y2 - e.y1);
+        let biggest = elements
+            .iter()
+            .filter(|e| e.label.is_title())
+            .map(area)
+            .fold(None, |max: Option<f32>, a| Some(max.map_or(a, |m| m.max(a))));
+        let mut rest = elements.clone();
+        if let Some(biggest) = biggest {
+            let first = elements.iter().find(|e| e.id == order[0]).unwrap();
+            prop_assert!(first.label.is_title() && area(first) == biggest);
+            rest.retain(|e| e.id != order[0]);
+        }
+        for cluster in detect_poster_clusters(&rest, &poster) {
+            let start = order.iter().position(|id| cluster.contains(id)).unwrap();
+            let mut read = order[start..start + cluster.len()].to_vec();
+            let mut cluster = cluster;
+            read.sort_unstable();
+            cluster.sort_unstable();
+            prop_assert_eq!(read, cluster);
+        }
+    }
+
+    #[test]
+    fn parallel_columns_are_read_one_after_the_other(
+        rows in prop::collection::vec((20u16..80, 0u8..8), 1..12),
+        hinted in any::<bool>(),
+    ) {
+        let mut elements = Vec::new();
+        let mut y = 60.0;
+        for (row, &(height, jitter)) in rows.iter().enumerate() {
+            // Each paragraph level with its translation, ids interleaving the languages
+            for language in 0..2 {
+                let x = 60.0 + language as f32 * 480.0;
+                let top = y + (jitter * language as u8) as f32;
+                let mut e = Element::new(2 * row + language, x, top, x + 420.0, top + height as f32, SemanticLabel::Regular);
+                e.language = hinted.then_some(language);
+                elements.push(e);
+            }
+            y += height as f32 + 20.0;
+        }
+        let parallel = XYCutPlusPlus::new(XYCutConfig::default())
+            .compute_parallel_order(&elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT);
+        prop_assert_eq!(parallel.columns.len(), 2);
+        for (language, column) in parallel.columns.iter().enumerate() {
+            let expected: Vec<usize> = (0..rows.len()).map(|row| 2 * row + language).collect();
+            prop_assert_eq!(column, &expected);
+        }
+    }
+
+    #[test]
+    fn table_cells_are_read_row_major(
+        rows in 1usize..6,
+        columns in 2usize..6,
+        jitter in prop::collection::vec((0u8..6, 0u8..6), 36),
+    ) {
+        let mut elements = vec![Element::new(0, 40.0, 100.0, 960.0, 900.0, SemanticLabel::Vision)];
+        for row in 0..rows {
+            for col in 0..columns {
+                let (dx, dy) = jitter[row * 6 + col];
+                let (x, y) = (60.0 + col as f32 * 150.0 + dx as f32, 120.0 + row as f32 * 60.0 + dy as f32);
+                // Ids against the reading order, so the input order does not help
+                let id = 100 - row * columns - col;
+                elements.push(Element::new(id, x, y, x + 120.0, y + 30.0, SemanticLabel::Regular));
+            }
+        }
+        let table = XYCutPlusPlus::new(XYCutConfig::default()).compute_table_order(
+            &elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT, &TableConfig::default(),
+        );
+        let expected: Vec<usize> =
+            std::iter::once(0).chain((0..rows * columns).map(|i| 100 - i)).collect();
+        prop_assert_eq!(&table.order, &expected);
+        for i in 0..rows * columns {
+            prop_assert_eq!(table.cell(100 - i), Some((i / columns, i % columns)));
+        }
+    }
+
+    #[test]
+    fn checkbox_grids_are_read_row_major(
+        rows in 1usize..5,
+        columns in 2usize..5,
+        spacing in 120u16..260,
+        labels in prop::collection::vec(20u16..90, 20),
+    ) {
+        let mut elements = vec![Element::new(0, 50.0, 50.0, 950.0, 80.0, SemanticLabel::Regular)];
+        let mut expected = vec![0];
+        for row in 0..rows {
+            for col in 0..columns {
+                let (x, y) = (60.0 + (col * spacing as usize) as f32, 100.0 + row as f32 * 30.0);
+                // Ids against the reading order, so the input order does not help
+                let id = 100 - 2 * (row * columns + col);
+                let label = x + 22.0 + labels[row * 5 + col] as f32;
+                elements.push(Element::new(id, x, y, x + 14.0, y + 14.0, SemanticLabel::Regular));
+                elements.push(Element::new(id - 1, x + 22.0, y - 2.0, label, y + 16.0, SemanticLabel::Regular));
+                expected.extend([id, id - 1]);
+            }
+        }
+        let below = 100.0 + rows as f32 * 30.0 + 20.0;
+        elements.push(Element::new(1, 50.0, below, 950.0, below + 30.0, SemanticLabel::Regular));
+        expected.push(1);
+        let order = XYCutPlusPlus::new(XYCutConfig::default()).compute_checkbox_order(
+            &elements, 0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT, &CheckboxConfig::default(),
+        );
+        prop_assert_eq!(order, expected);
+    }
+
+    #[test]
+    fn list_items_are_read_top_to_bottom(elements in page()) {
+        let grouped = XYCutConfig { group_lists: true, ..XYCutConfig::default() };
+        let order = order(&elements, grouped, (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        prop_assert_eq!(validate_order(&elements, &order), Ok(()));
+        for items in detect_lists(&elements) {
+            let start = order.iter().position(|id| *id == items[0]).unwrap();
+            prop_assert_eq!(&order[start..start + items.len()], items.as_slice());
+        }
+    }
+
+    #[test]
+    fn table_clusters_are_read_as_units(elements in page()) {
+        let grouped = XYCutConfig { group_tables: true, ..XYCutConfig::default() };
+        let order = order(&elements, grouped, (0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT));
+        prop_assert_eq!(validate_order(&elements, &order), Ok(()));
+        for cells in detect_table_clusters(&elements) {
+            let start = order.iter().position(|id| *id == cells[0]).unwrap();
+            prop_assert_eq!(&order[start..start + cells.len()], cells.as_slice());
+        }
+    }
+
+    #[test]
+    fn stacked_blocks_read_top_to_bottom(
+        blocks in prop::collection::vec((20u16..120, 1u16..60), 1..12),
+        x in 0u16..200,
+        width in 100u16..800,
+    ) {
+        let mut y = 0.0;
+        let mut elements = Vec::new();
+        for (id, &(height, gap)) in blocks.iter().enumerate() {
+            let (x1, y1) = (x as f32, y);
+            elements.push(Element::new(id, x1, y1, x1 + width as f32, y1 + height as f32, SemanticLabel::Regular));
+            y += (height + gap) as f32;
+        }
+        let order = order(&elements, XYCutConfig::default(), (0.0, 0.0, PAGE_WIDTH, y.max(1.0)));
+        prop_assert_eq!(order, (0..blocks.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn unequal_columns_read_column_by_column(
+        left in prop::collection::vec((20u16..120, 20u16..60), 1..10),
+        right in prop::collection::vec((20u16..120, 0u16..10), 1..10),
+    ) {
+        let mut elements = Vec::new();
+        for (x1, blocks) in [(50.0, &left), (520.0, &right)] {
+            let mut y = 0.0;
+            for &(height, gap) in blocks {
+                let id = elements.len();
+                elements.push(Element::new(id, x1, y, x1 + 430.0, y + height as f32, SemanticLabel::Regular));
+                y += (height + gap) as f32;
+            }
+        }
+        let consistent = XYCutConfig { column_consistency: true, ..XYCutConfig::default() };
+        let order = order(&elements, consistent, (0.0, 0.0, PAGE_WIDTH, 2000.0));
+        prop_assert_eq!(order, (0..elements.len()).collect::<Vec<_>>());
+    }
+}
+
+proptest! {
+    // Histograms of pages in CAD units are capped at their maximum size, making each
+    // case far slower than a pixel page
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn cad_units_order_alike_at_any_origin(elements in page(), exponent in 10u32..14) {
+        // Pages in CAD units span millions of units, capping the histograms, and
+        // sit far from the origin; powers of two keep every coordinate exact
+        let k = 2u32.pow(exponent) as f32;
+        let offset = 2u32.pow(23) as f32;
+        let at = |origin: f32| -> Vec<Element> {
+            elements
+                .iter()
+                .map(|e| Element {
+                    x1: e.x1 * k + origin,
+                    y1: e.y1 * k + origin,
+                    x2: e.x2 * k + origin,
+                    y2: e.y2 * k + origin,
+                    ..e.clone()
+                })
+                .collect()
+        };
+        let (width, height) = (PAGE_WIDTH * k, PAGE_HEIGHT * k);
+        prop_assert_eq!(
+            order(&at(0.0), XYCutConfig::default(), (0.0, 0.0, width, height)),
+            order(&at(offset), XYCutConfig::default(), (offset, offset, width + offset, height + offset))
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! Fidelity to the paper's reference implementation
+//!
+//! Each `tests/parity/*.json` file is a layout page (see `tests/common`) with the
+//! `"reference_order": [id, ...]` the authors' Python implementation gives it.
+//! The harness prints the Kendall tau between that order and ours for every page
+//! (`cargo test --test parity -- --nocapture`) and fails when any page scores below
+//! `PARITY_MIN_TAU`, 0.95 unless set.
+
+use xycut_plus_plus::eval::kendall_tau;
+use xycut_plus_plus::{XYCutConfig, XYCutPlusPlus};
+
+mod common;
+
+/// Kendall tau every page must reach when `PARITY_MIN_TAU` is not set
+const DEFAULT_MIN_TAU: f32 = 0.95;
+
+#[test]
+fn parity_with_reference_implementation() {
+    let dir = format!("{}/tests/parity", env!("CARGO_MANIFEST_DIR"));
+    let mut paths: Vec<String> = std::fs::read_dir(&dir)
+        .unwrap_or_else(|e| panic!("{dir}: {e}"))
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| path.display().to_string())
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no reference pages in {dir}");
+
+    let min_tau: f32 = std::env::var("PARITY_MIN_TAU").map_or(DEFAULT_MIN_TAU, |tau| {
+        tau.parse().expect("PARITY_MIN_TAU must be a number")
+    });
+    let xycut = XYCutPlusPlus::new(XYCutConfig::default());
+    let mut taus = Vec::new();
+    let mut below = Vec::new();
+
+    for path in &paths {
+        let page = common::read_json(path);
+        let (bounds, elements) = common::load_page(&page);
+        let reference: Vec<usize> = page["reference_order"]
+            .as_array()
+            .unwrap_or_else(|| panic!("{path}: missing reference_order"))
+            .iter()
+            .map(|id| id.as_u64().expect("id") as usize)
+            .collect();
+
+        let order = xycut.compute_order(&elements, bounds.0, bounds.1, bounds.2, bounds.3);
+        let tau = kendall_tau(&order, &reference);
+        println!("{tau:>7.4}  {path}");
+        if tau < min_tau {
+            below.push(path.clone());
+        }
+        taus.push(tau);
+    }
+
+    let mean = taus.iter().sum::<f32>() / taus.len() as f32;
+    println!("{mean:>7.4}  mean over {} pages", taus.len());
+    assert!(below.is_empty(), "pages below tau {min_tau}: {below:?}");
+}
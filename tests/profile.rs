@@ -0,0 +1,28 @@
+//! `compute_order_profiled` with the counting allocator installed
+
+use xycut_plus_plus::profile::CountingAllocator;
+use xycut_plus_plus::{XYCutConfig, XYCutPlusPlus};
+
+mod common;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn profile_counts_allocations_and_keeps_the_order() {
+    let path = format!(
+        "{}/tests/fixtures/two_column_paper.json",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    let ((x_min, y_min, x_max, y_max), elements) = common::load_page(&common::read_json(&path));
+    let engine = XYCutPlusPlus::new(XYCutConfig::default());
+
+    let (order, profile) = engine.compute_order_profiled(&elements, x_min, y_min, x_max, y_max);
+
+    assert_eq!(
+        order,
+        engine.compute_order(&elements, x_min, y_min, x_max, y_max)
+    );
+    assert!(profile.allocations.is_some_and(|count| count > 0));
+    assert!(profile.cutting + profile.insertion + profile.pre_mask <= profile.total);
+}